@@ -1,7 +1,9 @@
 use super::*;
+use crate::num::{IntBase, Unsigned};
 use std::{
     cmp::Ordering,
     marker::PhantomData,
+    mem::swap,
     ops::{Add, Mul, Sub},
 };
 
@@ -50,6 +52,16 @@ pub trait LazyMapMonoid {
     }
 }
 
+/// Segment Tree Beats
+///
+/// An update which is not closed under composition is converted into an act for each node,
+/// and the node is descended when it cannot be converted.
+pub trait LazyMapMonoidBeats: LazyMapMonoid {
+    type Update;
+    /// `None` to descend into the children, must be `Some` for a single element
+    fn update_act(x: &Self::Agg, u: &Self::Update) -> Option<Self::Act>;
+}
+
 pub struct EmptyActLazy<M> {
     _marker: PhantomData<fn() -> M>,
 }
@@ -112,6 +124,32 @@ where
     }
 }
 
+/// Keeps the aggregation of the reversed sequence as well, so that range reverse works for any `L`
+pub struct ReversibleLazy<L> {
+    _marker: PhantomData<fn() -> L>,
+}
+impl<L> LazyMapMonoid for ReversibleLazy<L>
+where
+    L: LazyMapMonoid,
+{
+    type Key = L::Key;
+    type Agg = (L::Agg, L::Agg);
+    type Act = L::Act;
+    type AggMonoid = (L::AggMonoid, ReverseOperation<L::AggMonoid>);
+    type ActMonoid = L::ActMonoid;
+    type KeyAct = L::KeyAct;
+    fn single_agg(key: &Self::Key) -> Self::Agg {
+        let agg = L::single_agg(key);
+        (agg.clone(), agg)
+    }
+    fn toggle(x: &mut Self::Agg) {
+        swap(&mut x.0, &mut x.1);
+    }
+    fn act_agg(x: &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+        Some((L::act_agg(&x.0, a)?, L::act_agg(&x.1, a)?))
+    }
+}
+
 pub struct RangeSumRangeAdd<T> {
     _marker: PhantomData<fn() -> T>,
 }
@@ -280,7 +318,7 @@ pub struct RangeChminChmaxAdd<T> {
 }
 impl<T> RangeChminChmaxAdd<T>
 where
-    T: Zero + Bounded,
+    T: Copy + Zero + Bounded,
 {
     pub fn chmin(x: T) -> Self {
         Self {
@@ -303,6 +341,13 @@ where
             bias: x,
         }
     }
+    pub fn update(x: T) -> Self {
+        Self {
+            lb: x,
+            ub: x,
+            bias: T::zero(),
+        }
+    }
 }
 impl<T> Magma for RangeChminChmaxAdd<T>
 where
@@ -360,8 +405,8 @@ where
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RangeSumRangeChminChmaxAdd<T> {
-    min: T,
-    max: T,
+    pub min: T,
+    pub max: T,
     min2: T,
     max2: T,
     pub sum: T,
@@ -556,3 +601,730 @@ where
         })
     }
 }
+
+/// Updates for [`RangeSumRangeChminChmaxAdd`] with Segment Tree Beats
+///
+/// `Div` and `Mod` take positive divisors and round toward negative infinity,
+/// `Sqrt` expects non-negative elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeBeatsUpdate<T> {
+    Act(RangeChminChmaxAdd<T>),
+    Div(T),
+    Mod(T),
+    Sqrt,
+}
+
+impl<T> LazyMapMonoidBeats for RangeSumRangeChminChmaxAdd<T>
+where
+    T: IntBase,
+{
+    type Update = RangeBeatsUpdate<T>;
+    fn update_act(x: &Self::Agg, u: &Self::Update) -> Option<Self::Act> {
+        let f = |a: T| match *u {
+            RangeBeatsUpdate::Act(act) => RangeChminChmaxAdd::act(&a, &act),
+            RangeBeatsUpdate::Div(d) => a.div_euclid(d),
+            RangeBeatsUpdate::Mod(m) => a.rem_euclid(m),
+            RangeBeatsUpdate::Sqrt => a.isqrt(),
+        };
+        Some(match *u {
+            RangeBeatsUpdate::Act(act) => act,
+            _ if x.size.is_zero() => Self::act_unit(),
+            _ if x.min == x.max => RangeChminChmaxAdd::update(f(x.min)),
+            RangeBeatsUpdate::Mod(m) if T::zero() <= x.min && x.max < m => Self::act_unit(),
+            // x -> f(x) - x is non-increasing for division and square root
+            RangeBeatsUpdate::Div(_) | RangeBeatsUpdate::Sqrt
+                if f(x.min) - x.min == f(x.max) - x.max =>
+            {
+                RangeChminChmaxAdd::add(f(x.min) - x.min)
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// lcm, `None` if it exceeds the maximum
+fn checked_lcm<T>(a: Option<T>, b: Option<T>) -> Option<T>
+where
+    T: Unsigned,
+{
+    let (a, b) = (a?, b?);
+    if a.is_zero() || b.is_zero() {
+        return Some(T::zero());
+    }
+    let a = a / a.gcd(b);
+    (a <= T::maximum() / b).then(|| a * b)
+}
+
+/// whether `l` divides `g`
+fn divides<T>(l: Option<T>, g: T) -> bool
+where
+    T: Unsigned,
+{
+    l.is_some_and(|l| {
+        if l.is_zero() {
+            g.is_zero()
+        } else {
+            (g % l).is_zero()
+        }
+    })
+}
+
+/// Range sum and max with range update and range gcd
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSumMaxRangeUpdateGcd<T> {
+    pub sum: T,
+    pub max: T,
+    min: T,
+    /// `None` if it exceeds the maximum
+    lcm: Option<T>,
+    size: T,
+}
+impl<T> RangeSumMaxRangeUpdateGcd<T>
+where
+    T: Unsigned,
+{
+    pub fn single(key: T, size: T) -> Self {
+        Self {
+            sum: key * size,
+            max: key,
+            min: key,
+            lcm: Some(key),
+            size,
+        }
+    }
+}
+impl<T> Magma for RangeSumMaxRangeUpdateGcd<T>
+where
+    T: Unsigned,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        Self {
+            sum: x.sum + y.sum,
+            max: x.max.max(y.max),
+            min: x.min.min(y.min),
+            lcm: checked_lcm(x.lcm, y.lcm),
+            size: x.size + y.size,
+        }
+    }
+}
+impl<T> Associative for RangeSumMaxRangeUpdateGcd<T> where T: Unsigned {}
+impl<T> Unital for RangeSumMaxRangeUpdateGcd<T>
+where
+    T: Unsigned,
+{
+    fn unit() -> Self::T {
+        Self {
+            sum: T::zero(),
+            max: T::minimum(),
+            min: T::maximum(),
+            lcm: Some(T::one()),
+            size: T::zero(),
+        }
+    }
+}
+impl<T> LazyMapMonoid for RangeSumMaxRangeUpdateGcd<T>
+where
+    T: Unsigned,
+{
+    type Key = T;
+    type Agg = Self;
+    type Act = Option<T>;
+    type AggMonoid = Self;
+    type ActMonoid = LastOperation<T>;
+    type KeyAct = UpdateAct<T>;
+    fn single_agg(&key: &Self::Key) -> Self::Agg {
+        Self::single(key, T::one())
+    }
+    fn act_agg(x: &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+        Some(match *a {
+            Some(a) if !x.size.is_zero() => Self::single(a, x.size),
+            _ => x.clone(),
+        })
+    }
+}
+/// Updates for [`RangeSumMaxRangeUpdateGcd`], `Gcd(g)` replaces each element `a` by `gcd(a, g)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOrGcd<T> {
+    Update(T),
+    Gcd(T),
+}
+impl<T> LazyMapMonoidBeats for RangeSumMaxRangeUpdateGcd<T>
+where
+    T: Unsigned,
+{
+    type Update = UpdateOrGcd<T>;
+    fn update_act(x: &Self::Agg, u: &Self::Update) -> Option<Self::Act> {
+        match *u {
+            UpdateOrGcd::Update(a) => Some(Some(a)),
+            UpdateOrGcd::Gcd(g) => {
+                if x.size.is_zero() {
+                    Some(None)
+                } else if x.min == x.max {
+                    Some(Some(x.min.gcd(g)))
+                } else if divides(x.lcm, g) {
+                    // every element divides g
+                    Some(None)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Range sum and max with range chmin and range gcd
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSumMaxRangeChminGcd<T> {
+    pub sum: T,
+    pub max: T,
+    max2: T,
+    n_max: T,
+    /// lcm of the elements except the maximums, `None` if it exceeds the maximum
+    lcm: Option<T>,
+    size: T,
+}
+impl<T> RangeSumMaxRangeChminGcd<T>
+where
+    T: Unsigned,
+{
+    pub fn single(key: T, size: T) -> Self {
+        Self {
+            sum: key * size,
+            max: key,
+            max2: T::minimum(),
+            n_max: size,
+            lcm: Some(T::one()),
+            size,
+        }
+    }
+}
+impl<T> Magma for RangeSumMaxRangeChminGcd<T>
+where
+    T: Unsigned,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        if x.size.is_zero() {
+            return y.clone();
+        }
+        if y.size.is_zero() {
+            return x.clone();
+        }
+        let (max2, n_max, lcm) = match x.max.cmp(&y.max) {
+            Ordering::Less => (
+                x.max.max(y.max2),
+                y.n_max,
+                checked_lcm(checked_lcm(x.lcm, Some(x.max)), y.lcm),
+            ),
+            Ordering::Equal => (
+                x.max2.max(y.max2),
+                x.n_max + y.n_max,
+                checked_lcm(x.lcm, y.lcm),
+            ),
+            Ordering::Greater => (
+                x.max2.max(y.max),
+                x.n_max,
+                checked_lcm(x.lcm, checked_lcm(y.lcm, Some(y.max))),
+            ),
+        };
+        Self {
+            sum: x.sum + y.sum,
+            max: x.max.max(y.max),
+            max2,
+            n_max,
+            lcm,
+            size: x.size + y.size,
+        }
+    }
+}
+impl<T> Associative for RangeSumMaxRangeChminGcd<T> where T: Unsigned {}
+impl<T> Unital for RangeSumMaxRangeChminGcd<T>
+where
+    T: Unsigned,
+{
+    fn unit() -> Self::T {
+        Self {
+            sum: T::zero(),
+            max: T::minimum(),
+            max2: T::minimum(),
+            n_max: T::zero(),
+            lcm: Some(T::one()),
+            size: T::zero(),
+        }
+    }
+}
+impl<T> LazyMapMonoid for RangeSumMaxRangeChminGcd<T>
+where
+    T: Unsigned,
+{
+    type Key = T;
+    type Agg = Self;
+    type Act = RangeChminChmaxAdd<T>;
+    type AggMonoid = Self;
+    type ActMonoid = RangeChminChmaxAdd<T>;
+    type KeyAct = RangeChminChmaxAdd<T>;
+    fn single_agg(&key: &Self::Key) -> Self::Agg {
+        Self::single(key, T::one())
+    }
+    fn act_agg(x: &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+        Some(if Self::is_act_unit(a) || x.size.is_zero() {
+            x.clone()
+        } else if x.n_max == x.size || a.lb == a.ub {
+            Self::single(RangeChminChmaxAdd::act(&x.max, a), x.size)
+        } else if a.lb == T::minimum() && a.bias.is_zero() && x.max2 < a.ub {
+            let mut x = x.clone();
+            let max = x.max.min(a.ub);
+            x.sum -= (x.max - max) * x.n_max;
+            x.max = max;
+            x
+        } else {
+            return None;
+        })
+    }
+}
+/// Updates for [`RangeSumMaxRangeChminGcd`], `Gcd(g)` replaces each element `a` by `gcd(a, g)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChminOrGcd<T> {
+    Chmin(T),
+    Gcd(T),
+}
+impl<T> LazyMapMonoidBeats for RangeSumMaxRangeChminGcd<T>
+where
+    T: Unsigned,
+{
+    type Update = ChminOrGcd<T>;
+    fn update_act(x: &Self::Agg, u: &Self::Update) -> Option<Self::Act> {
+        match *u {
+            ChminOrGcd::Chmin(c) => Some(RangeChminChmaxAdd::chmin(c)),
+            ChminOrGcd::Gcd(g) => {
+                if x.size.is_zero() {
+                    Some(Self::act_unit())
+                } else if x.n_max == x.size {
+                    Some(RangeChminChmaxAdd::update(x.max.gcd(g)))
+                } else if !divides(x.lcm, g) {
+                    None
+                } else if divides(Some(x.max), g) {
+                    // every element divides g
+                    Some(Self::act_unit())
+                } else if x.max.gcd(g) > x.max2 {
+                    // only the maximums change and stay the maximums
+                    Some(RangeChminChmaxAdd::chmin(x.max.gcd(g)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Range add with historic information
+///
+/// Each element `a` with the historic sum `h` is mapped to `a + add` and `h + time * a + bias`,
+/// and the values during the act range over `a + min_add ..= a + max_add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoricAdd<T> {
+    add: T,
+    time: T,
+    bias: T,
+    max_add: T,
+    min_add: T,
+}
+impl<T> HistoricAdd<T>
+where
+    T: Copy + Zero + One + Ord,
+{
+    pub fn add(x: T) -> Self {
+        Self {
+            add: x,
+            time: T::zero(),
+            bias: T::zero(),
+            max_add: x.max(T::zero()),
+            min_add: x.min(T::zero()),
+        }
+    }
+    /// Add the current value into the historic sum.
+    pub fn record() -> Self {
+        Self {
+            add: T::zero(),
+            time: T::one(),
+            bias: T::zero(),
+            max_add: T::zero(),
+            min_add: T::zero(),
+        }
+    }
+}
+impl<T> Magma for HistoricAdd<T>
+where
+    T: Copy + Zero + One + Ord + Add<Output = T> + Mul<Output = T>,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        Self {
+            add: x.add + y.add,
+            time: x.time + y.time,
+            bias: x.bias + y.time * x.add + y.bias,
+            max_add: x.max_add.max(x.add + y.max_add),
+            min_add: x.min_add.min(x.add + y.min_add),
+        }
+    }
+}
+impl<T> Associative for HistoricAdd<T> where
+    T: Copy + Zero + One + Ord + Add<Output = T> + Mul<Output = T>
+{
+}
+impl<T> Unital for HistoricAdd<T>
+where
+    T: Copy + Zero + One + Ord + Add<Output = T> + Mul<Output = T>,
+{
+    fn unit() -> Self::T {
+        Self {
+            add: T::zero(),
+            time: T::zero(),
+            bias: T::zero(),
+            max_add: T::zero(),
+            min_add: T::zero(),
+        }
+    }
+}
+impl<T> MonoidAct for HistoricAdd<T>
+where
+    T: Copy + Zero + One + Ord + Add<Output = T> + Mul<Output = T>,
+{
+    type Key = T;
+    type Act = Self;
+    type ActMonoid = Self;
+    fn act(x: &Self::Key, a: &Self::Act) -> Self::Key {
+        *x + a.add
+    }
+}
+
+/// Range sum, max and min with their historic values, and range add
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeHistoricRangeAdd<T> {
+    pub sum: T,
+    pub historic_sum: T,
+    pub max: T,
+    pub historic_max: T,
+    pub min: T,
+    pub historic_min: T,
+    size: T,
+}
+impl<T> RangeHistoricRangeAdd<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    pub fn single(key: T, size: T) -> Self {
+        Self {
+            sum: key * size,
+            historic_sum: T::zero(),
+            max: key,
+            historic_max: key,
+            min: key,
+            historic_min: key,
+            size,
+        }
+    }
+}
+impl<T> Magma for RangeHistoricRangeAdd<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        Self {
+            sum: x.sum + y.sum,
+            historic_sum: x.historic_sum + y.historic_sum,
+            max: x.max.max(y.max),
+            historic_max: x.historic_max.max(y.historic_max),
+            min: x.min.min(y.min),
+            historic_min: x.historic_min.min(y.historic_min),
+            size: x.size + y.size,
+        }
+    }
+}
+impl<T> Associative for RangeHistoricRangeAdd<T> where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>
+{
+}
+impl<T> Unital for RangeHistoricRangeAdd<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    fn unit() -> Self::T {
+        Self {
+            sum: T::zero(),
+            historic_sum: T::zero(),
+            max: T::minimum(),
+            historic_max: T::minimum(),
+            min: T::maximum(),
+            historic_min: T::maximum(),
+            size: T::zero(),
+        }
+    }
+}
+impl<T> LazyMapMonoid for RangeHistoricRangeAdd<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    type Key = T;
+    type Agg = Self;
+    type Act = HistoricAdd<T>;
+    type AggMonoid = Self;
+    type ActMonoid = HistoricAdd<T>;
+    type KeyAct = HistoricAdd<T>;
+    fn single_agg(&key: &Self::Key) -> Self::Agg {
+        Self::single(key, T::one())
+    }
+    fn act_agg(x: &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+        Some(if Self::is_act_unit(a) || x.size.is_zero() {
+            x.clone()
+        } else {
+            Self {
+                sum: x.sum + a.add * x.size,
+                historic_sum: x.historic_sum + a.time * x.sum + a.bias * x.size,
+                max: x.max + a.add,
+                historic_max: x.historic_max.max(x.max + a.max_add),
+                min: x.min + a.add,
+                historic_min: x.historic_min.min(x.min + a.min_add),
+                size: x.size,
+            }
+        })
+    }
+}
+
+/// [`HistoricAdd`] applied separately to the maximums and the other elements
+///
+/// The maximums are the elements equal to `threshold` before the act if the two acts differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoricChminAdd<T> {
+    max: HistoricAdd<T>,
+    other: HistoricAdd<T>,
+    threshold: T,
+}
+impl<T> HistoricChminAdd<T>
+where
+    T: IntBase,
+{
+    fn is_split(&self) -> bool {
+        self.max != self.other
+    }
+}
+impl<T> From<HistoricAdd<T>> for HistoricChminAdd<T>
+where
+    T: IntBase,
+{
+    fn from(a: HistoricAdd<T>) -> Self {
+        Self {
+            max: a,
+            other: a,
+            threshold: T::minimum(),
+        }
+    }
+}
+impl<T> Magma for HistoricChminAdd<T>
+where
+    T: IntBase,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        let op = |a: &HistoricAdd<T>, b: &HistoricAdd<T>| <HistoricAdd<T> as Magma>::operate(a, b);
+        // the maximums of `x` stay above the others, and `y.threshold` is at least the maximum after `x`
+        let (max, threshold) = if !y.is_split() {
+            (op(&x.max, &y.max), x.threshold)
+        } else if !x.is_split() {
+            (op(&x.max, &y.max), y.threshold - x.max.add)
+        } else {
+            match y.threshold.cmp(&(x.threshold + x.max.add)) {
+                Ordering::Equal => (op(&x.max, &y.max), x.threshold),
+                Ordering::Greater => (op(&x.max, &y.other), x.threshold),
+                // the maximums of `x` do not appear
+                Ordering::Less => (op(&x.other, &y.max), y.threshold - x.other.add),
+            }
+        };
+        let other = op(&x.other, &y.other);
+        if max == other {
+            other.into()
+        } else {
+            Self {
+                max,
+                other,
+                threshold,
+            }
+        }
+    }
+}
+impl<T> Associative for HistoricChminAdd<T> where T: IntBase {}
+impl<T> Unital for HistoricChminAdd<T>
+where
+    T: IntBase,
+{
+    fn unit() -> Self::T {
+        <HistoricAdd<T> as Unital>::unit().into()
+    }
+}
+impl<T> MonoidAct for HistoricChminAdd<T>
+where
+    T: IntBase,
+{
+    type Key = T;
+    type Act = Self;
+    type ActMonoid = Self;
+    fn act(x: &Self::Key, a: &Self::Act) -> Self::Key {
+        if a.is_split() && *x == a.threshold {
+            *x + a.max.add
+        } else {
+            *x + a.other.add
+        }
+    }
+}
+
+/// Range sum and max with their historic values, and range chmin and range add
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeHistoricRangeChminAdd<T> {
+    pub sum: T,
+    pub historic_sum: T,
+    pub max: T,
+    pub historic_max: T,
+    max2: T,
+    n_max: T,
+    size: T,
+}
+impl<T> RangeHistoricRangeChminAdd<T>
+where
+    T: IntBase,
+{
+    pub fn single(key: T, size: T) -> Self {
+        Self {
+            sum: key * size,
+            historic_sum: T::zero(),
+            max: key,
+            historic_max: key,
+            max2: T::minimum(),
+            n_max: size,
+            size,
+        }
+    }
+}
+impl<T> Magma for RangeHistoricRangeChminAdd<T>
+where
+    T: IntBase,
+{
+    type T = Self;
+    fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+        if x.size.is_zero() {
+            return y.clone();
+        }
+        if y.size.is_zero() {
+            return x.clone();
+        }
+        let (max2, n_max) = match x.max.cmp(&y.max) {
+            Ordering::Less => (x.max.max(y.max2), y.n_max),
+            Ordering::Equal => (x.max2.max(y.max2), x.n_max + y.n_max),
+            Ordering::Greater => (x.max2.max(y.max), x.n_max),
+        };
+        Self {
+            sum: x.sum + y.sum,
+            historic_sum: x.historic_sum + y.historic_sum,
+            max: x.max.max(y.max),
+            historic_max: x.historic_max.max(y.historic_max),
+            max2,
+            n_max,
+            size: x.size + y.size,
+        }
+    }
+}
+impl<T> Associative for RangeHistoricRangeChminAdd<T> where T: IntBase {}
+impl<T> Unital for RangeHistoricRangeChminAdd<T>
+where
+    T: IntBase,
+{
+    fn unit() -> Self::T {
+        Self {
+            sum: T::zero(),
+            historic_sum: T::zero(),
+            max: T::minimum(),
+            historic_max: T::minimum(),
+            max2: T::minimum(),
+            n_max: T::zero(),
+            size: T::zero(),
+        }
+    }
+}
+impl<T> LazyMapMonoid for RangeHistoricRangeChminAdd<T>
+where
+    T: IntBase,
+{
+    type Key = T;
+    type Agg = Self;
+    type Act = HistoricChminAdd<T>;
+    type AggMonoid = Self;
+    type ActMonoid = HistoricChminAdd<T>;
+    type KeyAct = HistoricChminAdd<T>;
+    fn single_agg(&key: &Self::Key) -> Self::Agg {
+        Self::single(key, T::one())
+    }
+    fn act_agg(x: &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+        if Self::is_act_unit(a) || x.size.is_zero() {
+            return Some(x.clone());
+        }
+        let m = if a.is_split() && x.max == a.threshold {
+            &a.max
+        } else {
+            &a.other
+        };
+        let o = &a.other;
+        let n_other = x.size - x.n_max;
+        let sum_max = x.max * x.n_max;
+        let sum_other = x.sum - sum_max;
+        let mut historic_max = x.historic_max.max(x.max + m.max_add);
+        let mut max2 = x.max2;
+        if !n_other.is_zero() {
+            historic_max = historic_max.max(x.max2 + o.max_add);
+            max2 = x.max2 + o.add;
+        }
+        Some(Self {
+            sum: (x.max + m.add) * x.n_max + sum_other + o.add * n_other,
+            historic_sum: x.historic_sum
+                + m.time * sum_max
+                + m.bias * x.n_max
+                + o.time * sum_other
+                + o.bias * n_other,
+            max: x.max + m.add,
+            historic_max,
+            max2,
+            n_max: x.n_max,
+            size: x.size,
+        })
+    }
+}
+/// Updates for [`RangeHistoricRangeChminAdd`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChminOrHistoricAdd<T> {
+    Chmin(T),
+    Act(HistoricAdd<T>),
+}
+impl<T> LazyMapMonoidBeats for RangeHistoricRangeChminAdd<T>
+where
+    T: IntBase,
+{
+    type Update = ChminOrHistoricAdd<T>;
+    fn update_act(x: &Self::Agg, u: &Self::Update) -> Option<Self::Act> {
+        match *u {
+            ChminOrHistoricAdd::Act(a) => Some(a.into()),
+            ChminOrHistoricAdd::Chmin(c) => {
+                if x.size.is_zero() || x.max <= c {
+                    Some(Self::act_unit())
+                } else if x.n_max == x.size || x.max2 < c {
+                    Some(HistoricChminAdd {
+                        max: HistoricAdd::add(c - x.max),
+                        other: <HistoricAdd<T> as Unital>::unit(),
+                        threshold: x.max,
+                    })
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
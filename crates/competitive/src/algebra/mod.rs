@@ -30,7 +30,9 @@ mod action;
             "AdditiveOperation",
             "MaxOperation",
             "MinOperation",
-            "bounded"
+            "ReverseOperation",
+            "bounded",
+            "integer"
         )
     )
 )]
@@ -1,3 +1,5 @@
+use super::UndirectedSparseGraph;
+
 /// solve with Mo's algorithm
 ///
 /// arg:
@@ -127,9 +129,472 @@ macro_rules! mo_algorithm {
     }};
 }
 
+/// Sorts queries `[l, r)` along the Hilbert curve and returns the visiting order
+pub fn hilbert_order(lr: &[(usize, usize)]) -> Vec<usize> {
+    let maxv = lr.iter().map(|&(l, r)| l.max(r)).max().unwrap_or_default() as u64;
+    let log = (u64::BITS - maxv.leading_zeros()).max(1);
+    let keys: Vec<u64> = lr
+        .iter()
+        .map(|&(l, r)| hilbert_index(l as u64, r as u64, log))
+        .collect();
+    let mut idx: Vec<usize> = (0..lr.len()).collect();
+    idx.sort_unstable_by_key(|&i| keys[i]);
+    idx
+}
+
+fn hilbert_index(mut x: u64, mut y: u64, log: u32) -> u64 {
+    let mask = (1u64 << log) - 1;
+    let mut d = 0u64;
+    for k in (0..log).rev() {
+        let s = 1u64 << k;
+        let rx = x & s != 0;
+        let ry = y & s != 0;
+        d += s * s * ((3 * rx as u64) ^ ry as u64);
+        if !ry {
+            if rx {
+                x = mask - x;
+                y = mask - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+    }
+    d
+}
+
+/// Mo's algorithm over ranges `[l, r)`
+///
+/// `add_left`/`remove_left` and `add_right`/`remove_right` default to `add`/`remove`.
+pub trait MoAlgorithm {
+    type Answer;
+
+    /// Adds the `i`-th element
+    fn add(&mut self, i: usize);
+
+    /// Removes the `i`-th element
+    fn remove(&mut self, i: usize);
+
+    /// Adds the `i`-th element at the left end
+    fn add_left(&mut self, i: usize) {
+        self.add(i);
+    }
+
+    /// Adds the `i`-th element at the right end
+    fn add_right(&mut self, i: usize) {
+        self.add(i);
+    }
+
+    /// Removes the `i`-th element at the left end
+    fn remove_left(&mut self, i: usize) {
+        self.remove(i);
+    }
+
+    /// Removes the `i`-th element at the right end
+    fn remove_right(&mut self, i: usize) {
+        self.remove(i);
+    }
+
+    /// Answers the `index`-th query for the current range
+    fn answer(&mut self, index: usize) -> Self::Answer;
+
+    /// Answers all queries in Hilbert order
+    fn solve(&mut self, lr: &[(usize, usize)]) -> Vec<Self::Answer> {
+        let mut ans: Vec<Option<Self::Answer>> = (0..lr.len()).map(|_| None).collect();
+        let (mut l, mut r) = (0usize, 0usize);
+        for i in hilbert_order(lr) {
+            let (nl, nr) = lr[i];
+            assert!(nl <= nr, "invalid range");
+            while l > nl {
+                l -= 1;
+                self.add_left(l);
+            }
+            while r < nr {
+                self.add_right(r);
+                r += 1;
+            }
+            while l < nl {
+                self.remove_left(l);
+                l += 1;
+            }
+            while r > nr {
+                r -= 1;
+                self.remove_right(r);
+            }
+            ans[i] = Some(self.answer(i));
+        }
+        ans.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// Mo's algorithm over ranges `[l, r)` with only additions
+///
+/// Elements are never removed; the state is restored by [`RollbackMo::rollback`] instead.
+pub trait RollbackMo {
+    type Answer;
+    type Snapshot;
+
+    /// Adds the `i`-th element at the left end
+    fn add_left(&mut self, i: usize);
+
+    /// Adds the `i`-th element at the right end
+    fn add_right(&mut self, i: usize);
+
+    /// Takes a snapshot of the current state
+    fn snapshot(&mut self) -> Self::Snapshot;
+
+    /// Restores the state to the snapshot
+    fn rollback(&mut self, snapshot: Self::Snapshot);
+
+    /// Answers the `index`-th query for the current range
+    fn answer(&mut self, index: usize) -> Self::Answer;
+
+    /// Answers all queries, starting from the current state as the empty range
+    fn solve(&mut self, lr: &[(usize, usize)]) -> Vec<Self::Answer> {
+        let q = lr.len();
+        let mut ans: Vec<Option<Self::Answer>> = (0..q).map(|_| None).collect();
+        let n = lr.iter().map(|&(_, r)| r).max().unwrap_or_default();
+        let width = ((n as f64) / (q.max(1) as f64).sqrt()).ceil().max(1.0) as usize;
+        let mut idx: Vec<usize> = (0..q).collect();
+        idx.sort_unstable_by_key(|&i| (lr[i].0 / width, lr[i].1));
+        let mut idx = &idx[..];
+        while let Some(&first) = idx.first() {
+            let block = lr[first].0 / width;
+            let k = idx.partition_point(|&i| lr[i].0 / width == block);
+            let (cur, rest) = idx.split_at(k);
+            idx = rest;
+            let bound = (block + 1) * width;
+            let base = self.snapshot();
+            let mut r = bound;
+            for &i in cur {
+                let (nl, nr) = lr[i];
+                assert!(nl <= nr, "invalid range");
+                if nr <= bound {
+                    let snapshot = self.snapshot();
+                    for j in nl..nr {
+                        self.add_right(j);
+                    }
+                    ans[i] = Some(self.answer(i));
+                    self.rollback(snapshot);
+                    continue;
+                }
+                while r < nr {
+                    self.add_right(r);
+                    r += 1;
+                }
+                let snapshot = self.snapshot();
+                for j in (nl..bound).rev() {
+                    self.add_left(j);
+                }
+                ans[i] = Some(self.answer(i));
+                self.rollback(snapshot);
+            }
+            self.rollback(base);
+        }
+        ans.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// Mo's algorithm over ranges `[l, r)` with point updates
+///
+/// Each query `(t, l, r)` is answered after applying the first `t` updates.
+pub trait MoWithUpdate {
+    type Answer;
+
+    /// Adds the `i`-th element
+    fn add(&mut self, i: usize);
+
+    /// Removes the `i`-th element
+    fn remove(&mut self, i: usize);
+
+    /// Applies the `t`-th update while the current range is `[l, r)`
+    fn apply(&mut self, t: usize, l: usize, r: usize);
+
+    /// Reverts the `t`-th update while the current range is `[l, r)`
+    ///
+    /// Defaults to `apply`, which suits updates that swap the old and new values.
+    fn undo(&mut self, t: usize, l: usize, r: usize) {
+        self.apply(t, l, r);
+    }
+
+    /// Answers the `index`-th query for the current range and time
+    fn answer(&mut self, index: usize) -> Self::Answer;
+
+    /// Answers all queries `(t, l, r)`
+    fn solve(&mut self, tlr: &[(usize, usize, usize)]) -> Vec<Self::Answer> {
+        let q = tlr.len();
+        let mut ans: Vec<Option<Self::Answer>> = (0..q).map(|_| None).collect();
+        let n = tlr.iter().map(|&(_, _, r)| r).max().unwrap_or_default();
+        let m = tlr.iter().map(|&(t, _, _)| t).max().unwrap_or_default();
+        let width = ((n as f64).powi(2) * (m.max(1) as f64) / (q.max(1) as f64))
+            .cbrt()
+            .ceil()
+            .max(1.0) as usize;
+        let mut idx: Vec<usize> = (0..q).collect();
+        idx.sort_unstable_by_key(|&i| {
+            let (t, l, r) = tlr[i];
+            let (bl, br) = (l / width, r / width);
+            let br = if bl & 1 == 1 { !br } else { br };
+            let t = if br & 1 == 1 { !t } else { t };
+            (bl, br, t)
+        });
+        let (mut t, mut l, mut r) = (0usize, 0usize, 0usize);
+        for i in idx {
+            let (nt, nl, nr) = tlr[i];
+            assert!(nl <= nr, "invalid range");
+            while l > nl {
+                l -= 1;
+                self.add(l);
+            }
+            while r < nr {
+                self.add(r);
+                r += 1;
+            }
+            while l < nl {
+                self.remove(l);
+                l += 1;
+            }
+            while r > nr {
+                r -= 1;
+                self.remove(r);
+            }
+            while t < nt {
+                self.apply(t, l, r);
+                t += 1;
+            }
+            while t > nt {
+                t -= 1;
+                self.undo(t, l, r);
+            }
+            ans[i] = Some(self.answer(i));
+        }
+        ans.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// Mo's algorithm over vertices on paths of a tree
+pub trait MoOnTree {
+    type Answer;
+
+    /// Adds the vertex `u`
+    fn add(&mut self, u: usize);
+
+    /// Removes the vertex `u`
+    fn remove(&mut self, u: usize);
+
+    /// Answers the `index`-th query for the current path
+    fn answer(&mut self, index: usize) -> Self::Answer;
+
+    /// Answers all queries on paths `u-v` including both ends
+    fn solve(
+        &mut self,
+        tree: &UndirectedSparseGraph,
+        root: usize,
+        paths: &[(usize, usize)],
+    ) -> Vec<Self::Answer>
+    where
+        Self: Sized,
+    {
+        let n = tree.vertices_size();
+        let mut trace = Vec::with_capacity(n * 2);
+        let tour = tree
+            .path_euler_tour_builder(root)
+            .build_with_trace(|u| trace.push(u));
+        let lca = tree.lca(root);
+        let mut extra = Vec::with_capacity(paths.len());
+        let lr: Vec<_> = paths
+            .iter()
+            .map(|&(u, v)| {
+                let (u, v) = if tour.vidx[u][0] <= tour.vidx[v][0] {
+                    (u, v)
+                } else {
+                    (v, u)
+                };
+                let w = lca.lca(u, v);
+                if w == u {
+                    extra.push(None);
+                    (tour.vidx[u][0], tour.vidx[v][0] + 1)
+                } else {
+                    extra.push(Some(w));
+                    (tour.vidx[u][1], tour.vidx[v][0] + 1)
+                }
+            })
+            .collect();
+        PathTour {
+            mo: self,
+            trace,
+            inside: vec![false; n],
+            extra,
+        }
+        .solve(&lr)
+    }
+}
+
+struct PathTour<'a, M> {
+    mo: &'a mut M,
+    trace: Vec<usize>,
+    inside: Vec<bool>,
+    extra: Vec<Option<usize>>,
+}
+
+impl<M> PathTour<'_, M>
+where
+    M: MoOnTree,
+{
+    fn toggle(&mut self, i: usize) {
+        let u = self.trace[i];
+        if self.inside[u] {
+            self.mo.remove(u);
+        } else {
+            self.mo.add(u);
+        }
+        self.inside[u] ^= true;
+    }
+}
+
+impl<M> MoAlgorithm for PathTour<'_, M>
+where
+    M: MoOnTree,
+{
+    type Answer = M::Answer;
+
+    fn add(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn answer(&mut self, index: usize) -> Self::Answer {
+        match self.extra[index] {
+            Some(w) => {
+                self.mo.add(w);
+                let ans = self.mo.answer(index);
+                self.mo.remove(w);
+                ans
+            }
+            None => self.mo.answer(index),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{rand, tools::NotEmptySegment as Nes, tools::Xorshift};
+    use super::*;
+    use crate::{
+        rand,
+        tools::{NotEmptySegment as Nes, WithEmptySegment as Wes, Xorshift},
+        tree::MixedTree,
+    };
+    use std::collections::HashSet;
+
+    struct Distinct {
+        a: Vec<usize>,
+        count: Vec<usize>,
+        distinct: usize,
+        history: Vec<usize>,
+    }
+
+    impl Distinct {
+        fn new(a: Vec<usize>, m: usize) -> Self {
+            Self {
+                a,
+                count: vec![0; m],
+                distinct: 0,
+                history: vec![],
+            }
+        }
+        fn inc(&mut self, x: usize) {
+            self.distinct += usize::from(self.count[x] == 0);
+            self.count[x] += 1;
+        }
+        fn dec(&mut self, x: usize) {
+            self.count[x] -= 1;
+            self.distinct -= usize::from(self.count[x] == 0);
+        }
+    }
+
+    impl MoAlgorithm for Distinct {
+        type Answer = usize;
+        fn add(&mut self, i: usize) {
+            self.inc(self.a[i]);
+        }
+        fn remove(&mut self, i: usize) {
+            self.dec(self.a[i]);
+        }
+        fn answer(&mut self, _index: usize) -> usize {
+            self.distinct
+        }
+    }
+
+    impl RollbackMo for Distinct {
+        type Answer = usize;
+        type Snapshot = usize;
+        fn add_left(&mut self, i: usize) {
+            self.inc(self.a[i]);
+            self.history.push(self.a[i]);
+        }
+        fn add_right(&mut self, i: usize) {
+            RollbackMo::add_left(self, i);
+        }
+        fn snapshot(&mut self) -> usize {
+            self.history.len()
+        }
+        fn rollback(&mut self, snapshot: usize) {
+            while self.history.len() > snapshot {
+                let x = self.history.pop().unwrap();
+                self.dec(x);
+            }
+        }
+        fn answer(&mut self, _index: usize) -> usize {
+            self.distinct
+        }
+    }
+
+    struct DistinctWithUpdate {
+        inner: Distinct,
+        updates: Vec<(usize, usize)>,
+    }
+
+    impl MoWithUpdate for DistinctWithUpdate {
+        type Answer = usize;
+        fn add(&mut self, i: usize) {
+            MoAlgorithm::add(&mut self.inner, i);
+        }
+        fn remove(&mut self, i: usize) {
+            MoAlgorithm::remove(&mut self.inner, i);
+        }
+        fn apply(&mut self, t: usize, l: usize, r: usize) {
+            let (p, ref mut x) = self.updates[t];
+            let inside = (l..r).contains(&p);
+            if inside {
+                self.inner.dec(self.inner.a[p]);
+            }
+            std::mem::swap(&mut self.inner.a[p], x);
+            if inside {
+                self.inner.inc(self.inner.a[p]);
+            }
+        }
+        fn answer(&mut self, _index: usize) -> usize {
+            self.inner.distinct
+        }
+    }
+
+    impl MoOnTree for Distinct {
+        type Answer = usize;
+        fn add(&mut self, u: usize) {
+            self.inc(self.a[u]);
+        }
+        fn remove(&mut self, u: usize) {
+            self.dec(self.a[u]);
+        }
+        fn answer(&mut self, _index: usize) -> usize {
+            self.distinct
+        }
+    }
+
+    fn brute(a: &[usize]) -> usize {
+        a.iter().collect::<HashSet<_>>().len()
+    }
 
     #[test]
     fn test_mo_algorithm() {
@@ -154,4 +619,94 @@ mod tests {
             assert_eq!(ans, exp);
         }
     }
+
+    #[test]
+    fn test_mo_trait() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1..100, m: 1..20, q: 1..200, a: [0..m; n], lr: [Wes(n); q]);
+            let ans = MoAlgorithm::solve(&mut Distinct::new(a.clone(), m), &lr);
+            for (&(l, r), ans) in lr.iter().zip(ans) {
+                assert_eq!(ans, brute(&a[l..r]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rollback_mo() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1..100, m: 1..20, q: 1..200, a: [0..m; n], lr: [Wes(n); q]);
+            let mut mo = Distinct::new(a.clone(), m);
+            let ans = RollbackMo::solve(&mut mo, &lr);
+            for (&(l, r), ans) in lr.iter().zip(ans) {
+                assert_eq!(ans, brute(&a[l..r]));
+            }
+            assert!(mo.history.is_empty());
+            assert_eq!(mo.distinct, 0);
+        }
+    }
+
+    #[test]
+    fn test_mo_with_update() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1..100, m: 1..20, k: 0..100, q: 1..200, a: [0..m; n], updates: [(0..n, 0..m); k]);
+            let tlr: Vec<_> = (0..q)
+                .map(|_| {
+                    let (l, r) = rng.random(Wes(n));
+                    (rng.random(0..=k), l, r)
+                })
+                .collect();
+            let mut mo = DistinctWithUpdate {
+                inner: Distinct::new(a.clone(), m),
+                updates: updates.clone(),
+            };
+            let ans = mo.solve(&tlr);
+            for (&(t, l, r), ans) in tlr.iter().zip(ans) {
+                let mut b = a.clone();
+                for &(p, x) in &updates[..t] {
+                    b[p] = x;
+                }
+                assert_eq!(ans, brute(&b[l..r]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mo_on_tree() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1..100, m: 1..20, q: 1..200, g: MixedTree(n), root: 0..n, a: [0..m; n], uv: [(0..n, 0..n); q]);
+            let (order, parents) = g.tree_order(root);
+            let mut depth = vec![0usize; n];
+            for &u in &order[1..] {
+                depth[u] = depth[parents[u]] + 1;
+            }
+            let ans = MoOnTree::solve(&mut Distinct::new(a.clone(), m), &g, root, &uv);
+            for (&(mut u, mut v), ans) in uv.iter().zip(ans) {
+                let mut path = vec![];
+                while u != v {
+                    if depth[u] < depth[v] {
+                        std::mem::swap(&mut u, &mut v);
+                    }
+                    path.push(a[u]);
+                    u = parents[u];
+                }
+                path.push(a[u]);
+                assert_eq!(ans, brute(&path));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hilbert_order() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1usize..100, q: 0..200, lr: [Wes(n); q]);
+            let mut idx = hilbert_order(&lr);
+            idx.sort_unstable();
+            assert!(idx.into_iter().eq(0..q));
+        }
+    }
 }
@@ -32,6 +32,8 @@ pub use self::esper::{EsperEstimator, EsperSolver};
 pub use self::horn_satisfiability::HornSatisfiability;
 #[codesnip::entry("ImpartialGame")]
 pub use self::impartial_game::{ImpartialGame, ImpartialGameAnalyzer, ImpartialGamer};
+#[cfg_attr(nightly, codesnip::entry("mo_algorithm"))]
+pub use self::mo_algorithm::{MoAlgorithm, MoOnTree, MoWithUpdate, RollbackMo, hilbert_order};
 #[codesnip::entry("number_of_increasing_sequences_between")]
 pub use self::number_of_increasing_sequences_between::{
     number_of_increasing_sequences_between, number_of_increasing_sequences_between_998244353,
@@ -93,7 +95,10 @@ mod esper;
 mod horn_satisfiability;
 #[cfg_attr(nightly, codesnip::entry("ImpartialGame"))]
 mod impartial_game;
-#[cfg_attr(nightly, codesnip::entry)]
+#[cfg_attr(
+    nightly,
+    codesnip::entry("mo_algorithm", include("EulerTour", "SparseGraph"))
+)]
 mod mo_algorithm;
 #[cfg_attr(
     nightly,
@@ -1,7 +1,9 @@
 use std::{
+    cell::RefCell,
     marker::PhantomData,
     mem::{replace, size_of, take},
     ptr::{self, NonNull, read, write},
+    rc::Rc,
 };
 
 pub trait Allocator<T> {
@@ -9,6 +11,12 @@ pub trait Allocator<T> {
     fn deallocate(&mut self, ptr: NonNull<T>) -> T;
 }
 
+/// Allocator whose handles share the same storage, so that nodes can be moved between containers
+pub trait SharedAllocator<T>: Allocator<T> {
+    fn share(&self) -> Self;
+    fn is_shared_with(&self, other: &Self) -> bool;
+}
+
 #[derive(Debug)]
 pub struct MemoryPool<T> {
     pool: Vec<T>,
@@ -109,6 +117,39 @@ impl<T> Allocator<T> for BoxAllocator<T> {
     }
 }
 
+impl<T> SharedAllocator<T> for BoxAllocator<T> {
+    fn share(&self) -> Self {
+        Self::default()
+    }
+    fn is_shared_with(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T, A> Allocator<T> for Rc<RefCell<A>>
+where
+    A: Allocator<T>,
+{
+    fn allocate(&mut self, value: T) -> NonNull<T> {
+        self.borrow_mut().allocate(value)
+    }
+    fn deallocate(&mut self, ptr: NonNull<T>) -> T {
+        self.borrow_mut().deallocate(ptr)
+    }
+}
+
+impl<T, A> SharedAllocator<T> for Rc<RefCell<A>>
+where
+    A: Allocator<T>,
+{
+    fn share(&self) -> Self {
+        Rc::clone(self)
+    }
+    fn is_shared_with(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
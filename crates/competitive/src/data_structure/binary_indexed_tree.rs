@@ -1,4 +1,4 @@
-use super::{Group, Monoid};
+use super::{AbelianGroup, Group, Monoid};
 use std::fmt::{self, Debug, Formatter};
 
 pub struct BinaryIndexedTree<M>
@@ -120,12 +120,102 @@ impl<G: Group> BinaryIndexedTree<G> {
     }
 }
 
+/// Range add and range fold by two [`BinaryIndexedTree`]s
+pub struct RangeAddBinaryIndexedTree<G>
+where
+    G: AbelianGroup,
+{
+    n: usize,
+    /// fold [0, k) = bit0.accumulate0(k) + k * bit1.accumulate0(k)
+    bit0: BinaryIndexedTree<G>,
+    bit1: BinaryIndexedTree<G>,
+}
+
+impl<G> Clone for RangeAddBinaryIndexedTree<G>
+where
+    G: AbelianGroup,
+{
+    fn clone(&self) -> Self {
+        Self {
+            n: self.n,
+            bit0: self.bit0.clone(),
+            bit1: self.bit1.clone(),
+        }
+    }
+}
+
+impl<G> Debug for RangeAddBinaryIndexedTree<G>
+where
+    G: AbelianGroup<T: Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangeAddBinaryIndexedTree")
+            .field("n", &self.n)
+            .field("bit0", &self.bit0)
+            .field("bit1", &self.bit1)
+            .finish()
+    }
+}
+
+impl<G> RangeAddBinaryIndexedTree<G>
+where
+    G: AbelianGroup,
+{
+    #[inline]
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            bit0: BinaryIndexedTree::new(n),
+            bit1: BinaryIndexedTree::new(n),
+        }
+    }
+    #[inline]
+    pub fn from_slice(slice: &[G::T]) -> Self {
+        Self {
+            n: slice.len(),
+            bit0: BinaryIndexedTree::from_slice(slice),
+            bit1: BinaryIndexedTree::new(slice.len()),
+        }
+    }
+    #[inline]
+    /// fold [0, k)
+    pub fn accumulate0(&self, k: usize) -> G::T {
+        debug_assert!(k <= self.n);
+        G::operate(
+            &self.bit0.accumulate0(k),
+            &G::pow(self.bit1.accumulate0(k), k),
+        )
+    }
+    #[inline]
+    /// operate x to each element in [l, r)
+    pub fn update(&mut self, l: usize, r: usize, x: G::T) {
+        debug_assert!(l <= r && r <= self.n);
+        if l < self.n {
+            self.bit0.update(l, G::inverse(&G::pow(x.clone(), l)));
+            self.bit1.update(l, x.clone());
+        }
+        if r < self.n {
+            self.bit0.update(r, G::pow(x.clone(), r));
+            self.bit1.update(r, G::inverse(&x));
+        }
+    }
+    #[inline]
+    pub fn fold(&self, l: usize, r: usize) -> G::T {
+        debug_assert!(l <= r && r <= self.n);
+        G::rinv_operate(&self.accumulate0(r), &self.accumulate0(l))
+    }
+    #[inline]
+    pub fn get(&self, k: usize) -> G::T {
+        self.fold(k, k + 1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         algebra::{AdditiveOperation, MaxOperation},
-        tools::Xorshift,
+        tools::{NotEmptySegment as Nes, Xorshift},
     };
 
     const N: usize = 10_000;
@@ -205,4 +295,25 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_range_add_binary_indexed_tree() {
+        const N: usize = 200;
+        let mut rng = Xorshift::default();
+        let mut arr: Vec<_> = rng.random_iter(-B..B).take(N).collect();
+        let mut bit = RangeAddBinaryIndexedTree::<AdditiveOperation<_>>::from_slice(&arr);
+        for _ in 0..Q / 10 {
+            let (l, r) = rng.random(Nes(N));
+            if rng.gen_bool(0.5) {
+                let x = rng.random(-B..B);
+                bit.update(l, r, x);
+                arr[l..r].iter_mut().for_each(|a| *a += x);
+            } else {
+                assert_eq!(bit.fold(l, r), arr[l..r].iter().sum::<i64>());
+            }
+        }
+        for (k, &a) in arr.iter().enumerate() {
+            assert_eq!(bit.get(k), a);
+        }
+    }
 }
@@ -77,6 +77,34 @@ where
             a += a & (!a + 1);
         }
     }
+    #[inline]
+    /// Return the maximum i such that pred(fold [0, i) x [0, j)), assuming pred is monotone
+    pub fn partition_point_acc<P>(&self, j: usize, mut pred: P) -> usize
+    where
+        P: FnMut(&M::T) -> bool,
+    {
+        debug_assert!(j <= self.w);
+        let h = self.h;
+        let mut acc = M::unit();
+        let mut pos = 0;
+        let mut k = h.next_power_of_two();
+        while k > 0 {
+            if k + pos <= h {
+                let mut nacc = acc.clone();
+                let mut b = j;
+                while b > 0 {
+                    nacc = M::operate(&nacc, &self.bit[k + pos][b]);
+                    b -= b & (!b + 1);
+                }
+                if pred(&nacc) {
+                    pos += k;
+                    acc = nacc;
+                }
+            }
+            k >>= 1;
+        }
+        pos
+    }
 }
 
 impl<G> BinaryIndexedTree2D<G>
@@ -204,4 +232,27 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_binary_indexed_tree_2d_partition_point_acc() {
+        let mut rng = Xorshift::default();
+        const H: usize = 30;
+        const W: usize = 40;
+        let mut bit = BinaryIndexedTree2D::<AdditiveOperation<_>>::new(H, W);
+        let mut arr = vec![vec![0usize; W]; H];
+        for (i, j) in rng.random_iter((..H, ..W)).take(1000) {
+            bit.update(i, j, 1);
+            arr[i][j] += 1;
+        }
+        for j in 0..=W {
+            let mut acc = vec![0usize; H + 1];
+            for i in 0..H {
+                acc[i + 1] = acc[i] + arr[i][..j].iter().sum::<usize>();
+            }
+            for k in 0..=acc[H] + 1 {
+                let expected = acc.partition_point(|&c| c < k).saturating_sub(1);
+                assert_eq!(bit.partition_point_acc(j, |&c| c < k), expected);
+            }
+        }
+    }
 }
@@ -0,0 +1,191 @@
+use super::{AbelianGroup, AbelianMonoid, RangeBoundsExt};
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::RangeBounds,
+};
+
+/// K-dimensional Binary Indexed Tree
+pub struct BinaryIndexedTreeKD<const K: usize, M>
+where
+    M: AbelianMonoid,
+{
+    dim: [usize; K],
+    offset: [usize; K],
+    bit: Vec<M::T>,
+}
+
+impl<const K: usize, M> Clone for BinaryIndexedTreeKD<K, M>
+where
+    M: AbelianMonoid,
+{
+    fn clone(&self) -> Self {
+        Self {
+            dim: self.dim,
+            offset: self.offset,
+            bit: self.bit.clone(),
+        }
+    }
+}
+
+impl<const K: usize, M> Debug for BinaryIndexedTreeKD<K, M>
+where
+    M: AbelianMonoid<T: Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BinaryIndexedTreeKD")
+            .field("dim", &self.dim)
+            .field("offset", &self.offset)
+            .field("bit", &self.bit)
+            .finish()
+    }
+}
+
+impl<const K: usize, M> BinaryIndexedTreeKD<K, M>
+where
+    M: AbelianMonoid,
+{
+    #[inline]
+    pub fn new(dim: [usize; K]) -> Self {
+        let mut offset = [1; K];
+        for d in (1..K).rev() {
+            offset[d - 1] = offset[d] * (dim[d] + 1);
+        }
+        let size = if K == 0 { 1 } else { offset[0] * (dim[0] + 1) };
+        Self {
+            dim,
+            offset,
+            bit: vec![M::unit(); size],
+        }
+    }
+    fn accumulate_dfs(&self, x: &[usize; K], d: usize, p: usize, acc: &mut M::T) {
+        if d == K {
+            *acc = M::operate(acc, &self.bit[p]);
+            return;
+        }
+        let mut a = x[d];
+        while a > 0 {
+            self.accumulate_dfs(x, d + 1, p + a * self.offset[d], acc);
+            a -= a & (!a + 1);
+        }
+    }
+    #[inline]
+    /// fold [0, x_0) x ... x [0, x_{K-1})
+    pub fn accumulate0(&self, x: [usize; K]) -> M::T {
+        for (d, x) in x.into_iter().enumerate() {
+            debug_assert!(x <= self.dim[d]);
+        }
+        let mut acc = M::unit();
+        self.accumulate_dfs(&x, 0, 0, &mut acc);
+        acc
+    }
+    #[inline]
+    /// fold [0, x_0] x ... x [0, x_{K-1}]
+    pub fn accumulate(&self, x: [usize; K]) -> M::T {
+        self.accumulate0(x.map(|x| x + 1))
+    }
+    fn update_dfs(&mut self, x: &[usize; K], d: usize, p: usize, v: &M::T) {
+        if d == K {
+            self.bit[p] = M::operate(&self.bit[p], v);
+            return;
+        }
+        let mut a = x[d] + 1;
+        while a <= self.dim[d] {
+            self.update_dfs(x, d + 1, p + a * self.offset[d], v);
+            a += a & (!a + 1);
+        }
+    }
+    #[inline]
+    pub fn update(&mut self, x: [usize; K], v: M::T) {
+        for (d, x) in x.into_iter().enumerate() {
+            debug_assert!(x < self.dim[d]);
+        }
+        self.update_dfs(&x, 0, 0, &v);
+    }
+}
+
+impl<const K: usize, G> BinaryIndexedTreeKD<K, G>
+where
+    G: AbelianGroup,
+{
+    pub fn fold<R>(&self, ranges: [R; K]) -> G::T
+    where
+        R: RangeBounds<usize>,
+    {
+        let ranges: [_; K] = std::array::from_fn(|d| {
+            let range = ranges[d]
+                .to_range_bounded(0, self.dim[d])
+                .expect("invalid range");
+            let (l, r) = (range.start, range.end);
+            assert!(l <= r, "bad range [{}, {})", l, r);
+            [l, r]
+        });
+        let mut acc = G::unit();
+        for mask in 0usize..1 << K {
+            let x: [_; K] = std::array::from_fn(|d| ranges[d][!mask >> d & 1]);
+            let y = self.accumulate0(x);
+            if mask.count_ones() % 2 == 0 {
+                acc = G::operate(&acc, &y);
+            } else {
+                acc = G::rinv_operate(&acc, &y);
+            }
+        }
+        acc
+    }
+    #[inline]
+    pub fn get(&self, x: [usize; K]) -> G::T {
+        self.fold(x.map(|x| x..x + 1))
+    }
+    #[inline]
+    pub fn set(&mut self, x: [usize; K], v: G::T) {
+        let y = G::inverse(&self.get(x));
+        let z = G::operate(&y, &v);
+        self.update(x, z);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::AdditiveOperation, tools::Xorshift};
+
+    #[test]
+    fn test_binary_indexed_tree_kd() {
+        const D: usize = 6;
+        const Q: usize = 2_000;
+        let mut rng = Xorshift::default();
+        let mut bit = BinaryIndexedTreeKD::<3, AdditiveOperation<i64>>::new([D; 3]);
+        let mut arr = [[[0i64; D]; D]; D];
+        for _ in 0..Q {
+            let x = [rng.random(..D), rng.random(..D), rng.random(..D)];
+            let v = rng.random(-1000..1000);
+            if rng.gen_bool(0.5) {
+                bit.update(x, v);
+                arr[x[0]][x[1]][x[2]] += v;
+            } else {
+                bit.set(x, v);
+                arr[x[0]][x[1]][x[2]] = v;
+            }
+
+            let x = [rng.random(..=D), rng.random(..=D), rng.random(..=D)];
+            let mut expected = 0;
+            for a in &arr[..x[0]] {
+                for b in &a[..x[1]] {
+                    expected += b[..x[2]].iter().sum::<i64>();
+                }
+            }
+            assert_eq!(bit.accumulate0(x), expected);
+
+            let ranges: [_; 3] = std::array::from_fn(|_| {
+                let l = rng.random(..=D);
+                l..rng.random(l..=D)
+            });
+            let mut expected = 0;
+            for a in &arr[ranges[0].clone()] {
+                for b in &a[ranges[1].clone()] {
+                    expected += b[ranges[2].clone()].iter().sum::<i64>();
+                }
+            }
+            assert_eq!(bit.fold(ranges), expected);
+        }
+    }
+}
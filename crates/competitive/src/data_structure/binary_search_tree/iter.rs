@@ -0,0 +1,136 @@
+use super::{
+    BstDataAccess, BstDataMutRef, BstNodePtr, BstRoot, BstSpec, LazyMapMonoid, RangeBoundsExt,
+    data::{self, LazyMapElement},
+};
+use std::{cmp::Ordering, iter::FusedIterator, marker::PhantomData, ops::RangeBounds};
+
+/// In-order iterator over a range of indices without splitting the tree
+///
+/// Lazy data is pushed down along the visited nodes.
+pub struct BstRangeIter<'a, Spec, L>
+where
+    Spec: BstSpec,
+    L: LazyMapMonoid,
+{
+    stack: Vec<BstNodePtr<Spec::Data, Spec::Parent>>,
+    remaining: usize,
+    _marker: PhantomData<&'a L::Key>,
+}
+
+impl<'a, Spec, L> BstRangeIter<'a, Spec, L>
+where
+    Spec: BstSpec<
+        Data: BstDataAccess<data::marker::Size, Value = usize>
+                  + BstDataAccess<data::marker::LazyMap, Value = LazyMapElement<L>>,
+    >,
+    L: LazyMapMonoid,
+{
+    pub fn new<R>(root: &'a mut Option<BstRoot<Spec>>, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let mut iter = Self {
+            stack: vec![],
+            remaining: 0,
+            _marker: PhantomData,
+        };
+        let size = root.as_ref().map_or(0, |root| Self::size(Some(root.node)));
+        let range = range.to_range_bounded(0, size).expect("invalid range");
+        iter.remaining = range.len();
+        let Some(mut node) = root
+            .as_ref()
+            .map(|root| root.node)
+            .filter(|_| iter.remaining > 0)
+        else {
+            return iter;
+        };
+        let mut index = range.start;
+        loop {
+            Self::top_down(node);
+            let [left, right] = unsafe { node.as_ref().child };
+            let left_size = Self::size(left);
+            match index.cmp(&left_size) {
+                Ordering::Less => {
+                    iter.stack.push(node);
+                    node = left.unwrap();
+                }
+                Ordering::Equal => {
+                    iter.stack.push(node);
+                    break;
+                }
+                Ordering::Greater => {
+                    index -= left_size + 1;
+                    node = right.unwrap();
+                }
+            }
+        }
+        iter
+    }
+
+    fn size(node: Option<BstNodePtr<Spec::Data, Spec::Parent>>) -> usize {
+        node.map_or(0, |node| unsafe {
+            *BstDataAccess::<data::marker::Size>::bst_data(&node.as_ref().data)
+        })
+    }
+
+    fn top_down(node: BstNodePtr<Spec::Data, Spec::Parent>) {
+        Spec::top_down(unsafe { BstDataMutRef::new_unchecked(node) });
+    }
+}
+
+impl<'a, Spec, L> Iterator for BstRangeIter<'a, Spec, L>
+where
+    Spec: BstSpec<
+            Data: BstDataAccess<data::marker::Size, Value = usize>
+                      + BstDataAccess<data::marker::LazyMap, Value = LazyMapElement<L>>
+                      + 'a,
+            Parent: 'a,
+        >,
+    L: LazyMapMonoid + 'a,
+{
+    type Item = &'a L::Key;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = self.stack.pop()?;
+        let mut next = unsafe { node.as_ref().child[1] };
+        while let Some(child) = next {
+            Self::top_down(child);
+            self.stack.push(child);
+            next = unsafe { child.as_ref().child[0] };
+        }
+        let data = unsafe { &(*node.as_ptr()).data };
+        Some(&BstDataAccess::<data::marker::LazyMap>::bst_data(data).key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, Spec, L> ExactSizeIterator for BstRangeIter<'a, Spec, L>
+where
+    Spec: BstSpec<
+            Data: BstDataAccess<data::marker::Size, Value = usize>
+                      + BstDataAccess<data::marker::LazyMap, Value = LazyMapElement<L>>
+                      + 'a,
+            Parent: 'a,
+        >,
+    L: LazyMapMonoid + 'a,
+{
+}
+
+impl<'a, Spec, L> FusedIterator for BstRangeIter<'a, Spec, L>
+where
+    Spec: BstSpec<
+            Data: BstDataAccess<data::marker::Size, Value = usize>
+                      + BstDataAccess<data::marker::LazyMap, Value = LazyMapElement<L>>
+                      + 'a,
+            Parent: 'a,
+        >,
+    L: LazyMapMonoid + 'a,
+{
+}
@@ -1,6 +1,7 @@
-use super::{Allocator, LazyMapMonoid, Monoid, MonoidAct};
+use super::{Allocator, LazyMapMonoid, Monoid, MonoidAct, RangeBoundsExt};
 
 pub use data::BstDataAccess;
+pub use iter::BstRangeIter;
 pub use node::{
     BstDataMutRef, BstEdgeHandle, BstImmutRef, BstNode, BstNodePtr, BstNodeRef, BstRoot, BstSpec,
     EqualSide,
@@ -10,6 +11,7 @@ pub use seeker::BstSeeker;
 pub use split::{Split, Split3};
 
 pub mod data;
+pub mod iter;
 pub mod node;
 pub mod node_id;
 pub mod seeker;
@@ -57,6 +57,46 @@ impl BitSet {
         self.size as u64 - self.count_ones()
     }
 
+    /// The smallest `j >= i` such that the `j`-th bit is set
+    pub fn next_one(&self, i: usize) -> Option<usize> {
+        if i >= self.size {
+            return None;
+        }
+        let mut k = i >> 6;
+        let mut x = self.bits[k] & (!0 << (i & 63));
+        while x == 0 {
+            k += 1;
+            x = *self.bits.get(k)?;
+        }
+        Some((k << 6) | x.trailing_zeros() as usize)
+    }
+
+    /// The largest `j <= i` such that the `j`-th bit is set
+    pub fn prev_one(&self, i: usize) -> Option<usize> {
+        let i = i.min(self.size.checked_sub(1)?);
+        let mut k = i >> 6;
+        let mut x = self.bits[k] & (!0 >> (63 - (i & 63)));
+        while x == 0 {
+            k = k.checked_sub(1)?;
+            x = self.bits[k];
+        }
+        Some((k << 6) | (63 - x.leading_zeros() as usize))
+    }
+
+    /// Iterates over the indices of set bits in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(|(k, &x)| {
+            let mut x = x;
+            std::iter::from_fn(move || {
+                (x != 0).then(|| {
+                    let i = x.trailing_zeros() as usize;
+                    x &= x - 1;
+                    (k << 6) | i
+                })
+            })
+        })
+    }
+
     pub fn push(&mut self, b: bool) {
         let d = self.size & 63;
         if d == 0 {
@@ -457,4 +497,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_next_prev_one() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 0..=300, p: 0..=8u32);
+            let arr: Vec<bool> = (0..n).map(|_| rng.random(0..8u32) < p).collect();
+            let bitset: BitSet = arr.iter().copied().collect();
+            for i in 0..=n + 64 {
+                assert_eq!(bitset.next_one(i), (i..n).find(|&j| arr[j]));
+                assert_eq!(
+                    bitset.prev_one(i),
+                    (0..n.min(i + 1)).rev().find(|&j| arr[j]),
+                );
+            }
+            assert!(bitset.iter_ones().eq((0..n).filter(|&i| arr[i])));
+        }
+    }
 }
@@ -0,0 +1,648 @@
+use super::{Allocator, IntBase, LazyMapMonoid, MemoryPool, RangeBoundsExt};
+use std::{
+    fmt::{self, Debug, Formatter},
+    mem::replace,
+    ops::Range,
+    ptr::NonNull,
+};
+
+type NodePtr<M> = Option<NonNull<Node<M>>>;
+
+struct Node<M>
+where
+    M: LazyMapMonoid,
+{
+    children: [NodePtr<M>; 2],
+    agg: M::Agg,
+    lazy: M::Act,
+}
+
+/// Segment tree over a huge index range, nodes are allocated on demand.
+///
+/// With [`DynamicSegmentTree::new`], unallocated elements are `M::agg_unit()` and are not affected by acts.
+/// With [`DynamicSegmentTree::with_init`], acts also apply to elements never set.
+/// Use [`EmptyActLazy`](crate::algebra::EmptyActLazy) for plain [`Monoid`](crate::algebra::Monoid) folds.
+pub struct DynamicSegmentTree<K, M>
+where
+    M: LazyMapMonoid,
+{
+    start: K,
+    end: K,
+    root: NodePtr<M>,
+    init: Option<fn(K) -> M::Agg>,
+    allocator: MemoryPool<Node<M>>,
+}
+
+impl<K, M> Debug for DynamicSegmentTree<K, M>
+where
+    K: Debug,
+    M: LazyMapMonoid,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicSegmentTree")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl<K, M> DynamicSegmentTree<K, M>
+where
+    K: IntBase,
+    M: LazyMapMonoid,
+{
+    /// Elements indexed by `range`, e.g. `-1e18..1e18`
+    pub fn new(range: Range<K>) -> Self {
+        assert!(range.start <= range.end);
+        Self {
+            start: range.start,
+            end: range.end,
+            root: None,
+            init: None,
+            allocator: MemoryPool::new(),
+        }
+    }
+
+    /// Elements indexed by `range`, `init(len)` is the aggregation of `len` consecutive elements never set.
+    ///
+    /// `init(a + b)` must equal `M::agg_operate(&init(a), &init(b))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `range` does not fit in `K`.
+    pub fn with_init(range: Range<K>, init: fn(K) -> M::Agg) -> Self {
+        assert!(
+            range.start >= K::zero() || range.end <= range.start + K::maximum(),
+            "length of range must fit in the index type"
+        );
+        Self {
+            init: Some(init),
+            ..Self::new(range)
+        }
+    }
+
+    fn mid(start: K, end: K) -> K {
+        start.midpoint(end)
+    }
+
+    fn is_leaf(start: K, end: K) -> bool {
+        start + K::one() == end
+    }
+
+    fn init_agg(&self, start: K, end: K) -> M::Agg {
+        self.init.map_or_else(M::agg_unit, |init| init(end - start))
+    }
+
+    fn subtree_agg(&self, node: NodePtr<M>, start: K, end: K) -> M::Agg {
+        node.map(|node| unsafe { node.as_ref().agg.clone() })
+            .unwrap_or_else(|| self.init_agg(start, end))
+    }
+
+    fn allocate<'a>(&mut self, node: &mut NodePtr<M>, start: K, end: K) -> &'a mut Node<M> {
+        if node.is_none() {
+            let agg = self.init_agg(start, end);
+            *node = Some(self.allocator.allocate(Node {
+                children: [None, None],
+                agg,
+                lazy: M::act_unit(),
+            }));
+        }
+        unsafe { node.unwrap().as_mut() }
+    }
+
+    /// Allocate `node` only if elements never set are affected by acts.
+    fn node_mut<'a>(&mut self, node: &mut NodePtr<M>, start: K, end: K) -> Option<&'a mut Node<M>> {
+        if node.is_none() && self.init.is_none() {
+            None
+        } else {
+            Some(self.allocate(node, start, end))
+        }
+    }
+
+    fn apply(&mut self, node: &mut NodePtr<M>, start: K, end: K, act: &M::Act) {
+        let Some(node) = self.node_mut(node, start, end) else {
+            return;
+        };
+        let is_leaf = Self::is_leaf(start, end);
+        if !is_leaf {
+            node.lazy = M::act_operate(&node.lazy, act);
+        }
+        if let Some(agg) = M::act_agg(&node.agg, act) {
+            node.agg = agg;
+        } else {
+            assert!(!is_leaf, "act failed on leaf");
+            self.propagate(node, start, end);
+            self.recalc(node, start, end);
+        }
+    }
+
+    fn propagate(&mut self, node: &mut Node<M>, start: K, end: K) {
+        let lazy = replace(&mut node.lazy, M::act_unit());
+        if M::is_act_unit(&lazy) {
+            return;
+        }
+        let mid = Self::mid(start, end);
+        self.apply(&mut node.children[0], start, mid, &lazy);
+        self.apply(&mut node.children[1], mid, end, &lazy);
+    }
+
+    fn recalc(&self, node: &mut Node<M>, start: K, end: K) {
+        let mid = Self::mid(start, end);
+        node.agg = M::agg_operate(
+            &self.subtree_agg(node.children[0], start, mid),
+            &self.subtree_agg(node.children[1], mid, end),
+        );
+    }
+
+    fn update_dfs(
+        &mut self,
+        node: &mut NodePtr<M>,
+        start: K,
+        end: K,
+        range: &Range<K>,
+        act: &M::Act,
+    ) {
+        if range.end <= start || end <= range.start {
+            return;
+        }
+        if range.start <= start && end <= range.end {
+            self.apply(node, start, end, act);
+            return;
+        }
+        let Some(node) = self.node_mut(node, start, end) else {
+            return;
+        };
+        self.propagate(node, start, end);
+        let mid = Self::mid(start, end);
+        self.update_dfs(&mut node.children[0], start, mid, range, act);
+        self.update_dfs(&mut node.children[1], mid, end, range, act);
+        self.recalc(node, start, end);
+    }
+
+    fn set_dfs(&mut self, node: &mut NodePtr<M>, start: K, end: K, index: K, agg: M::Agg) {
+        let node = self.allocate(node, start, end);
+        if Self::is_leaf(start, end) {
+            node.agg = agg;
+            return;
+        }
+        self.propagate(node, start, end);
+        let mid = Self::mid(start, end);
+        if index < mid {
+            self.set_dfs(&mut node.children[0], start, mid, index, agg);
+        } else {
+            self.set_dfs(&mut node.children[1], mid, end, index, agg);
+        }
+        self.recalc(node, start, end);
+    }
+
+    fn fold_dfs(&mut self, node: &mut NodePtr<M>, start: K, end: K, range: &Range<K>) -> M::Agg {
+        if range.end <= start || end <= range.start {
+            return M::agg_unit();
+        }
+        let Some(node) = self.node_mut(node, start, end) else {
+            return M::agg_unit();
+        };
+        if range.start <= start && end <= range.end {
+            return node.agg.clone();
+        }
+        self.propagate(node, start, end);
+        let mid = Self::mid(start, end);
+        let left = self.fold_dfs(&mut node.children[0], start, mid, range);
+        let right = self.fold_dfs(&mut node.children[1], mid, end, range);
+        M::agg_operate(&left, &right)
+    }
+
+    fn act_pending(agg: M::Agg, pending: &M::Act) -> Option<M::Agg> {
+        if M::is_act_unit(pending) {
+            Some(agg)
+        } else {
+            M::act_agg(&agg, pending)
+        }
+    }
+
+    /// Fold without allocating nodes, `pending` is the composition of the lazy acts of the ancestors.
+    ///
+    /// Return `None` if `M::act_agg` fails on the way.
+    fn fold_pending_dfs(
+        &self,
+        node: NodePtr<M>,
+        start: K,
+        end: K,
+        range: &Range<K>,
+        pending: &M::Act,
+    ) -> Option<M::Agg> {
+        if range.end <= start || end <= range.start {
+            return Some(M::agg_unit());
+        }
+        let Some(node) = node.map(|node| unsafe { node.as_ref() }) else {
+            return match self.init {
+                Some(_) => Self::act_pending(
+                    self.init_agg(start.max(range.start), end.min(range.end)),
+                    pending,
+                ),
+                None => Some(M::agg_unit()),
+            };
+        };
+        if range.start <= start && end <= range.end {
+            return Self::act_pending(node.agg.clone(), pending);
+        }
+        let pending = M::act_operate(&node.lazy, pending);
+        let mid = Self::mid(start, end);
+        let left = self.fold_pending_dfs(node.children[0], start, mid, range, &pending)?;
+        let right = self.fold_pending_dfs(node.children[1], mid, end, range, &pending)?;
+        Some(M::agg_operate(&left, &right))
+    }
+
+    /// Nodes are propagated only if `M::act_agg` fails on a pending act.
+    fn fold_range(&mut self, range: &Range<K>) -> M::Agg {
+        if let Some(agg) =
+            self.fold_pending_dfs(self.root, self.start, self.end, range, &M::act_unit())
+        {
+            return agg;
+        }
+        let mut root = self.root;
+        let agg = self.fold_dfs(&mut root, self.start, self.end, range);
+        self.root = root;
+        agg
+    }
+
+    fn partition_point_dfs<P>(
+        &mut self,
+        node: &mut NodePtr<M>,
+        start: K,
+        end: K,
+        left: K,
+        acc: &mut M::Agg,
+        pred: &mut P,
+    ) -> Option<K>
+    where
+        P: FnMut(&M::Agg) -> bool,
+    {
+        if end <= left {
+            return None;
+        }
+        let Some(node) = self.node_mut(node, start, end) else {
+            return (!pred(acc)).then(|| start.max(left));
+        };
+        if left <= start {
+            let nacc = M::agg_operate(acc, &node.agg);
+            if pred(&nacc) {
+                *acc = nacc;
+                return None;
+            }
+            if Self::is_leaf(start, end) {
+                return Some(start);
+            }
+        }
+        self.propagate(node, start, end);
+        let mid = Self::mid(start, end);
+        self.partition_point_dfs(&mut node.children[0], start, mid, left, acc, pred)
+            .or_else(|| self.partition_point_dfs(&mut node.children[1], mid, end, left, acc, pred))
+    }
+
+    fn rpartition_point_dfs<P>(
+        &mut self,
+        node: &mut NodePtr<M>,
+        start: K,
+        end: K,
+        right: K,
+        acc: &mut M::Agg,
+        pred: &mut P,
+    ) -> Option<K>
+    where
+        P: FnMut(&M::Agg) -> bool,
+    {
+        if right <= start {
+            return None;
+        }
+        let Some(node) = self.node_mut(node, start, end) else {
+            return (!pred(acc)).then(|| end.min(right));
+        };
+        if end <= right {
+            let nacc = M::agg_operate(&node.agg, acc);
+            if pred(&nacc) {
+                *acc = nacc;
+                return None;
+            }
+            if Self::is_leaf(start, end) {
+                return Some(end);
+            }
+        }
+        self.propagate(node, start, end);
+        let mid = Self::mid(start, end);
+        self.rpartition_point_dfs(&mut node.children[1], mid, end, right, acc, pred)
+            .or_else(|| {
+                self.rpartition_point_dfs(&mut node.children[0], start, mid, right, acc, pred)
+            })
+    }
+
+    pub fn set(&mut self, index: K, agg: M::Agg) {
+        assert!(self.start <= index && index < self.end);
+        let mut root = self.root;
+        self.set_dfs(&mut root, self.start, self.end, index, agg);
+        self.root = root;
+    }
+
+    pub fn get(&mut self, index: K) -> M::Agg {
+        assert!(self.start <= index && index < self.end);
+        self.fold_range(&(index..index + K::one()))
+    }
+
+    pub fn update<R>(&mut self, range: R, act: M::Act)
+    where
+        R: RangeBoundsExt<K>,
+    {
+        let range = range
+            .to_range_bounded(self.start, self.end)
+            .expect("invalid range");
+        if range.start < range.end {
+            let mut root = self.root;
+            self.update_dfs(&mut root, self.start, self.end, &range, &act);
+            self.root = root;
+        }
+    }
+
+    pub fn fold<R>(&mut self, range: R) -> M::Agg
+    where
+        R: RangeBoundsExt<K>,
+    {
+        let range = range
+            .to_range_bounded(self.start, self.end)
+            .expect("invalid range");
+        if range.start < range.end {
+            self.fold_range(&range)
+        } else {
+            M::agg_unit()
+        }
+    }
+
+    pub fn fold_all(&mut self) -> M::Agg {
+        self.subtree_agg(self.root, self.start, self.end)
+    }
+
+    /// Return the first index `i` from `left` such that `!pred(fold(left..=i))`, `end` if none.
+    pub fn partition_point_acc<P>(&mut self, left: K, mut pred: P) -> K
+    where
+        P: FnMut(&M::Agg) -> bool,
+    {
+        assert!(self.start <= left && left <= self.end);
+        let mut acc = M::agg_unit();
+        let mut root = self.root;
+        let res =
+            self.partition_point_dfs(&mut root, self.start, self.end, left, &mut acc, &mut pred);
+        self.root = root;
+        res.unwrap_or(self.end)
+    }
+
+    /// Return the last index `i` until `right` such that `!pred(fold(i - 1..right))`, `start` if none.
+    pub fn rpartition_point_acc<P>(&mut self, right: K, mut pred: P) -> K
+    where
+        P: FnMut(&M::Agg) -> bool,
+    {
+        assert!(self.start <= right && right <= self.end);
+        let mut acc = M::agg_unit();
+        let mut root = self.root;
+        let res =
+            self.rpartition_point_dfs(&mut root, self.start, self.end, right, &mut acc, &mut pred);
+        self.root = root;
+        res.unwrap_or(self.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::{AdditiveOperation, EmptyActLazy, RangeMaxRangeAdd, RangeSumRangeAdd},
+        tools::Xorshift,
+    };
+    use std::collections::BTreeMap;
+
+    const Q: usize = 5_000;
+
+    #[test]
+    fn test_dynamic_segment_tree_monoid() {
+        let mut rng = Xorshift::default();
+        const L: i64 = -1_000_000_000_000_000_000;
+        let mut seg = DynamicSegmentTree::<i64, EmptyActLazy<AdditiveOperation<i64>>>::new(L..-L);
+        let mut map = BTreeMap::new();
+        let keys: Vec<i64> = (0..50).map(|_| rng.random(L..-L)).collect();
+        for _ in 0..Q {
+            match rng.random(0..4) {
+                0 => {
+                    let k = keys[rng.random(0..keys.len())];
+                    let x = rng.random(0..1000i64);
+                    seg.set(k, x);
+                    map.insert(k, x);
+                }
+                1 => {
+                    let (mut l, mut r) = (rng.random(L..=-L), rng.random(L..=-L));
+                    if l > r {
+                        std::mem::swap(&mut l, &mut r);
+                    }
+                    assert_eq!(seg.fold(l..r), map.range(l..r).map(|(_, x)| x).sum());
+                }
+                2 => {
+                    let left = rng.random(L..=-L);
+                    let limit = rng.random(0..10_000i64);
+                    let mut acc = 0;
+                    let expected = map
+                        .range(left..)
+                        .find(|&(_, &x)| {
+                            acc += x;
+                            acc >= limit
+                        })
+                        .map_or(-L, |(&k, _)| k);
+                    assert_eq!(seg.partition_point_acc(left, |&s| s < limit), expected);
+                }
+                _ => {
+                    let right = rng.random(L..=-L);
+                    let limit = rng.random(0..10_000i64);
+                    let mut acc = 0;
+                    let expected = map
+                        .range(..right)
+                        .rev()
+                        .find(|&(_, &x)| {
+                            acc += x;
+                            acc >= limit
+                        })
+                        .map_or(L, |(&k, _)| k + 1);
+                    assert_eq!(seg.rpartition_point_acc(right, |&s| s < limit), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_segment_tree_lazy() {
+        let mut rng = Xorshift::default();
+        const N: u64 = 1 << 50;
+        let mut seg = DynamicSegmentTree::<u64, RangeSumRangeAdd<i64>>::new(0..N);
+        let mut mseg = DynamicSegmentTree::<u64, RangeMaxRangeAdd<i64>>::new(0..N);
+        let mut map = BTreeMap::new();
+        let keys: Vec<u64> = (0..50).map(|_| rng.random(0..N)).collect();
+        for _ in 0..Q {
+            let (mut l, mut r) = (rng.random(0..=N), rng.random(0..=N));
+            if l > r {
+                std::mem::swap(&mut l, &mut r);
+            }
+            match rng.random(0..4) {
+                0 => {
+                    let k = keys[rng.random(0..keys.len())];
+                    let x = rng.random(-1000..1000i64);
+                    seg.set(k, (x, 1));
+                    mseg.set(k, x);
+                    map.insert(k, x);
+                }
+                1 => {
+                    let x = rng.random(-1000..1000i64);
+                    seg.update(l..r, x);
+                    mseg.update(l..r, x);
+                    for (_, v) in map.range_mut(l..r) {
+                        *v += x;
+                    }
+                }
+                2 => {
+                    assert_eq!(seg.fold(l..r).0, map.range(l..r).map(|(_, x)| x).sum());
+                    assert_eq!(
+                        mseg.fold(l..r),
+                        map.range(l..r).map(|(_, &x)| x).max().unwrap_or(i64::MIN)
+                    );
+                }
+                _ => {
+                    let k = keys[rng.random(0..keys.len())];
+                    assert_eq!(seg.get(k).0, map.get(&k).copied().unwrap_or_default());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_segment_tree_with_init() {
+        let mut rng = Xorshift::default();
+        const N: u64 = 1 << 50;
+        const W: usize = 64;
+        let mut seg = DynamicSegmentTree::<u64, RangeSumRangeAdd<i64>>::with_init(0..N, |len| {
+            (0, len as i64)
+        });
+        let mut mseg = DynamicSegmentTree::<u64, RangeMaxRangeAdd<i64>>::with_init(0..N, |_| 0);
+        seg.update(.., 3);
+        mseg.update(.., 3);
+        assert_eq!(
+            seg.fold(N / 3..N / 2),
+            ((N / 2 - N / 3) as i64 * 3, (N / 2 - N / 3) as i64)
+        );
+        assert_eq!(mseg.fold(N / 3..N / 2), 3);
+
+        let base = rng.random(0..N - W as u64);
+        let mut arr = vec![3i64; W];
+        for _ in 0..Q {
+            let (mut l, mut r) = (rng.random(0..=W), rng.random(0..=W));
+            if l > r {
+                std::mem::swap(&mut l, &mut r);
+            }
+            let range = base + l as u64..base + r as u64;
+            match rng.random(0..4) {
+                0 => {
+                    let k = rng.random(0..W);
+                    let x = rng.random(-1000..1000i64);
+                    seg.set(base + k as u64, (x, 1));
+                    mseg.set(base + k as u64, x);
+                    arr[k] = x;
+                }
+                1 => {
+                    let x = rng.random(-1000..1000i64);
+                    seg.update(range.clone(), x);
+                    mseg.update(range, x);
+                    for a in &mut arr[l..r] {
+                        *a += x;
+                    }
+                }
+                2 => {
+                    assert_eq!(
+                        seg.fold(range.clone()),
+                        (arr[l..r].iter().sum(), (r - l) as i64)
+                    );
+                    assert_eq!(
+                        mseg.fold(range),
+                        arr[l..r].iter().copied().max().unwrap_or(i64::MIN)
+                    );
+                }
+                _ => {
+                    let k = rng.random(0..W);
+                    assert_eq!(seg.get(base + k as u64), (arr[k], 1));
+                    assert_eq!(
+                        seg.fold_all().0,
+                        arr.iter().sum::<i64>() + (N - W as u64) as i64 * 3
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_segment_tree_full_range() {
+        let mut rng = Xorshift::default();
+        let mut seg = DynamicSegmentTree::<i64, RangeSumRangeAdd<i64>>::new(i64::MIN..i64::MAX);
+        let mut map = BTreeMap::new();
+        let keys: Vec<i64> = (0..50).map(|_| rng.random(i64::MIN..i64::MAX)).collect();
+        for _ in 0..Q {
+            let (mut l, mut r) = (
+                rng.random(i64::MIN..=i64::MAX),
+                rng.random(i64::MIN..=i64::MAX),
+            );
+            if l > r {
+                std::mem::swap(&mut l, &mut r);
+            }
+            match rng.random(0..3) {
+                0 => {
+                    let k = keys[rng.random(0..keys.len())];
+                    let x = rng.random(-1000..1000i64);
+                    seg.set(k, (x, 1));
+                    map.insert(k, x);
+                }
+                1 => {
+                    let x = rng.random(-1000..1000i64);
+                    seg.update(l..r, x);
+                    for (_, v) in map.range_mut(l..r) {
+                        *v += x;
+                    }
+                }
+                _ => {
+                    assert_eq!(seg.fold(l..r).0, map.range(l..r).map(|(_, x)| x).sum());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_segment_tree_fold_without_allocation() {
+        fn count<M: LazyMapMonoid>(node: NodePtr<M>) -> usize {
+            node.map_or(0, |node| {
+                let node = unsafe { node.as_ref() };
+                1 + count(node.children[0]) + count(node.children[1])
+            })
+        }
+        const N: u64 = 1 << 50;
+        let mut seg = DynamicSegmentTree::<u64, RangeSumRangeAdd<i64>>::with_init(0..N, |len| {
+            (0, len as i64)
+        });
+        assert_eq!(seg.fold(N / 3..N / 2), (0, (N / 2 - N / 3) as i64));
+        assert_eq!(seg.get(N / 5), (0, 1));
+        assert_eq!(count(seg.root), 0);
+
+        seg.update(N / 4..N / 2, 3);
+        seg.set(N / 3, (5, 1));
+        let nodes = count(seg.root);
+        assert_eq!(seg.get(N / 4 + 1), (3, 1));
+        assert_eq!(seg.get(N / 3), (5, 1));
+        assert_eq!(
+            seg.fold(N / 5..N / 3 + 1),
+            (
+                (N / 3 + 1 - N / 4) as i64 * 3 + 2,
+                (N / 3 + 1 - N / 5) as i64
+            )
+        );
+        assert_eq!(count(seg.root), nodes);
+    }
+}
@@ -0,0 +1,201 @@
+/// Set of integers in `0..n` by a 64-ary tree of bit words
+///
+/// `insert`, `remove`, `next` and `prev` take $O(\log_{64} n)$ time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FastSet {
+    n: usize,
+    len: usize,
+    /// `levels[0]` is the leaves, each bit of a word in `levels[d + 1]` tells whether the word in `levels[d]` is non-zero
+    levels: Vec<Vec<u64>>,
+}
+
+impl FastSet {
+    pub fn new(n: usize) -> Self {
+        let mut levels = vec![];
+        let mut m = n;
+        loop {
+            m = m.div_ceil(64).max(1);
+            levels.push(vec![0; m]);
+            if m == 1 {
+                break;
+            }
+        }
+        Self { n, len: 0, levels }
+    }
+
+    /// Size of the universe
+    pub fn capacity(&self) -> usize {
+        self.n
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.n && self.levels[0][i >> 6] >> (i & 63) & 1 != 0
+    }
+
+    /// Returns `true` if `i` was not in the set.
+    pub fn insert(&mut self, i: usize) -> bool {
+        assert!(i < self.n, "index out of range");
+        if self.contains(i) {
+            return false;
+        }
+        let mut i = i;
+        for level in &mut self.levels {
+            let was_empty = level[i >> 6] == 0;
+            level[i >> 6] |= 1 << (i & 63);
+            if !was_empty {
+                break;
+            }
+            i >>= 6;
+        }
+        self.len += 1;
+        true
+    }
+
+    /// Returns `true` if `i` was in the set.
+    pub fn remove(&mut self, i: usize) -> bool {
+        if !self.contains(i) {
+            return false;
+        }
+        let mut i = i;
+        for level in &mut self.levels {
+            level[i >> 6] &= !(1 << (i & 63));
+            if level[i >> 6] != 0 {
+                break;
+            }
+            i >>= 6;
+        }
+        self.len -= 1;
+        true
+    }
+
+    pub fn clear(&mut self) {
+        for level in &mut self.levels {
+            level.fill(0);
+        }
+        self.len = 0;
+    }
+
+    /// The smallest element `>= i`
+    pub fn next(&self, i: usize) -> Option<usize> {
+        if i >= self.n {
+            return None;
+        }
+        let mut i = i;
+        let mut d = 0;
+        // ascend until a word has a set bit at or after the position
+        loop {
+            let word = *self.levels[d].get(i >> 6)?;
+            let x = word & (!0 << (i & 63));
+            if x != 0 {
+                i = (i & !63) | x.trailing_zeros() as usize;
+                break;
+            }
+            d += 1;
+            if d == self.levels.len() {
+                return None;
+            }
+            i = (i >> 6) + 1;
+        }
+        // descend to the leftmost set bit
+        while d > 0 {
+            d -= 1;
+            i = (i << 6) | self.levels[d][i].trailing_zeros() as usize;
+        }
+        Some(i)
+    }
+
+    /// The largest element `<= i`
+    pub fn prev(&self, i: usize) -> Option<usize> {
+        let mut i = i.min(self.n.checked_sub(1)?);
+        let mut d = 0;
+        loop {
+            let x = self.levels[d][i >> 6] & (!0 >> (63 - (i & 63)));
+            if x != 0 {
+                i = (i & !63) | (63 - x.leading_zeros() as usize);
+                break;
+            }
+            d += 1;
+            if d == self.levels.len() {
+                return None;
+            }
+            i = (i >> 6).checked_sub(1)?;
+        }
+        while d > 0 {
+            d -= 1;
+            i = (i << 6) | (63 - self.levels[d][i].leading_zeros() as usize);
+        }
+        Some(i)
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.next(0)
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.prev(usize::MAX)
+    }
+
+    /// Iterates over the elements in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.levels[0].iter().enumerate().flat_map(|(k, &x)| {
+            let mut x = x;
+            std::iter::from_fn(move || {
+                (x != 0).then(|| {
+                    let i = x.trailing_zeros() as usize;
+                    x &= x - 1;
+                    (k << 6) | i
+                })
+            })
+        })
+    }
+}
+
+impl Extend<usize> for FastSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_fast_set() {
+        let mut rng = Xorshift::default();
+        for n in (0..300).chain([4095, 4096, 4097, 300_000]) {
+            let mut set = FastSet::new(n);
+            let mut reference = BTreeSet::new();
+            for _ in 0..2_000 {
+                let i = rng.random(0..n + 10);
+                match rng.random(0..4) {
+                    0 if i < n => assert_eq!(set.insert(i), reference.insert(i)),
+                    1 => assert_eq!(set.remove(i), reference.remove(&i)),
+                    2 => assert_eq!(set.next(i), reference.range(i..).next().copied()),
+                    _ => assert_eq!(set.prev(i), reference.range(..=i).next_back().copied()),
+                }
+                assert_eq!(set.contains(i), reference.contains(&i));
+                assert_eq!(set.len(), reference.len());
+            }
+            assert_eq!(set.min(), reference.first().copied());
+            assert_eq!(set.max(), reference.last().copied());
+            assert!(set.iter().eq(reference.iter().copied()));
+            set.clear();
+            assert!(set.is_empty());
+            assert_eq!(set.next(0), None);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Bounded, LazySegmentTree, Zero};
+use crate::{
+    algebra::{HistoricAdd, RangeHistoricRangeAdd},
+    num::One,
+};
+use std::ops::{Add, Mul, RangeBounds};
+
+/// Segment tree with range add and historic sum/max/min
+///
+/// The historic sums are accumulated by [`HistoricSegmentTree::record`],
+/// the historic max/min are the extremes of all values so far.
+#[derive(Debug, Clone)]
+pub struct HistoricSegmentTree<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    seg: LazySegmentTree<RangeHistoricRangeAdd<T>>,
+}
+
+impl<T> HistoricSegmentTree<T>
+where
+    T: Copy + Zero + One + Ord + Bounded + Add<Output = T> + Mul<Output = T>,
+{
+    pub fn from_vec(v: Vec<T>) -> Self {
+        Self {
+            seg: LazySegmentTree::from_keys(v.into_iter()),
+        }
+    }
+    pub fn add<R>(&mut self, range: R, x: T)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.seg.update(range, HistoricAdd::add(x));
+    }
+    /// Add the current values into the historic sums, e.g. once after each operation.
+    pub fn record<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.seg.update(range, HistoricAdd::record());
+    }
+    pub fn fold<R>(&mut self, range: R) -> RangeHistoricRangeAdd<T>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.seg.fold(range)
+    }
+    pub fn sum<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        self.fold(range).sum
+    }
+    pub fn historic_sum<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        self.fold(range).historic_sum
+    }
+    pub fn historic_max<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        self.fold(range).historic_max
+    }
+    pub fn historic_min<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        self.fold(range).historic_min
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rand,
+        tools::{NotEmptySegment, Xorshift},
+    };
+
+    #[test]
+    fn test_historic_segment_tree() {
+        const N: usize = 100;
+        const Q: usize = 10_000;
+        const A: i64 = 1_000_000;
+        let mut rng = Xorshift::default();
+        for _ in 0..10 {
+            rand!(rng, n: 1..=N, mut arr: [-A..=A; n]);
+            let mut hsum = vec![0i64; n];
+            let mut hmax = arr.clone();
+            let mut hmin = arr.clone();
+            let mut seg = HistoricSegmentTree::from_vec(arr.clone());
+            for _ in 0..Q {
+                rand!(rng, ty: 0..3, (l, r): NotEmptySegment(n));
+                match ty {
+                    0 => {
+                        rand!(rng, x: -A..=A);
+                        seg.add(l..r, x);
+                        for i in l..r {
+                            arr[i] += x;
+                            hmax[i] = hmax[i].max(arr[i]);
+                            hmin[i] = hmin[i].min(arr[i]);
+                        }
+                    }
+                    1 => {
+                        seg.record(l..r);
+                        for i in l..r {
+                            hsum[i] += arr[i];
+                        }
+                    }
+                    _ => {
+                        let res = seg.fold(l..r);
+                        assert_eq!(res.sum, arr[l..r].iter().sum::<i64>());
+                        assert_eq!(res.max, *arr[l..r].iter().max().unwrap());
+                        assert_eq!(res.min, *arr[l..r].iter().min().unwrap());
+                        assert_eq!(seg.historic_sum(l..r), hsum[l..r].iter().sum::<i64>());
+                        assert_eq!(seg.historic_max(l..r), *hmax[l..r].iter().max().unwrap());
+                        assert_eq!(seg.historic_min(l..r), *hmin[l..r].iter().min().unwrap());
+                    }
+                }
+            }
+        }
+    }
+}
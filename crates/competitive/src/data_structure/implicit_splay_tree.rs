@@ -1,7 +1,8 @@
 use super::{
-    Allocator, LazyMapMonoid, MemoryPool,
+    Allocator, LazyMapMonoid, MemoryPool, SharedAllocator,
     binary_search_tree::{
-        BstDataAccess, BstDataMutRef, BstNode, BstRoot, BstSeeker, BstSpec, EqualSide,
+        BstDataAccess, BstDataMutRef, BstNode, BstRangeIter, BstRoot, BstSeeker, BstSpec,
+        EqualSide,
         data::{self, LazyMapElement},
         node::WithNoParent,
         seeker::{SeekByAccCond, SeekByRaccCond, SeekBySize},
@@ -227,6 +228,13 @@ where
             _marker: PhantomData,
         }
     }
+
+    /// Builds a balanced tree in $O(n)$ time.
+    pub fn from_vec(v: Vec<T::Key>) -> Self {
+        let mut tree = Self::with_capacity(v.len());
+        tree.extend(v);
+        tree
+    }
 }
 
 impl<T, A> ImplicitSplayTree<T, A>
@@ -234,6 +242,15 @@ where
     T: LazyMapMonoid,
     A: Allocator<ImplicitSplayTreeNode<T>>,
 {
+    pub fn with_allocator(allocator: A) -> Self {
+        Self {
+            root: None,
+            length: 0,
+            allocator: ManuallyDrop::new(allocator),
+            _marker: PhantomData,
+        }
+    }
+
     fn node(&mut self, key: T::Key) -> ImplicitSplayTreeRoot<T> {
         BstRoot::from_data(
             ImplicitSplayTreeData {
@@ -400,6 +417,48 @@ where
         assert!(k <= self.length);
         self.rotate_left(self.length - k);
     }
+
+    pub fn iter(&mut self) -> BstRangeIter<'_, ImplicitSplayTreeSpec<T>, T> {
+        BstRangeIter::new(&mut self.root, ..)
+    }
+
+    /// Iterates over `range` without splaying.
+    pub fn range<R>(&mut self, range: R) -> BstRangeIter<'_, ImplicitSplayTreeSpec<T>, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        BstRangeIter::new(&mut self.root, range)
+    }
+
+    /// Moves `[at, len)` into a new sequence sharing the allocator.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: SharedAllocator<ImplicitSplayTreeNode<T>>,
+    {
+        assert!(at <= self.length);
+        let (left, right) =
+            ImplicitSplayTreeSpec::split(self.root.take(), SeekBySize::new(at), EqualSide::Right);
+        self.root = left;
+        let mut other = Self::with_allocator(self.allocator.share());
+        other.root = right;
+        other.length = self.length - at;
+        self.length = at;
+        other
+    }
+
+    /// Moves all elements of `other` to the end, `other` must share the allocator.
+    pub fn append(&mut self, other: &mut Self)
+    where
+        A: SharedAllocator<ImplicitSplayTreeNode<T>>,
+    {
+        assert!(
+            self.allocator.is_shared_with(&other.allocator),
+            "allocators must be shared"
+        );
+        self.root = ImplicitSplayTreeSpec::merge(self.root.take(), other.root.take());
+        self.length += other.length;
+        other.length = 0;
+    }
 }
 
 impl<T, A> Extend<T::Key> for ImplicitSplayTree<T, A>
@@ -447,11 +506,27 @@ where
     }
 }
 
+impl<T, A> FromIterator<T::Key> for ImplicitSplayTree<T, A>
+where
+    T: LazyMapMonoid,
+    A: Allocator<ImplicitSplayTreeNode<T>> + Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T::Key>,
+    {
+        let mut tree = Self::default();
+        tree.extend(iter);
+        tree
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         algebra::RangeMaxRangeUpdate,
+        data_structure::BoxAllocator,
         rand,
         tools::{NotEmptySegment, Xorshift},
     };
@@ -539,4 +614,50 @@ mod tests {
             assert_eq!(tree.get(index), arr.get(index));
         }
     }
+
+    #[test]
+    fn test_implicit_splay_tree_split_off_append() {
+        const A: i64 = 1_000_000_000;
+
+        let mut rng = Xorshift::default();
+        rand!(rng, arr: [-A..A; 1_000]);
+        let mut arrs = vec![arr, vec![]];
+        let mut trees: Vec<ImplicitSplayTree<RangeMaxRangeUpdate<_>, BoxAllocator<_>>> = arrs
+            .iter()
+            .map(|arr| arr.iter().copied().collect())
+            .collect();
+        for _ in 0..5_000 {
+            rand!(rng, k: 0usize..2, ty: 0..4);
+            let n = arrs[k].len();
+            rand!(rng, l: ..=n, r: ..=n);
+            let (l, r) = (l.min(r), l.max(r));
+            match ty {
+                0 => {
+                    rand!(rng, x: -A..A);
+                    trees[k].update(l..r, Some(x));
+                    arrs[k][l..r].fill(x);
+                }
+                1 => {
+                    trees[k].reverse(l..r);
+                    arrs[k][l..r].reverse();
+                }
+                2 => assert!(trees[k].range(l..r).eq(&arrs[k][l..r])),
+                _ => {
+                    let mut other = trees[k].split_off(l);
+                    let mut rest = arrs[k].split_off(l);
+                    trees[1 - k].append(&mut other);
+                    arrs[1 - k].append(&mut rest);
+                }
+            }
+            for (tree, arr) in trees.iter_mut().zip(&arrs) {
+                assert_eq!(tree.len(), arr.len());
+                assert_eq!(tree.fold(..), arr.iter().copied().max().unwrap_or(i64::MIN));
+            }
+        }
+        for (tree, arr) in trees.iter_mut().zip(&arrs) {
+            assert!(tree.iter().eq(arr));
+        }
+        let mut tree = ImplicitSplayTree::<RangeMaxRangeUpdate<_>>::from_vec(arrs[0].clone());
+        assert!(tree.iter().eq(&arrs[0]));
+    }
 }
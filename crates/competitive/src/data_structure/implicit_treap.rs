@@ -1,7 +1,8 @@
 use super::{
-    Allocator, LazyMapMonoid, MemoryPool, Xorshift,
+    Allocator, LazyMapMonoid, MemoryPool, SharedAllocator, Xorshift,
     binary_search_tree::{
-        BstDataAccess, BstDataMutRef, BstNode, BstRoot, BstSeeker, BstSpec, EqualSide,
+        BstDataAccess, BstDataMutRef, BstNode, BstRangeIter, BstRoot, BstSeeker, BstSpec,
+        EqualSide,
         data::{self, LazyMapElement},
         node::WithNoParent,
         seeker::{SeekByAccCond, SeekByRaccCond, SeekBySize},
@@ -274,6 +275,13 @@ where
             _marker: PhantomData,
         }
     }
+
+    /// Builds in $O(n)$ time.
+    pub fn from_vec(v: Vec<T::Key>) -> Self {
+        let mut treap = Self::with_capacity(v.len());
+        treap.extend(v);
+        treap
+    }
 }
 
 impl<T, A> ImplicitTreap<T, A>
@@ -281,6 +289,16 @@ where
     T: LazyMapMonoid,
     A: Allocator<ImplicitTreapNode<T>>,
 {
+    pub fn with_allocator(allocator: A) -> Self {
+        Self {
+            root: None,
+            length: 0,
+            rng: Xorshift::new(),
+            allocator: ManuallyDrop::new(allocator),
+            _marker: PhantomData,
+        }
+    }
+
     fn node(&mut self, key: T::Key) -> ImplicitTreapRoot<T> {
         BstRoot::from_data(
             ImplicitTreapData {
@@ -504,6 +522,48 @@ where
         assert!(k <= self.length);
         self.rotate_left(self.length - k);
     }
+
+    pub fn iter(&mut self) -> BstRangeIter<'_, ImplicitTreapSpec<T>, T> {
+        BstRangeIter::new(&mut self.root, ..)
+    }
+
+    /// Iterates over `range` without splitting the tree.
+    pub fn range<R>(&mut self, range: R) -> BstRangeIter<'_, ImplicitTreapSpec<T>, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        BstRangeIter::new(&mut self.root, range)
+    }
+
+    /// Moves `[at, len)` into a new sequence sharing the allocator.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: SharedAllocator<ImplicitTreapNode<T>>,
+    {
+        assert!(at <= self.length);
+        let (left, right) =
+            ImplicitTreapSpec::<T>::split(self.root.take(), SeekBySize::new(at), EqualSide::Right);
+        self.root = left;
+        let mut other = Self::with_allocator(self.allocator.share());
+        other.root = right;
+        other.length = self.length - at;
+        self.length = at;
+        other
+    }
+
+    /// Moves all elements of `other` to the end, `other` must share the allocator.
+    pub fn append(&mut self, other: &mut Self)
+    where
+        A: SharedAllocator<ImplicitTreapNode<T>>,
+    {
+        assert!(
+            self.allocator.is_shared_with(&other.allocator),
+            "allocators must be shared"
+        );
+        self.root = ImplicitTreapSpec::<T>::merge(self.root.take(), other.root.take());
+        self.length += other.length;
+        other.length = 0;
+    }
 }
 
 impl<T, A> Extend<T::Key> for ImplicitTreap<T, A>
@@ -521,14 +581,64 @@ where
     }
 }
 
+impl<T, A> FromIterator<T::Key> for ImplicitTreap<T, A>
+where
+    T: LazyMapMonoid,
+    A: Allocator<ImplicitTreapNode<T>> + Default,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T::Key>,
+    {
+        let mut treap = Self::default();
+        treap.extend(iter);
+        treap
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        algebra::{RangeChminChmaxAdd, RangeSumRangeChminChmaxAdd},
+        algebra::{
+            AdditiveOperation, Associative, FlattenAct, Magma, RangeChminChmaxAdd,
+            RangeSumRangeChminChmaxAdd, ReversibleLazy, Unital,
+        },
         num::Saturating,
         tools::{NotEmptySegment, Xorshift},
     };
+    use std::{cell::RefCell, rc::Rc};
+
+    /// (sum, sum of index * value, count)
+    struct WeightedSumOperation;
+    impl Magma for WeightedSumOperation {
+        type T = (i64, i64, i64);
+        fn operate(x: &Self::T, y: &Self::T) -> Self::T {
+            (x.0 + y.0, x.1 + y.1 + x.2 * y.0, x.2 + y.2)
+        }
+    }
+    impl Unital for WeightedSumOperation {
+        fn unit() -> Self::T {
+            (0, 0, 0)
+        }
+    }
+    impl Associative for WeightedSumOperation {}
+
+    struct RangeWeightedSumRangeAdd;
+    impl LazyMapMonoid for RangeWeightedSumRangeAdd {
+        type Key = i64;
+        type Agg = (i64, i64, i64);
+        type Act = i64;
+        type AggMonoid = WeightedSumOperation;
+        type ActMonoid = AdditiveOperation<i64>;
+        type KeyAct = FlattenAct<AdditiveOperation<i64>>;
+        fn single_agg(key: &Self::Key) -> Self::Agg {
+            (*key, 0, 1)
+        }
+        fn act_agg(&(s, w, c): &Self::Agg, a: &Self::Act) -> Option<Self::Agg> {
+            Some((s + a * c, w + a * c * (c - 1) / 2, c))
+        }
+    }
 
     #[test]
     fn test_implicit_treap_range_sum_chmin_chmax_add_random() {
@@ -653,4 +763,92 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_implicit_treap_split_off_append() {
+        type L = ReversibleLazy<RangeWeightedSumRangeAdd>;
+        type Pool = Rc<RefCell<MemoryPool<ImplicitTreapNode<L>>>>;
+        const A: i64 = 1_000;
+
+        let mut rng = Xorshift::default();
+        let pool = Pool::default();
+        let mut arrs: Vec<Vec<i64>> = vec![];
+        let mut treaps: Vec<ImplicitTreap<L, Pool>> = vec![];
+        for _ in 0..3 {
+            let arr: Vec<_> = (0..rng.random(0..100))
+                .map(|_| rng.random(-A..=A))
+                .collect();
+            let mut treap = ImplicitTreap::with_allocator(pool.share());
+            treap.extend(arr.iter().copied());
+            arrs.push(arr);
+            treaps.push(treap);
+        }
+        for _ in 0..5_000 {
+            let k = rng.random(0usize..3);
+            let n = arrs[k].len();
+            let (l, r) = (rng.random(0..=n), rng.random(0..=n));
+            let (l, r) = (l.min(r), l.max(r));
+            match rng.random(0..6) {
+                0 => {
+                    treaps[k].reverse(l..r);
+                    arrs[k][l..r].reverse();
+                }
+                1 => {
+                    let x = rng.random(-A..=A);
+                    treaps[k].update(l..r, x);
+                    arrs[k][l..r].iter_mut().for_each(|a| *a += x);
+                }
+                2 => {
+                    let arr = &arrs[k][l..r];
+                    let (sum, weighted, count) = treaps[k].fold(l..r).0;
+                    assert_eq!(sum, arr.iter().sum::<i64>());
+                    assert_eq!(
+                        weighted,
+                        arr.iter()
+                            .enumerate()
+                            .map(|(i, a)| i as i64 * a)
+                            .sum::<i64>()
+                    );
+                    assert_eq!(count, (r - l) as i64);
+                }
+                3 => {
+                    assert!(treaps[k].range(l..r).eq(&arrs[k][l..r]));
+                    assert_eq!(treaps[k].range(l..r).len(), r - l);
+                }
+                4 => {
+                    let mut other = treaps[k].split_off(l);
+                    let mut rest = arrs[k].split_off(l);
+                    assert!(other.iter().eq(&rest));
+                    let j = rng.random(0usize..3);
+                    if j == k {
+                        other.append(&mut treaps[k]);
+                        treaps[k] = other;
+                        rest.append(&mut arrs[k]);
+                        arrs[k] = rest;
+                    } else {
+                        treaps[j].append(&mut other);
+                        arrs[j].append(&mut rest);
+                    }
+                }
+                _ => {
+                    let v: Vec<i64> = (0..rng.random(0..50)).map(|_| rng.random(-A..=A)).collect();
+                    let mut other = ImplicitTreap::with_allocator(pool.share());
+                    other.extend(v.iter().copied());
+                    treaps[k].append(&mut other);
+                    arrs[k].extend(v);
+                }
+            }
+            for (treap, arr) in treaps.iter_mut().zip(&arrs) {
+                assert_eq!(treap.len(), arr.len());
+            }
+        }
+        for (treap, arr) in treaps.iter_mut().zip(&arrs) {
+            assert!(treap.iter().eq(arr));
+        }
+        let arr: Vec<_> = (0..1_000).map(|_| rng.random(-A..=A)).collect();
+        let mut treap = ImplicitTreap::<RangeWeightedSumRangeAdd>::from_vec(arr.clone());
+        assert!(treap.iter().eq(&arr));
+        let treap: ImplicitTreap<RangeWeightedSumRangeAdd> = arr.iter().copied().collect();
+        assert_eq!(treap.len(), arr.len());
+    }
 }
@@ -0,0 +1,474 @@
+use super::{LazyMapMonoid, Zero};
+use std::{
+    collections::BinaryHeap,
+    mem::replace,
+    ops::{Add, Mul, Range, Sub},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdTreeMetric {
+    /// squared Euclidean distance
+    Euclidean,
+    Manhattan,
+}
+
+/// K-d tree with [`LazyMapMonoid`] aggregates of active points
+///
+/// Aggregates are folded in an unspecified order, so `M::AggMonoid` should be commutative.
+pub struct LazyKdTree<T, M, const K: usize>
+where
+    M: LazyMapMonoid,
+{
+    /// the node of a subtree `l..r` is at `(l + r) / 2`
+    points: Vec<[T; K]>,
+    /// original index of each node
+    ids: Vec<usize>,
+    /// node of each original index
+    nodes: Vec<usize>,
+    keys: Vec<Option<M::Key>>,
+    agg: Vec<M::Agg>,
+    lazy: Vec<M::Act>,
+    /// number of active points in the subtree
+    count: Vec<usize>,
+    lower: Vec<[T; K]>,
+    upper: Vec<[T; K]>,
+}
+
+impl<T, M, const K: usize> LazyKdTree<T, M, K>
+where
+    T: Copy + Ord + Zero + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    M: LazyMapMonoid<Key: Clone>,
+{
+    /// Points are indexed in the given order, and are initially inactive.
+    pub fn new(points: Vec<[T; K]>) -> Self {
+        Self::from_keys(points.into_iter().map(|p| (p, None)))
+    }
+
+    /// Points are indexed in the given order, and are active if the key is `Some`.
+    pub fn from_keys(points: impl IntoIterator<Item = ([T; K], Option<M::Key>)>) -> Self {
+        let mut items: Vec<_> = points
+            .into_iter()
+            .enumerate()
+            .map(|(i, (p, key))| (p, (i, key)))
+            .collect();
+        let n = items.len();
+        Self::arrange(&mut items, 0);
+        let mut nodes = vec![0; n];
+        let mut points = Vec::with_capacity(n);
+        let mut ids = Vec::with_capacity(n);
+        let mut keys = Vec::with_capacity(n);
+        for (k, (p, (i, key))) in items.into_iter().enumerate() {
+            nodes[i] = k;
+            points.push(p);
+            ids.push(i);
+            keys.push(key);
+        }
+        let mut self_ = Self {
+            lower: points.clone(),
+            upper: points.clone(),
+            points,
+            ids,
+            nodes,
+            keys,
+            agg: vec![M::agg_unit(); n],
+            lazy: vec![M::act_unit(); n],
+            count: vec![0; n],
+        };
+        self_.build(0, n);
+        self_
+    }
+
+    fn arrange<U>(items: &mut [([T; K], U)], depth: usize) {
+        if items.len() <= 1 {
+            return;
+        }
+        let m = items.len() / 2;
+        let d = depth % K;
+        items.select_nth_unstable_by(m, |a, b| a.0[d].cmp(&b.0[d]));
+        let (left, right) = items.split_at_mut(m);
+        Self::arrange(left, depth + 1);
+        Self::arrange(&mut right[1..], depth + 1);
+    }
+
+    fn build(&mut self, l: usize, r: usize) {
+        if l >= r {
+            return;
+        }
+        let m = (l + r) / 2;
+        self.build(l, m);
+        self.build(m + 1, r);
+        for c in [Self::child(l, m), Self::child(m + 1, r)]
+            .into_iter()
+            .flatten()
+        {
+            for d in 0..K {
+                self.lower[m][d] = self.lower[m][d].min(self.lower[c][d]);
+                self.upper[m][d] = self.upper[m][d].max(self.upper[c][d]);
+            }
+        }
+        self.recalc(l, r);
+    }
+
+    fn child(l: usize, r: usize) -> Option<usize> {
+        (l < r).then(|| (l + r) / 2)
+    }
+
+    fn subtree_agg(&self, l: usize, r: usize) -> M::Agg {
+        Self::child(l, r).map_or_else(M::agg_unit, |c| self.agg[c].clone())
+    }
+
+    fn recalc(&mut self, l: usize, r: usize) {
+        let m = (l + r) / 2;
+        let own = self.keys[m]
+            .as_ref()
+            .map_or_else(M::agg_unit, |key| M::single_agg(key));
+        self.agg[m] = M::agg_operate(
+            &M::agg_operate(&self.subtree_agg(l, m), &own),
+            &self.subtree_agg(m + 1, r),
+        );
+        self.count[m] = self.keys[m].is_some() as usize
+            + Self::child(l, m).map_or(0, |c| self.count[c])
+            + Self::child(m + 1, r).map_or(0, |c| self.count[c]);
+    }
+
+    fn apply(&mut self, l: usize, r: usize, act: &M::Act) {
+        if l >= r || M::is_act_unit(act) {
+            return;
+        }
+        let m = (l + r) / 2;
+        if let Some(key) = &self.keys[m] {
+            self.keys[m] = Some(M::act_key(key, act));
+        }
+        if r - l > 1 {
+            self.lazy[m] = M::act_operate(&self.lazy[m], act);
+        }
+        if let Some(agg) = M::act_agg(&self.agg[m], act) {
+            self.agg[m] = agg;
+        } else {
+            self.propagate(l, r);
+            self.recalc(l, r);
+        }
+    }
+
+    fn propagate(&mut self, l: usize, r: usize) {
+        let m = (l + r) / 2;
+        let lazy = replace(&mut self.lazy[m], M::act_unit());
+        self.apply(l, m, &lazy);
+        self.apply(m + 1, r, &lazy);
+    }
+
+    fn contains(rect: &[Range<T>; K], p: &[T; K]) -> bool {
+        rect.iter().zip(p).all(|(range, x)| range.contains(x))
+    }
+
+    /// Return (disjoint, covered) of the subtree box and `rect`
+    fn relation(&self, m: usize, rect: &[Range<T>; K]) -> (bool, bool) {
+        let mut disjoint = false;
+        let mut covered = true;
+        for (d, range) in rect.iter().enumerate() {
+            disjoint |= self.upper[m][d] < range.start || range.end <= self.lower[m][d];
+            covered &= range.start <= self.lower[m][d] && self.upper[m][d] < range.end;
+        }
+        (disjoint, covered)
+    }
+
+    fn fold_dfs(&mut self, l: usize, r: usize, rect: &[Range<T>; K]) -> M::Agg {
+        if l >= r {
+            return M::agg_unit();
+        }
+        let m = (l + r) / 2;
+        let (disjoint, covered) = self.relation(m, rect);
+        if disjoint || self.count[m] == 0 {
+            return M::agg_unit();
+        }
+        if covered {
+            return self.agg[m].clone();
+        }
+        self.propagate(l, r);
+        let mut acc = self.fold_dfs(l, m, rect);
+        if let Some(key) = &self.keys[m]
+            && Self::contains(rect, &self.points[m])
+        {
+            acc = M::agg_operate(&acc, &M::single_agg(key));
+        }
+        M::agg_operate(&acc, &self.fold_dfs(m + 1, r, rect))
+    }
+
+    fn update_dfs(&mut self, l: usize, r: usize, rect: &[Range<T>; K], act: &M::Act) {
+        if l >= r {
+            return;
+        }
+        let m = (l + r) / 2;
+        let (disjoint, covered) = self.relation(m, rect);
+        if disjoint || self.count[m] == 0 {
+            return;
+        }
+        if covered {
+            self.apply(l, r, act);
+            return;
+        }
+        self.propagate(l, r);
+        if let Some(key) = &self.keys[m]
+            && Self::contains(rect, &self.points[m])
+        {
+            self.keys[m] = Some(M::act_key(key, act));
+        }
+        self.update_dfs(l, m, rect, act);
+        self.update_dfs(m + 1, r, rect, act);
+        self.recalc(l, r);
+    }
+
+    /// Replace the key of `node` if `key` is `Some`, and return the previous key
+    fn set_dfs(
+        &mut self,
+        l: usize,
+        r: usize,
+        node: usize,
+        key: Option<Option<M::Key>>,
+    ) -> Option<M::Key> {
+        let m = (l + r) / 2;
+        self.propagate(l, r);
+        let res = if m == node {
+            match key {
+                Some(key) => replace(&mut self.keys[m], key),
+                None => self.keys[m].clone(),
+            }
+        } else if node < m {
+            self.set_dfs(l, m, node, key)
+        } else {
+            self.set_dfs(m + 1, r, node, key)
+        };
+        self.recalc(l, r);
+        res
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Activate the `i`-th point with `key`, or deactivate it with `None`.
+    pub fn set(&mut self, i: usize, key: Option<M::Key>) {
+        let n = self.len();
+        self.set_dfs(0, n, self.nodes[i], Some(key));
+    }
+
+    pub fn get(&mut self, i: usize) -> Option<M::Key> {
+        let n = self.len();
+        self.set_dfs(0, n, self.nodes[i], None)
+    }
+
+    /// Fold active points in the box `rect`
+    pub fn fold(&mut self, rect: [Range<T>; K]) -> M::Agg {
+        let n = self.len();
+        self.fold_dfs(0, n, &rect)
+    }
+
+    /// Act on active points in the box `rect`
+    pub fn update(&mut self, rect: [Range<T>; K], act: M::Act) {
+        let n = self.len();
+        self.update_dfs(0, n, &rect, &act);
+    }
+
+    fn distance(metric: KdTreeMetric, p: &[T; K], lower: &[T; K], upper: &[T; K]) -> T {
+        let mut res = T::zero();
+        for d in 0..K {
+            let diff = if p[d] < lower[d] {
+                lower[d] - p[d]
+            } else if upper[d] < p[d] {
+                p[d] - upper[d]
+            } else {
+                T::zero()
+            };
+            res = res
+                + match metric {
+                    KdTreeMetric::Euclidean => diff * diff,
+                    KdTreeMetric::Manhattan => diff,
+                };
+        }
+        res
+    }
+
+    fn nearest_dfs(
+        &self,
+        l: usize,
+        r: usize,
+        p: &[T; K],
+        k: usize,
+        metric: KdTreeMetric,
+        heap: &mut BinaryHeap<(T, usize)>,
+    ) {
+        if l >= r {
+            return;
+        }
+        let m = (l + r) / 2;
+        if self.count[m] == 0
+            || heap.len() == k
+                && heap.peek().unwrap().0
+                    <= Self::distance(metric, p, &self.lower[m], &self.upper[m])
+        {
+            return;
+        }
+        if self.keys[m].is_some() {
+            let dist = Self::distance(metric, p, &self.points[m], &self.points[m]);
+            heap.push((dist, self.ids[m]));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        let mut children = [(l, m), (m + 1, r)];
+        let dist = |(l, r): (usize, usize)| {
+            Self::child(l, r).map(|c| Self::distance(metric, p, &self.lower[c], &self.upper[c]))
+        };
+        if dist(children[1]) < dist(children[0]) {
+            children.swap(0, 1);
+        }
+        for (l, r) in children {
+            self.nearest_dfs(l, r, p, k, metric, heap);
+        }
+    }
+
+    /// Return at most `k` pairs of (distance, index) of active points nearest to `p`, ordered by distance.
+    pub fn k_nearest(&self, p: [T; K], k: usize, metric: KdTreeMetric) -> Vec<(T, usize)> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.nearest_dfs(0, self.len(), &p, k, metric, &mut heap);
+        }
+        heap.into_sorted_vec()
+    }
+
+    /// Return (distance, index) of the active point nearest to `p`.
+    pub fn nearest(&self, p: [T; K], metric: KdTreeMetric) -> Option<(T, usize)> {
+        self.k_nearest(p, 1, metric).pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::RangeSumRangeLinear, num::montgomery::MInt998244353 as M, tools::Xorshift,
+    };
+
+    type Tree = LazyKdTree<i64, RangeSumRangeLinear<M>, 2>;
+
+    #[test]
+    fn test_lazy_kdtree() {
+        const Q: usize = 2_000;
+        let mut rng = Xorshift::default();
+        for _ in 0..20 {
+            let n = rng.random(0..100usize);
+            let points: Vec<[i64; 2]> = (0..n)
+                .map(|_| [rng.random(-20..20), rng.random(-20..20)])
+                .collect();
+            let mut values: Vec<Option<M>> = (0..n)
+                .map(|_| rng.gen_bool(0.7).then(|| M::from(rng.random(0..1000u32))))
+                .collect();
+            let mut tree = Tree::from_keys(points.iter().copied().zip(values.iter().copied()));
+            let rand_rect = |rng: &mut Xorshift| {
+                [0, 1].map(|_| {
+                    let (a, b) = (rng.random(-21i64..21), rng.random(-21..21));
+                    a.min(b)..a.max(b)
+                })
+            };
+            for _ in 0..Q {
+                match rng.random(0..5) {
+                    0 if n > 0 => {
+                        let i = rng.random(0..n);
+                        let x = rng.gen_bool(0.7).then(|| M::from(rng.random(0..1000u32)));
+                        tree.set(i, x);
+                        values[i] = x;
+                        assert_eq!(tree.get(i), x);
+                    }
+                    1 => {
+                        let rect = rand_rect(&mut rng);
+                        let a = M::from(rng.random(0..1000u32));
+                        let b = M::from(rng.random(0..1000u32));
+                        tree.update(rect.clone(), (a, b));
+                        for (p, v) in points.iter().zip(&mut values) {
+                            if let Some(v) = v
+                                && Tree::contains(&rect, p)
+                            {
+                                *v = a * *v + b;
+                            }
+                        }
+                    }
+                    2 => {
+                        let rect = rand_rect(&mut rng);
+                        let expected: M = points
+                            .iter()
+                            .zip(&values)
+                            .filter(|(p, _)| Tree::contains(&rect, p))
+                            .filter_map(|(_, v)| *v)
+                            .sum();
+                        assert_eq!(tree.fold(rect).0, expected);
+                    }
+                    _ => {
+                        let p: [i64; 2] = [rng.random(-25..25), rng.random(-25..25)];
+                        let k = rng.random(0..5);
+                        for metric in [KdTreeMetric::Euclidean, KdTreeMetric::Manhattan] {
+                            let dist = |q: &[i64; 2]| {
+                                let (dx, dy) = ((p[0] - q[0]).abs(), (p[1] - q[1]).abs());
+                                match metric {
+                                    KdTreeMetric::Euclidean => dx * dx + dy * dy,
+                                    KdTreeMetric::Manhattan => dx + dy,
+                                }
+                            };
+                            let mut expected: Vec<i64> = points
+                                .iter()
+                                .zip(&values)
+                                .filter(|(_, v)| v.is_some())
+                                .map(|(q, _)| dist(q))
+                                .collect();
+                            expected.sort_unstable();
+                            assert_eq!(
+                                tree.nearest(p, metric).map(|(d, _)| d),
+                                expected.first().copied()
+                            );
+                            expected.truncate(k);
+                            let result = tree.k_nearest(p, k, metric);
+                            for &(d, i) in &result {
+                                assert!(values[i].is_some());
+                                assert_eq!(d, dist(&points[i]));
+                            }
+                            assert_eq!(
+                                result.iter().map(|&(d, _)| d).collect::<Vec<_>>(),
+                                expected
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lazy_kdtree_3d() {
+        let mut rng = Xorshift::default();
+        let points: Vec<[u32; 3]> = (0..200)
+            .map(|_| [rng.random(0..10), rng.random(0..10), rng.random(0..10)])
+            .collect();
+        let mut tree = LazyKdTree::<u32, RangeSumRangeLinear<M>, 3>::new(points.clone());
+        let mut active = vec![false; points.len()];
+        for _ in 0..1_000 {
+            let i = rng.random(0..points.len());
+            active[i] ^= true;
+            tree.set(i, active[i].then(|| M::new(1)));
+            let rect = [0, 1, 2].map(|_| {
+                let (a, b) = (rng.random(0u32..11), rng.random(0..11));
+                a.min(b)..a.max(b)
+            });
+            let expected = points
+                .iter()
+                .zip(&active)
+                .filter(|&(p, &a)| {
+                    a && LazyKdTree::<u32, RangeSumRangeLinear<M>, 3>::contains(&rect, p)
+                })
+                .count();
+            assert_eq!(tree.fold(rect).0, M::from(expected as u32));
+        }
+    }
+}
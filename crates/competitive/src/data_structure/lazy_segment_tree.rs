@@ -1,4 +1,4 @@
-use super::{LazyMapMonoid, RangeBoundsExt};
+use super::{LazyMapMonoid, LazyMapMonoidBeats, RangeBoundsExt};
 use std::{
     fmt::{self, Debug, Formatter},
     mem::replace,
@@ -165,6 +165,63 @@ where
             }
         }
     }
+    fn update_beats_at(&mut self, k: usize, u: &M::Update)
+    where
+        M: LazyMapMonoidBeats,
+    {
+        if let Some(x) = M::update_act(&self.seg[k], u) {
+            self.update_at(k, &x);
+        } else {
+            assert!(k < self.n, "update failed on leaf");
+            self.propagate_at(k);
+            self.update_beats_at(2 * k, u);
+            self.update_beats_at(2 * k + 1, u);
+            self.recalc_at(k);
+        }
+    }
+    /// Segment Tree Beats: descend into the nodes where the update cannot be converted into an act
+    pub fn update_beats<R>(&mut self, range: R, u: M::Update)
+    where
+        R: RangeBounds<usize>,
+        M: LazyMapMonoidBeats,
+    {
+        let range = range.to_range_bounded(0, self.len).expect("invalid range");
+        if range.is_empty() {
+            return;
+        }
+        let mut a = range.start + self.n;
+        let mut b = range.end + self.n;
+        for i in (1..=self.n.trailing_zeros()).rev() {
+            if (a >> i) << i != a {
+                self.propagate_at(a >> i);
+            }
+            if (b >> i) << i != b {
+                self.propagate_at((b - 1) >> i);
+            }
+        }
+        while a < b {
+            if a & 1 != 0 {
+                self.update_beats_at(a, &u);
+                a += 1;
+            }
+            if b & 1 != 0 {
+                b -= 1;
+                self.update_beats_at(b, &u);
+            }
+            a /= 2;
+            b /= 2;
+        }
+        let a = range.start + self.n;
+        let b = range.end + self.n;
+        for i in 1..=self.n.trailing_zeros() {
+            if (a >> i) << i != a {
+                self.recalc_at(a >> i);
+            }
+            if (b >> i) << i != b {
+                self.recalc_at((b - 1) >> i);
+            }
+        }
+    }
     pub fn fold<R>(&mut self, range: R) -> M::Agg
     where
         R: RangeBounds<usize>,
@@ -315,9 +372,11 @@ mod tests {
     use super::*;
     use crate::{
         algebra::{
-            RangeChminChmaxAdd, RangeMaxRangeUpdate, RangeSumRangeAdd, RangeSumRangeChminChmaxAdd,
+            ChminOrGcd, ChminOrHistoricAdd, HistoricAdd, RangeBeatsUpdate, RangeChminChmaxAdd,
+            RangeHistoricRangeChminAdd, RangeMaxRangeUpdate, RangeSumMaxRangeChminGcd,
+            RangeSumMaxRangeUpdateGcd, RangeSumRangeAdd, RangeSumRangeChminChmaxAdd, UpdateOrGcd,
         },
-        num::Saturating,
+        num::{Saturating, Unsigned},
         rand,
         tools::{NotEmptySegment, Xorshift},
     };
@@ -452,4 +511,152 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_lazy_segment_tree_beats() {
+        let mut rng = Xorshift::default();
+        // Range Sum/Min/Max Query & Range Chmin/Add/Update/Div/Mod/Sqrt Query
+        let mut arr = rng
+            .random_iter(0..=A)
+            .map(Saturating)
+            .take(N)
+            .collect::<Vec<_>>();
+        let mut seg =
+            LazySegmentTree::<RangeSumRangeChminChmaxAdd<_>>::from_keys(arr.iter().copied());
+        for _ in 0..Q {
+            rand!(rng, ty: 0..7, (l, r): NotEmptySegment(N));
+            match ty {
+                0 => {
+                    rand!(rng, x: 0..=A);
+                    let x = Saturating(x);
+                    seg.update_beats(l..r, RangeBeatsUpdate::Act(RangeChminChmaxAdd::chmin(x)));
+                    arr[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                }
+                1 => {
+                    rand!(rng, x: 0..=A);
+                    let x = Saturating(x);
+                    seg.update_beats(l..r, RangeBeatsUpdate::Act(RangeChminChmaxAdd::add(x)));
+                    arr[l..r].iter_mut().for_each(|a| *a += x);
+                }
+                2 => {
+                    rand!(rng, x: 0..=A);
+                    let x = Saturating(x);
+                    seg.update_beats(l..r, RangeBeatsUpdate::Act(RangeChminChmaxAdd::update(x)));
+                    arr[l..r].iter_mut().for_each(|a| *a = x);
+                }
+                3 => {
+                    rand!(rng, x: 1..=10);
+                    let x = Saturating(x);
+                    seg.update_beats(l..r, RangeBeatsUpdate::Div(x));
+                    arr[l..r].iter_mut().for_each(|a| *a /= x);
+                }
+                4 => {
+                    rand!(rng, x: 1..=A);
+                    let x = Saturating(x);
+                    seg.update_beats(l..r, RangeBeatsUpdate::Mod(x));
+                    arr[l..r].iter_mut().for_each(|a| *a %= x);
+                }
+                5 => {
+                    seg.update_beats(l..r, RangeBeatsUpdate::Sqrt);
+                    arr[l..r]
+                        .iter_mut()
+                        .for_each(|a| *a = Saturating(a.0.isqrt()));
+                }
+                _ => {
+                    let res = seg.fold(l..r);
+                    assert_eq!(res.sum, arr[l..r].iter().copied().sum::<Saturating<i64>>());
+                    assert_eq!(res.min, arr[l..r].iter().copied().min().unwrap());
+                    assert_eq!(res.max, arr[l..r].iter().copied().max().unwrap());
+                }
+            }
+        }
+
+        // Range Sum/Max Query & Range Update/Gcd Query
+        rand!(rng, mut arr: [0u64..=A as u64; N]);
+        let mut seg =
+            LazySegmentTree::<RangeSumMaxRangeUpdateGcd<_>>::from_keys(arr.iter().copied());
+        for _ in 0..Q {
+            rand!(rng, ty: 0..3, (l, r): NotEmptySegment(N));
+            match ty {
+                0 => {
+                    rand!(rng, x: 0..=A as u64);
+                    seg.update_beats(l..r, UpdateOrGcd::Update(x));
+                    arr[l..r].iter_mut().for_each(|a| *a = x);
+                }
+                1 => {
+                    rand!(rng, x: 0..=A as u64);
+                    seg.update_beats(l..r, UpdateOrGcd::Gcd(x));
+                    arr[l..r].iter_mut().for_each(|a| *a = a.gcd(x));
+                }
+                _ => {
+                    let res = seg.fold(l..r);
+                    assert_eq!(res.sum, arr[l..r].iter().sum());
+                    assert_eq!(res.max, *arr[l..r].iter().max().unwrap());
+                }
+            }
+        }
+
+        // Range Sum/Max Query & Range Chmin/Gcd Query
+        rand!(rng, mut arr: [0u64..=A as u64; N]);
+        let mut seg =
+            LazySegmentTree::<RangeSumMaxRangeChminGcd<_>>::from_keys(arr.iter().copied());
+        for _ in 0..Q {
+            rand!(rng, ty: 0..3, (l, r): NotEmptySegment(N));
+            match ty {
+                0 => {
+                    rand!(rng, x: 0..=A as u64);
+                    seg.update_beats(l..r, ChminOrGcd::Chmin(x));
+                    arr[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                }
+                1 => {
+                    rand!(rng, x: 0..=A as u64);
+                    seg.update_beats(l..r, ChminOrGcd::Gcd(x));
+                    arr[l..r].iter_mut().for_each(|a| *a = a.gcd(x));
+                }
+                _ => {
+                    let res = seg.fold(l..r);
+                    assert_eq!(res.sum, arr[l..r].iter().sum());
+                    assert_eq!(res.max, *arr[l..r].iter().max().unwrap());
+                }
+            }
+        }
+
+        // Range Sum/Max/Historic Sum/Historic Max Query & Range Chmin/Add Query
+        rand!(rng, mut arr: [-A..=A; N]);
+        let mut hmax = arr.clone();
+        let mut hsum = vec![0i64; N];
+        let mut seg =
+            LazySegmentTree::<RangeHistoricRangeChminAdd<_>>::from_keys(arr.iter().copied());
+        for _ in 0..Q {
+            rand!(rng, ty: 0..4, (l, r): NotEmptySegment(N));
+            match ty {
+                0 => {
+                    rand!(rng, x: -A..=A);
+                    seg.update_beats(l..r, ChminOrHistoricAdd::Chmin(x));
+                    arr[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                }
+                1 => {
+                    rand!(rng, x: -A..=A);
+                    seg.update_beats(l..r, ChminOrHistoricAdd::Act(HistoricAdd::add(x)));
+                    arr[l..r].iter_mut().for_each(|a| *a += x);
+                }
+                2 => {
+                    seg.update_beats(l..r, ChminOrHistoricAdd::Act(HistoricAdd::record()));
+                    for (h, a) in hsum[l..r].iter_mut().zip(&arr[l..r]) {
+                        *h += a;
+                    }
+                }
+                _ => {
+                    let res = seg.fold(l..r);
+                    assert_eq!(res.sum, arr[l..r].iter().sum());
+                    assert_eq!(res.max, *arr[l..r].iter().max().unwrap());
+                    assert_eq!(res.historic_sum, hsum[l..r].iter().sum());
+                    assert_eq!(res.historic_max, *hmax[l..r].iter().max().unwrap());
+                }
+            }
+            for (h, a) in hmax.iter_mut().zip(&arr) {
+                *h = (*h).max(*a);
+            }
+        }
+    }
 }
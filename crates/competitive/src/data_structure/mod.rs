@@ -2,20 +2,22 @@
 
 use crate::algebra::{
     AbelianGroup, AbelianMonoid, AdditiveOperation, Associative, EmptyAct, Group, LazyMapMonoid,
-    Magma, MaxOperation, MinOperation, Monoid, MonoidAct, SemiGroup, Unital,
+    LazyMapMonoidBeats, Magma, MaxOperation, MinOperation, Monoid, MonoidAct, SemiGroup, Unital,
 };
 use crate::algorithm::{BitDpExt, RadixSortKey, SliceBisectExt, SliceSortExt};
-use crate::num::{Bounded, IntBase, RangeBoundsExt, Zero};
+use crate::num::{Bounded, IntBase, RangeBoundsExt, Signed, Zero};
 use crate::tools::{Comparator, Xorshift, comparator};
 
 #[codesnip::entry("Accumulate")]
 pub use self::accumulate::{Accumulate, Accumulate2d, AccumulateKd};
 #[codesnip::entry("Allocator")]
-pub use self::allocator::{Allocator, BoxAllocator, MemoryPool};
+pub use self::allocator::{Allocator, BoxAllocator, MemoryPool, SharedAllocator};
 #[codesnip::entry("BinaryIndexedTree")]
-pub use self::binary_indexed_tree::BinaryIndexedTree;
+pub use self::binary_indexed_tree::{BinaryIndexedTree, RangeAddBinaryIndexedTree};
 #[codesnip::entry("BinaryIndexedTree2D")]
 pub use self::binary_indexed_tree_2d::BinaryIndexedTree2D;
+#[codesnip::entry("BinaryIndexedTreeKD")]
+pub use self::binary_indexed_tree_kd::BinaryIndexedTreeKD;
 #[codesnip::entry("BinaryTrie")]
 pub use self::binary_trie::BinaryTrie;
 #[codesnip::entry("BitVector")]
@@ -45,16 +47,24 @@ pub use self::counter::{BTreeCounter, HashCounter};
 pub use self::disjoint_sparse_table::DisjointSparseTable;
 #[codesnip::entry("DoublyLinkedList")]
 pub use self::doubly_linked_list::DoublyLinkedList;
+#[codesnip::entry("DynamicSegmentTree")]
+pub use self::dynamic_segment_tree::DynamicSegmentTree;
+#[codesnip::entry("FastSet")]
+pub use self::fast_set::FastSet;
 #[codesnip::entry("FibonacciHash")]
 pub use self::fibonacci_hash::{
     FibHashMap, FibHashSet, FibonacciHasher, FibonacciHasheru32, FibonacciHasheru64,
 };
+#[codesnip::entry("HistoricSegmentTree")]
+pub use self::historic_segment_tree::HistoricSegmentTree;
 #[codesnip::entry("ImplicitSplayTree")]
 pub use self::implicit_splay_tree::ImplicitSplayTree;
 #[codesnip::entry("ImplicitTreap")]
 pub use self::implicit_treap::ImplicitTreap;
 #[codesnip::entry("Static2DTree")]
 pub use self::kdtree::Static2DTree;
+#[codesnip::entry("LazyKdTree")]
+pub use self::lazy_kdtree::{KdTreeMetric, LazyKdTree};
 #[codesnip::entry("LazySegmentTree")]
 pub use self::lazy_segment_tree::LazySegmentTree;
 #[codesnip::entry("LazySegmentTreeMap")]
@@ -63,14 +73,26 @@ pub use self::lazy_segment_tree_map::LazySegmentTreeMap;
 pub use self::li_chao_tree::{LiChaoLine, LiChaoTree, OfflineLiChaoTree};
 #[codesnip::entry("LineSet")]
 pub use self::line_set::LineSet;
+#[codesnip::entry("OfflineDynamicConnectivity")]
+pub use self::offline_dynamic_connectivity::OfflineDynamicConnectivity;
 #[codesnip::entry("PairingHeap")]
-pub use self::pairing_heap::PairingHeap;
+pub use self::pairing_heap::{DecreaseKeyPairingHeap, PairingHeap, PairingHeapNodeId};
 #[codesnip::entry("PartiallyRetroactivePriorityQueue")]
 pub use self::partially_retroactive_priority_queue::PartiallyRetroactivePriorityQueue;
+#[codesnip::entry("PersistentArray")]
+pub use self::persistent_array::PersistentArray;
+#[codesnip::entry("PersistentLazySegmentTree")]
+pub use self::persistent_lazy_segment_tree::PersistentLazySegmentTree;
+#[codesnip::entry("PersistentLeftistHeap")]
+pub use self::persistent_leftist_heap::PersistentLeftistHeap;
 #[codesnip::entry("PersistentSegmentTree")]
-pub use self::persistent_segment_tree::PersistentSegmentTree;
+pub use self::persistent_segment_tree::{PersistentSegmentTree, PersistentSegmentTreeVersion};
+#[codesnip::entry("PersistentUnionFind")]
+pub use self::persistent_union_find::PersistentUnionFind;
+#[codesnip::entry("RadixHeap")]
+pub use self::radix_heap::{RadixHeap, RadixHeapKey};
 #[codesnip::entry("RangeArithmeticProgressionAdd")]
-pub use self::range_ap_add::RangeArithmeticProgressionAdd;
+pub use self::range_ap_add::{RangeArithmeticProgressionAdd, RangePolynomialAdd};
 #[codesnip::entry("RangeFoldWithUpperBound")]
 pub use self::range_fold_with_upper_bound::RangeFoldWithUpperBound;
 #[codesnip::entry("RangeFrequency")]
@@ -83,14 +105,20 @@ pub use self::range_minimum_query::RangeMinimumQuery;
 pub use self::segment_tree::SegmentTree;
 #[codesnip::entry("SegmentTreeMap")]
 pub use self::segment_tree_map::SegmentTreeMap;
+#[codesnip::entry("SkewHeap")]
+pub use self::skew_heap::SkewHeap;
 #[codesnip::entry("sliding_window_aggregation")]
 pub use self::sliding_window_aggregation::{DequeAggregation, QueueAggregation};
 #[codesnip::entry("slope_trick")]
 pub use self::slope_trick::SlopeTrick;
+#[codesnip::entry("SortedBucketList")]
+pub use self::sorted_bucket_list::SortedBucketList;
 #[codesnip::entry("SparseSet")]
 pub use self::sparse_set::SparseSet;
 #[codesnip::entry("SplayTree")]
 pub use self::splay_tree::SplayTree;
+#[codesnip::entry("SqrtTree")]
+pub use self::sqrt_tree::SqrtTree;
 #[codesnip::entry("StaticRangeProduct")]
 pub use self::static_range_product::StaticRangeProduct;
 #[codesnip::entry("SubmaskRangeQuery")]
@@ -123,7 +151,15 @@ mod binary_indexed_tree;
 mod binary_indexed_tree_2d;
 #[cfg_attr(
     nightly,
-    codesnip::entry("binary_search_tree", include("Allocator", "LazyMapMonoid"))
+    codesnip::entry("BinaryIndexedTreeKD", include("algebra", "discrete_steps"))
+)]
+mod binary_indexed_tree_kd;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "binary_search_tree",
+        include("Allocator", "LazyMapMonoid", "discrete_steps")
+    )
 )]
 pub mod binary_search_tree;
 #[cfg_attr(
@@ -152,8 +188,23 @@ mod counter;
 mod disjoint_sparse_table;
 #[cfg_attr(nightly, codesnip::entry("DoublyLinkedList"))]
 mod doubly_linked_list;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "DynamicSegmentTree",
+        include("Allocator", "LazyMapMonoid", "discrete_steps", "integer")
+    )
+)]
+mod dynamic_segment_tree;
+#[cfg_attr(nightly, codesnip::entry("FastSet"))]
+mod fast_set;
 #[cfg_attr(nightly, codesnip::entry("FibonacciHash"))]
 mod fibonacci_hash;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("HistoricSegmentTree", include("LazySegmentTree"))
+)]
+mod historic_segment_tree;
 #[cfg_attr(
     nightly,
     codesnip::entry("ImplicitSplayTree", include("_splay_operations"))
@@ -166,6 +217,11 @@ mod implicit_splay_tree;
 mod implicit_treap;
 #[cfg_attr(nightly, codesnip::entry("Static2DTree"))]
 mod kdtree;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("LazyKdTree", include("LazyMapMonoid", "zero_one"))
+)]
+mod lazy_kdtree;
 #[cfg_attr(
     nightly,
     codesnip::entry("LazySegmentTree", include("LazyMapMonoid", "discrete_steps"))
@@ -186,6 +242,11 @@ mod lazy_segment_tree_map;
 mod li_chao_tree;
 #[cfg_attr(nightly, codesnip::entry("LineSet", include("bounded")))]
 mod line_set;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("OfflineDynamicConnectivity", include("UnionFind"))
+)]
+mod offline_dynamic_connectivity;
 #[cfg_attr(
     nightly,
     codesnip::entry("PairingHeap", include("Comparator", "MonoidAct"))
@@ -199,6 +260,29 @@ mod pairing_heap;
     )
 )]
 pub mod partially_retroactive_priority_queue;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("PersistentArray", include("PersistentSegmentTree", "LastOperation"))
+)]
+mod persistent_array;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "PersistentLazySegmentTree",
+        include(
+            "Allocator",
+            "LazyMapMonoid",
+            "PersistentSegmentTree",
+            "discrete_steps"
+        )
+    )
+)]
+mod persistent_lazy_segment_tree;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("PersistentLeftistHeap", include("Comparator"))
+)]
+mod persistent_leftist_heap;
 #[cfg_attr(
     nightly,
     codesnip::entry(
@@ -207,7 +291,20 @@ pub mod partially_retroactive_priority_queue;
     )
 )]
 mod persistent_segment_tree;
-#[cfg_attr(nightly, codesnip::entry("RangeArithmeticProgressionAdd"))]
+#[cfg_attr(
+    nightly,
+    codesnip::entry("PersistentUnionFind", include("PersistentArray"))
+)]
+mod persistent_union_find;
+#[cfg_attr(nightly, codesnip::entry("RadixHeap"))]
+mod radix_heap;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "RangeArithmeticProgressionAdd",
+        include("BinaryIndexedTree", "AdditiveOperation", "zero_one")
+    )
+)]
 mod range_ap_add;
 #[cfg_attr(
     nightly,
@@ -239,13 +336,26 @@ mod segment_tree;
     )
 )]
 mod segment_tree_map;
+#[cfg_attr(nightly, codesnip::entry("SkewHeap", include("Comparator")))]
+mod skew_heap;
 #[cfg_attr(
     nightly,
     codesnip::entry("sliding_window_aggregation", include("algebra"))
 )]
 mod sliding_window_aggregation;
-#[cfg_attr(nightly, codesnip::entry("slope_trick"))]
+#[cfg_attr(
+    nightly,
+    codesnip::entry("slope_trick", include("integer", "zero_one"))
+)]
 mod slope_trick;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "SortedBucketList",
+        include("algebra", "TupleOperation", "discrete_steps")
+    )
+)]
+mod sorted_bucket_list;
 #[cfg_attr(nightly, codesnip::entry("SparseSet"))]
 mod sparse_set;
 #[cfg_attr(
@@ -255,6 +365,8 @@ mod sparse_set;
 pub mod splay_operations;
 #[cfg_attr(nightly, codesnip::entry("SplayTree", include("_splay_operations")))]
 mod splay_tree;
+#[cfg_attr(nightly, codesnip::entry("SqrtTree", include("algebra")))]
+mod sqrt_tree;
 #[cfg_attr(
     nightly,
     codesnip::entry("StaticRangeProduct", include("DisjointSparseTable"))
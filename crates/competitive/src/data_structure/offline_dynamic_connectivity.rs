@@ -0,0 +1,259 @@
+use super::{
+    Group, UnionFindBase,
+    union_find::{UfMergeSpec, Undoable, UnionStrategy},
+};
+use std::collections::HashMap;
+
+/// Offline dynamic connectivity by segment tree on time
+///
+/// Each edge is alive over an interval of queries and united in [`UnionFindBase`] with [`Undoable`].
+/// An edge has a potential `T` used by [`UnionFindBase::unite_with`].
+#[derive(Debug, Clone)]
+pub struct OfflineDynamicConnectivity<T> {
+    queries: usize,
+    /// normalized edge -> alive edges
+    alive: HashMap<(usize, usize), Vec<TimedEdge<T>>>,
+    removed: Vec<TimedEdge<T>>,
+}
+
+/// Edge (u, v) with the potential `x` alive in queries [start, end)
+#[derive(Debug, Clone)]
+struct TimedEdge<T> {
+    start: usize,
+    end: usize,
+    u: usize,
+    v: usize,
+    x: T,
+}
+
+impl<T> Default for OfflineDynamicConnectivity<T> {
+    fn default() -> Self {
+        Self {
+            queries: 0,
+            alive: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+impl<T> OfflineDynamicConnectivity<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an edge (u, v) with the potential `x`, multi edges are allowed.
+    pub fn add_edge_with(&mut self, u: usize, v: usize, x: T) {
+        self.alive
+            .entry((u.min(v), u.max(v)))
+            .or_default()
+            .push(TimedEdge {
+                start: self.queries,
+                end: usize::MAX,
+                u,
+                v,
+                x,
+            });
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize)
+    where
+        T: Default,
+    {
+        self.add_edge_with(u, v, T::default());
+    }
+
+    /// Remove one of the alive edges (u, v) or (v, u).
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        let edges = self.alive.get_mut(&key).expect("edge not found");
+        let mut edge = edges.pop().expect("edge not found");
+        if edges.is_empty() {
+            self.alive.remove(&key);
+        }
+        if edge.start < self.queries {
+            edge.end = self.queries;
+            self.removed.push(edge);
+        }
+    }
+
+    /// Register a query on the current graph and return its id.
+    pub fn query(&mut self) -> usize {
+        self.queries += 1;
+        self.queries - 1
+    }
+
+    /// Call `f(uf, id)` for each query in order, where `uf` is the state at the query.
+    ///
+    /// `uf` should be the initial state and is restored after solving.
+    pub fn solve<U, M, P, F>(&self, uf: &mut UnionFindBase<U, (), M, P, Undoable>, mut f: F)
+    where
+        T: Clone,
+        U: UnionStrategy,
+        M: UfMergeSpec<Data: Clone>,
+        P: Group<T = T>,
+        F: FnMut(&mut UnionFindBase<U, (), M, P, Undoable>, usize),
+    {
+        let q = self.queries;
+        if q == 0 {
+            return;
+        }
+        let size = q.next_power_of_two();
+        let mut nodes: Vec<Vec<usize>> = vec![vec![]; size * 2];
+        let edges: Vec<_> = self
+            .removed
+            .iter()
+            .chain(self.alive.values().flatten())
+            .filter(|edge| edge.start < q)
+            .collect();
+        for (i, edge) in edges.iter().enumerate() {
+            let mut l = edge.start + size;
+            let mut r = edge.end.min(q) + size;
+            while l < r {
+                if l & 1 == 1 {
+                    nodes[l].push(i);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    nodes[r].push(i);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+        // (node, [l, r), leave)
+        let mut stack = vec![(1, 0, size, false)];
+        let mut merged = vec![0usize; size * 2];
+        while let Some((k, l, r, leave)) = stack.pop() {
+            if leave {
+                for _ in 0..merged[k] {
+                    uf.undo();
+                }
+                continue;
+            }
+            for &i in &nodes[k] {
+                let TimedEdge { u, v, x, .. } = edges[i];
+                if uf.unite_with(*u, *v, x.clone()) {
+                    merged[k] += 1;
+                }
+            }
+            stack.push((k, l, r, true));
+            if r - l == 1 {
+                f(uf, l);
+            } else {
+                let m = (l + r) / 2;
+                if m < q {
+                    stack.push((k * 2 + 1, m, r, false));
+                }
+                stack.push((k * 2, l, m, false));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::AdditiveOperation,
+        data_structure::{UndoableUnionFind, UnionFind, union_find::UnionBySize},
+        tools::Xorshift,
+    };
+
+    #[test]
+    fn test_offline_dynamic_connectivity() {
+        const N: usize = 10;
+        const Q: usize = 500;
+        let mut rng = Xorshift::default();
+        for _ in 0..20 {
+            let mut dc = OfflineDynamicConnectivity::<()>::new();
+            let mut edges: Vec<(usize, usize)> = vec![];
+            let mut expected = vec![];
+            let mut queries = vec![];
+            for _ in 0..Q {
+                match rng.random(0..3) {
+                    0 => {
+                        let (u, v) = (rng.random(0..N), rng.random(0..N));
+                        dc.add_edge(u, v);
+                        edges.push((u, v));
+                    }
+                    1 if !edges.is_empty() => {
+                        let i = rng.random(0..edges.len());
+                        let (u, v) = edges.swap_remove(i);
+                        dc.remove_edge(v, u);
+                    }
+                    _ => {
+                        let mut uf = UnionFind::new(N);
+                        for &(u, v) in &edges {
+                            uf.unite(u, v);
+                        }
+                        let (x, y) = (rng.random(0..N), rng.random(0..N));
+                        assert_eq!(dc.query(), queries.len());
+                        queries.push((x, y));
+                        expected.push((uf.same(x, y), uf.size(x)));
+                    }
+                }
+            }
+            let mut uf = UndoableUnionFind::new(N);
+            let mut result = vec![];
+            dc.solve(&mut uf, |uf, id| {
+                assert_eq!(id, result.len());
+                let (x, y) = queries[id];
+                result.push((uf.same(x, y), uf.size(x)));
+            });
+            assert_eq!(result, expected);
+            assert!((0..N).all(|x| uf.size(x) == 1));
+        }
+    }
+
+    #[test]
+    fn test_offline_dynamic_connectivity_with_merger_and_potential() {
+        const N: usize = 8;
+        const Q: usize = 300;
+        let mut rng = Xorshift::default();
+        for _ in 0..20 {
+            let p: Vec<i64> = (0..N).map(|_| rng.random(-100..100)).collect();
+            let w: Vec<i64> = (0..N).map(|_| rng.random(0..100)).collect();
+            let mut dc = OfflineDynamicConnectivity::<i64>::new();
+            let mut edges: Vec<(usize, usize)> = vec![];
+            let mut expected = vec![];
+            let mut queries = vec![];
+            for _ in 0..Q {
+                match rng.random(0..3) {
+                    0 => {
+                        let (u, v) = (rng.random(0..N), rng.random(0..N));
+                        dc.add_edge_with(u, v, p[v] - p[u]);
+                        edges.push((u, v));
+                    }
+                    1 if !edges.is_empty() => {
+                        let i = rng.random(0..edges.len());
+                        let (u, v) = edges.swap_remove(i);
+                        dc.remove_edge(u, v);
+                    }
+                    _ => {
+                        let mut uf = UnionFind::new(N);
+                        for &(u, v) in &edges {
+                            uf.unite(u, v);
+                        }
+                        let (x, y) = (rng.random(0..N), rng.random(0..N));
+                        dc.query();
+                        queries.push((x, y));
+                        let sum: i64 = (0..N).filter(|&z| uf.same(x, z)).map(|z| w[z]).sum();
+                        expected.push((uf.same(x, y).then_some(p[y] - p[x]), sum));
+                    }
+                }
+            }
+            let mut uf = UnionFindBase::<UnionBySize, (), _, AdditiveOperation<i64>, Undoable>::new_with_merger(
+                N,
+                |i| w[i],
+                |x, y| *x += *y,
+            );
+            let mut result = vec![];
+            dc.solve(&mut uf, |uf, id| {
+                let (x, y) = queries[id];
+                result.push((uf.difference(x, y), *uf.merge_data(x)));
+            });
+            assert_eq!(result, expected);
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use super::{Comparator, EmptyAct, MonoidAct, Unital, comparator::Less};
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
     iter::FusedIterator,
     mem::{replace, swap},
     ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    sync::atomic::{self, AtomicU64},
 };
 
-#[derive(Clone)]
 struct Node<T, A>
 where
     A: MonoidAct<Key = T, Act: PartialEq>,
@@ -15,6 +18,8 @@ where
     value: T,
     first_child: Option<Box<Node<T, A>>>,
     next_sibling: Option<Box<Node<T, A>>>,
+    /// parent if first child, otherwise previous sibling
+    prev: Option<NonNull<Node<T, A>>>,
     lazy: A::Act,
 }
 
@@ -27,6 +32,7 @@ where
             value,
             first_child: None,
             next_sibling: None,
+            prev: None,
             lazy: A::unit(),
         }
     }
@@ -49,6 +55,22 @@ where
     }
 }
 
+impl<T, A> Clone for Node<T, A>
+where
+    T: Clone,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            first_child: self.first_child.clone(),
+            next_sibling: self.next_sibling.clone(),
+            prev: self.prev,
+            lazy: self.lazy.clone(),
+        }
+    }
+}
+
 impl<T, A> Debug for Node<T, A>
 where
     T: Debug,
@@ -64,7 +86,6 @@ where
     }
 }
 
-#[derive(Clone)]
 pub struct PairingHeap<T, C = Less, A = EmptyAct<T>>
 where
     A: MonoidAct<Key = T, Act: PartialEq>,
@@ -74,6 +95,90 @@ where
     cmp: C,
 }
 
+// SAFETY: every node is owned through a `Box` reachable from `root`, and `prev` only points to
+// a node owned by the same heap, so moving the heap moves all the nodes referred to.
+unsafe impl<T, C, A> Send for PairingHeap<T, C, A>
+where
+    T: Send,
+    C: Send,
+    A: MonoidAct<Key = T, Act: PartialEq + Send>,
+{
+}
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Stable handle of an element pushed by [`DecreaseKeyPairingHeap::push_with_id`]
+///
+/// It remains valid across [`DecreaseKeyPairingHeap::append`] and [`DecreaseKeyPairingHeap::meld`] until the element is popped.
+pub struct PairingHeapNodeId<T, A = EmptyAct<T>>
+where
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    node: NonNull<Node<T, A>>,
+    generation: u64,
+}
+
+impl<T, A> Clone for PairingHeapNodeId<T, A>
+where
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, A> Copy for PairingHeapNodeId<T, A> where A: MonoidAct<Key = T, Act: PartialEq> {}
+
+impl<T, A> PartialEq for PairingHeapNodeId<T, A>
+where
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node && self.generation == other.generation
+    }
+}
+
+impl<T, A> Eq for PairingHeapNodeId<T, A> where A: MonoidAct<Key = T, Act: PartialEq> {}
+
+impl<T, A> Hash for PairingHeapNodeId<T, A>
+where
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T, C, A> Clone for PairingHeap<T, C, A>
+where
+    T: Clone,
+    C: Clone,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn clone(&self) -> Self {
+        let mut root = self.root.clone();
+        let mut stack: Vec<&mut Node<T, A>> = root.as_deref_mut().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let ptr = NonNull::from(&mut *node);
+            let Node {
+                first_child,
+                next_sibling,
+                ..
+            } = node;
+            for child in [first_child, next_sibling].into_iter().flatten() {
+                child.prev = Some(ptr);
+                stack.push(child);
+            }
+        }
+        Self {
+            root,
+            len: self.len,
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
 impl<T, C, A> PairingHeap<T, C, A>
 where
     C: Comparator<T>,
@@ -100,7 +205,10 @@ where
     }
 
     pub fn push(&mut self, value: T) {
-        let node = Box::new(Node::new(value));
+        self.push_node(Box::new(Node::new(value)));
+    }
+
+    fn push_node(&mut self, node: Box<Node<T, A>>) {
         let root = self.root.take();
         self.root = self.merge_option(root, Some(node));
         self.len += 1;
@@ -145,6 +253,58 @@ where
         self.len = 0;
     }
 
+    /// Propagate lazy acts on the path from the root to `node` inclusive.
+    fn propagate_path(&mut self, node: NonNull<Node<T, A>>) {
+        let mut path = vec![node];
+        while let Some(prev) = unsafe { path.last().unwrap().as_ref() }.prev {
+            path.push(prev);
+        }
+        for mut node in path.into_iter().rev() {
+            unsafe { node.as_mut() }.propagate();
+        }
+    }
+
+    /// `node` must be in the heap.
+    fn get_node(&mut self, node: NonNull<Node<T, A>>) -> &T {
+        self.propagate_path(node);
+        unsafe { &node.as_ref().value }
+    }
+
+    /// `node` must be in the heap.
+    fn decrease_key_node(&mut self, node: NonNull<Node<T, A>>, value: T) {
+        self.propagate_path(node);
+        let ptr = node.as_ptr();
+        let node = unsafe { &mut *ptr };
+        assert_ne!(
+            self.cmp.compare(&value, &node.value),
+            Ordering::Greater,
+            "new value must not be greater than the current one"
+        );
+        node.value = value;
+        let Some(mut prev) = node.prev else {
+            return;
+        };
+        // cut the subtree of `node` and meld it with the root
+        let mut next = node.next_sibling.take();
+        if let Some(next) = next.as_mut() {
+            next.prev = Some(prev);
+        }
+        let prev = unsafe { prev.as_mut() };
+        let slot = if prev
+            .first_child
+            .as_deref()
+            .is_some_and(|child| ptr::eq(child, ptr))
+        {
+            &mut prev.first_child
+        } else {
+            &mut prev.next_sibling
+        };
+        let mut node = replace(slot, next).unwrap();
+        node.prev = None;
+        let root = self.root.take();
+        self.root = self.merge_option(root, Some(node));
+    }
+
     pub fn apply_all(&mut self, act: A::Act) {
         if let Some(root) = self.root.as_mut() {
             root.apply(&act);
@@ -166,14 +326,23 @@ where
     ) -> Option<Box<Node<T, A>>> {
         match (a, b) {
             (None, None) => None,
-            (Some(node), None) | (None, Some(node)) => Some(node),
+            (Some(mut node), None) | (None, Some(mut node)) => {
+                node.prev = None;
+                Some(node)
+            }
             (Some(mut a), Some(mut b)) => {
                 a.propagate();
                 b.propagate();
                 if self.cmp.compare(&a.value, &b.value) == Ordering::Greater {
                     swap(&mut a, &mut b);
                 }
+                let b_ptr = NonNull::from(&mut *b);
                 b.next_sibling = a.first_child.take();
+                if let Some(next) = b.next_sibling.as_mut() {
+                    next.prev = Some(b_ptr);
+                }
+                a.prev = None;
+                b.prev = Some(NonNull::from(&mut *a));
                 a.first_child = Some(b);
                 Some(a)
             }
@@ -262,6 +431,162 @@ where
     }
 }
 
+/// [`PairingHeap`] with stable node ids for [`DecreaseKeyPairingHeap::decrease_key`]
+pub struct DecreaseKeyPairingHeap<T, C = Less, A = EmptyAct<T>>
+where
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    heap: PairingHeap<T, C, A>,
+    node_ids: HashMap<NonNull<Node<T, A>>, u64>,
+}
+
+// SAFETY: the keys of `node_ids` only point to nodes owned by `heap`.
+unsafe impl<T, C, A> Send for DecreaseKeyPairingHeap<T, C, A>
+where
+    T: Send,
+    C: Send,
+    A: MonoidAct<Key = T, Act: PartialEq + Send>,
+{
+}
+
+impl<T, C, A> Clone for DecreaseKeyPairingHeap<T, C, A>
+where
+    T: Clone,
+    C: Clone,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    /// Node ids of `self` are not valid for the cloned heap.
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            node_ids: HashMap::new(),
+        }
+    }
+}
+
+impl<T, C, A> DecreaseKeyPairingHeap<T, C, A>
+where
+    C: Comparator<T>,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            heap: PairingHeap::with_comparator(cmp),
+            node_ids: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Push `value` and returns its stable id.
+    pub fn push_with_id(&mut self, value: T) -> PairingHeapNodeId<T, A> {
+        let mut node = Box::new(Node::new(value));
+        let node_id = PairingHeapNodeId {
+            node: NonNull::from(&mut *node),
+            generation: GENERATION
+                .fetch_update(atomic::Ordering::Relaxed, atomic::Ordering::Relaxed, |x| {
+                    x.checked_add(1)
+                })
+                .expect("Generation counter overflow"),
+        };
+        self.node_ids.insert(node_id.node, node_id.generation);
+        self.heap.push_node(node);
+        node_id
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = NonNull::from(self.heap.root.as_deref()?);
+        self.node_ids.remove(&root);
+        self.heap.pop()
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        self.heap.append(&mut other.heap);
+        self.node_ids.extend(other.node_ids.drain());
+    }
+
+    /// Move all elements of `other` into `self` keeping node ids of both valid.
+    pub fn meld(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.node_ids.clear();
+    }
+
+    /// Whether the element of `node_id` is in the heap
+    pub fn contains(&self, node_id: &PairingHeapNodeId<T, A>) -> bool {
+        self.node_ids
+            .get(&node_id.node)
+            .is_some_and(|&g| g == node_id.generation)
+    }
+
+    pub fn get(&mut self, node_id: PairingHeapNodeId<T, A>) -> Option<&T> {
+        if !self.contains(&node_id) {
+            return None;
+        }
+        Some(self.heap.get_node(node_id.node))
+    }
+
+    /// Replace the element of `node_id` with `value` not greater than the current one.
+    pub fn decrease_key(&mut self, node_id: PairingHeapNodeId<T, A>, value: T) {
+        assert!(self.contains(&node_id), "invalid node id");
+        self.heap.decrease_key_node(node_id.node, value);
+    }
+
+    pub fn apply_all(&mut self, act: A::Act) {
+        self.heap.apply_all(act);
+    }
+
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+impl<T, C, A> Default for DecreaseKeyPairingHeap<T, C, A>
+where
+    C: Comparator<T> + Default,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, A> DecreaseKeyPairingHeap<T, Less, A>
+where
+    T: Ord,
+    A: MonoidAct<Key = T, Act: PartialEq>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, C, A> Debug for DecreaseKeyPairingHeap<T, C, A>
+where
+    T: Debug,
+    C: Debug + Comparator<T>,
+    A: MonoidAct<Key = T, Act: PartialEq + Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecreaseKeyPairingHeap")
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
 pub struct PeekMut<'a, T, C = Less, A = EmptyAct<T>>
 where
     C: Comparator<T>,
@@ -532,4 +857,71 @@ mod tests {
             assert!(reference_other.is_empty());
         }
     }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<PairingHeap<i64, Less, FlattenAct<AdditiveOperation<i64>>>>();
+        assert_send::<DecreaseKeyPairingHeap<i64>>();
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            type Heap = DecreaseKeyPairingHeap<i64, Less, FlattenAct<AdditiveOperation<i64>>>;
+            let mut heap = Heap::default();
+            // (id, value - offset)
+            let mut reference = vec![];
+            let mut offset = 0i64;
+            for _ in 0..1_000 {
+                match rng.rand(6) {
+                    0 => {
+                        let value: i64 = rng.random(-1_000_000..=1_000_000);
+                        reference.push((heap.push_with_id(value), value - offset));
+                    }
+                    1 => {
+                        let value = heap.pop();
+                        let i = reference.iter().position(|(id, _)| !heap.contains(id));
+                        assert_eq!(value, i.map(|i| reference.swap_remove(i).1 + offset));
+                        assert!(reference.iter().all(|(id, _)| heap.contains(id)));
+                    }
+                    2 if !reference.is_empty() => {
+                        let i = rng.random(0..reference.len());
+                        let value = reference[i].1 + offset - rng.random(0i64..1_000);
+                        heap.decrease_key(reference[i].0, value);
+                        reference[i].1 = value - offset;
+                    }
+                    3 => {
+                        let mut other = Heap::default();
+                        for _ in 0..rng.random(0..10) {
+                            let value: i64 = rng.random(-1_000_000..=1_000_000);
+                            reference.push((other.push_with_id(value), value - offset));
+                        }
+                        heap.meld(other);
+                    }
+                    4 => {
+                        let add: i64 = rng.random(-1_000..=1_000);
+                        heap.apply_all(add);
+                        offset += add;
+                    }
+                    _ if !reference.is_empty() => {
+                        let (id, value) = reference[rng.random(0..reference.len())];
+                        assert_eq!(heap.get(id), Some(&(value + offset)));
+                    }
+                    _ => {}
+                }
+                assert_eq!(heap.len(), reference.len());
+                assert_eq!(
+                    heap.peek().copied(),
+                    reference.iter().map(|&(_, value)| value + offset).min()
+                );
+            }
+            let cloned = heap.clone();
+            let mut expected: Vec<_> = reference.iter().map(|&(_, value)| value + offset).collect();
+            expected.sort_unstable();
+            assert_eq!(cloned.into_sorted_vec(), expected);
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+    }
 }
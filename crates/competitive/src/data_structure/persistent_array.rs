@@ -0,0 +1,92 @@
+use super::{PersistentSegmentTree, PersistentSegmentTreeVersion};
+use crate::algebra::LastOperation;
+use std::fmt::{self, Debug, Formatter};
+
+/// Fully persistent array on [`PersistentSegmentTree`]
+pub struct PersistentArray<T>
+where
+    T: Clone,
+{
+    seg: PersistentSegmentTree<LastOperation<T>>,
+    base: PersistentSegmentTreeVersion,
+}
+
+impl<T> Debug for PersistentArray<T>
+where
+    T: Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentArray")
+            .field("seg", &self.seg)
+            .finish()
+    }
+}
+
+impl<T> PersistentArray<T>
+where
+    T: Clone,
+{
+    #[must_use]
+    pub fn from_vec(v: Vec<T>) -> Self {
+        let mut seg = PersistentSegmentTree::new(v.len());
+        let base = seg.from_vec(v.into_iter().map(Some).collect());
+        Self { seg, base }
+    }
+
+    /// The version of the initial array
+    pub fn base(&self) -> PersistentSegmentTreeVersion {
+        self.base
+    }
+
+    pub fn len(&self) -> usize {
+        self.seg.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seg.is_empty()
+    }
+
+    #[must_use]
+    pub fn get(&self, version: PersistentSegmentTreeVersion, index: usize) -> T {
+        self.seg.get(version, index).expect("invalid version")
+    }
+
+    pub fn set(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        index: usize,
+        value: T,
+    ) -> PersistentSegmentTreeVersion {
+        self.seg.set(version, index, Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_persistent_array() {
+        const N: usize = 20;
+        const Q: usize = 5_000;
+        let mut rng = Xorshift::default();
+        let initial: Vec<u64> = (0..N).map(|_| rng.random(0..100)).collect();
+        let mut array = PersistentArray::from_vec(initial.clone());
+        let mut versions = vec![array.base()];
+        let mut states = vec![initial];
+        for _ in 0..Q {
+            let i = rng.random(0..versions.len());
+            let k = rng.random(0..N);
+            let x = rng.random(0..100);
+            let mut state = states[i].clone();
+            state[k] = x;
+            versions.push(array.set(versions[i], k, x));
+            states.push(state);
+
+            let i = rng.random(0..versions.len());
+            let k = rng.random(0..N);
+            assert_eq!(array.get(versions[i], k), states[i][k]);
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use super::{Allocator, LazyMapMonoid, MemoryPool, PersistentSegmentTreeVersion, RangeBoundsExt};
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::{Range, RangeBounds},
+    ptr::NonNull,
+};
+
+type NodePtr<M> = Option<NonNull<Node<M>>>;
+
+struct Node<M>
+where
+    M: LazyMapMonoid,
+{
+    children: [NodePtr<M>; 2],
+    agg: M::Agg,
+    lazy: M::Act,
+}
+
+impl<M> Node<M>
+where
+    M: LazyMapMonoid,
+{
+    fn new(children: [NodePtr<M>; 2], agg: M::Agg, lazy: M::Act) -> Self {
+        Self {
+            children,
+            agg,
+            lazy,
+        }
+    }
+}
+
+/// Persistent segment tree with lazy propagation, nodes on the paths are copied on write.
+pub struct PersistentLazySegmentTree<M>
+where
+    M: LazyMapMonoid,
+{
+    len: usize,
+    version_roots: Vec<NodePtr<M>>,
+    allocator: MemoryPool<Node<M>>,
+}
+
+impl<M> Debug for PersistentLazySegmentTree<M>
+where
+    M: LazyMapMonoid,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentLazySegmentTree")
+            .field("len", &self.len)
+            .field("versions", &self.version_roots.len())
+            .finish()
+    }
+}
+
+impl<M> PersistentLazySegmentTree<M>
+where
+    M: LazyMapMonoid,
+{
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            version_roots: vec![None],
+            allocator: MemoryPool::new(),
+        }
+    }
+
+    pub fn base(&self) -> PersistentSegmentTreeVersion {
+        PersistentSegmentTreeVersion::base()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn version_root(&self, version: PersistentSegmentTreeVersion) -> NodePtr<M> {
+        *self
+            .version_roots
+            .get(version.index())
+            .expect("invalid version")
+    }
+
+    fn push_version_root(&mut self, root: NodePtr<M>) -> PersistentSegmentTreeVersion {
+        let version_id = self.version_roots.len();
+        self.version_roots.push(root);
+        PersistentSegmentTreeVersion::new(version_id)
+    }
+
+    fn allocate_node(
+        &mut self,
+        children: [NodePtr<M>; 2],
+        agg: M::Agg,
+        lazy: M::Act,
+    ) -> NodePtr<M> {
+        Some(self.allocator.allocate(Node::new(children, agg, lazy)))
+    }
+
+    fn build_dfs(&mut self, start: usize, end: usize, values: &[M::Agg]) -> NodePtr<M> {
+        if end - start == 1 {
+            return self.allocate_node([None, None], values[start].clone(), M::act_unit());
+        }
+        let mid = (start + end) / 2;
+        let left = self.build_dfs(start, mid, values);
+        let right = self.build_dfs(mid, end, values);
+        self.merge_nodes(left, right)
+    }
+
+    fn merge_nodes(&mut self, left: NodePtr<M>, right: NodePtr<M>) -> NodePtr<M> {
+        let agg = M::agg_operate(&Self::subtree_agg(left), &Self::subtree_agg(right));
+        self.allocate_node([left, right], agg, M::act_unit())
+    }
+
+    fn subtree_agg(node: NodePtr<M>) -> M::Agg {
+        node.map(|node| unsafe { node.as_ref().agg.clone() })
+            .unwrap_or_else(M::agg_unit)
+    }
+
+    /// Return a copy of `node` with `act` applied.
+    fn apply(&mut self, node: NodePtr<M>, start: usize, end: usize, act: &M::Act) -> NodePtr<M> {
+        if M::is_act_unit(act) {
+            return node;
+        }
+        let (children, agg, lazy) = match node {
+            Some(node) => {
+                let node = unsafe { node.as_ref() };
+                (node.children, node.agg.clone(), node.lazy.clone())
+            }
+            None => ([None, None], M::agg_unit(), M::act_unit()),
+        };
+        let is_leaf = end - start == 1;
+        let lazy = if is_leaf {
+            lazy
+        } else {
+            M::act_operate(&lazy, act)
+        };
+        if let Some(agg) = M::act_agg(&agg, act) {
+            self.allocate_node(children, agg, lazy)
+        } else {
+            assert!(!is_leaf, "act failed on leaf");
+            let mid = (start + end) / 2;
+            let left = self.apply(children[0], start, mid, &lazy);
+            let right = self.apply(children[1], mid, end, &lazy);
+            self.merge_nodes(left, right)
+        }
+    }
+
+    /// Return the children of `node` with its lazy act applied.
+    fn propagate(&mut self, node: NodePtr<M>, start: usize, end: usize) -> [NodePtr<M>; 2] {
+        let Some(node) = node else {
+            return [None, None];
+        };
+        let node = unsafe { node.as_ref() };
+        let mid = (start + end) / 2;
+        let lazy = node.lazy.clone();
+        [
+            self.apply(node.children[0], start, mid, &lazy),
+            self.apply(node.children[1], mid, end, &lazy),
+        ]
+    }
+
+    fn update_dfs(
+        &mut self,
+        node: NodePtr<M>,
+        start: usize,
+        end: usize,
+        range: &Range<usize>,
+        act: &M::Act,
+    ) -> NodePtr<M> {
+        if range.end <= start || end <= range.start {
+            return node;
+        }
+        if range.start <= start && end <= range.end {
+            return self.apply(node, start, end, act);
+        }
+        let mid = (start + end) / 2;
+        let [left, right] = self.propagate(node, start, end);
+        let left = self.update_dfs(left, start, mid, range, act);
+        let right = self.update_dfs(right, mid, end, range, act);
+        self.merge_nodes(left, right)
+    }
+
+    fn set_dfs(
+        &mut self,
+        node: NodePtr<M>,
+        start: usize,
+        end: usize,
+        index: usize,
+        agg: &M::Agg,
+    ) -> NodePtr<M> {
+        if end - start == 1 {
+            return self.allocate_node([None, None], agg.clone(), M::act_unit());
+        }
+        let mid = (start + end) / 2;
+        let [mut left, mut right] = self.propagate(node, start, end);
+        if index < mid {
+            left = self.set_dfs(left, start, mid, index, agg);
+        } else {
+            right = self.set_dfs(right, mid, end, index, agg);
+        }
+        self.merge_nodes(left, right)
+    }
+
+    /// `acc`: composed acts of the ancestors
+    fn fold_dfs(
+        node: NodePtr<M>,
+        start: usize,
+        end: usize,
+        range: &Range<usize>,
+        acc: &M::Act,
+    ) -> M::Agg {
+        if range.end <= start || end <= range.start {
+            return M::agg_unit();
+        }
+        let Some(node) = node else {
+            return M::act_agg(&M::agg_unit(), acc).unwrap_or_else(M::agg_unit);
+        };
+        let node = unsafe { node.as_ref() };
+        if range.start <= start && end <= range.end {
+            if let Some(agg) = M::act_agg(&node.agg, acc) {
+                return agg;
+            }
+            assert!(end - start > 1, "act failed on leaf");
+        }
+        let mid = (start + end) / 2;
+        let acc = M::act_operate(&node.lazy, acc);
+        let left = Self::fold_dfs(node.children[0], start, mid, range, &acc);
+        let right = Self::fold_dfs(node.children[1], mid, end, range, &acc);
+        M::agg_operate(&left, &right)
+    }
+
+    pub fn from_vec(&mut self, v: Vec<M::Agg>) -> PersistentSegmentTreeVersion {
+        assert_eq!(v.len(), self.len);
+        let root = if self.len == 0 {
+            None
+        } else {
+            self.build_dfs(0, self.len, &v)
+        };
+        self.push_version_root(root)
+    }
+
+    pub fn from_keys(
+        &mut self,
+        keys: impl ExactSizeIterator<Item = M::Key>,
+    ) -> PersistentSegmentTreeVersion {
+        self.from_vec(keys.map(|key| M::single_agg(&key)).collect())
+    }
+
+    pub fn update<R>(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        range: R,
+        act: M::Act,
+    ) -> PersistentSegmentTreeVersion
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = range.to_range_bounded(0, self.len).expect("invalid range");
+        let root = self.version_root(version);
+        let root = if range.is_empty() {
+            root
+        } else {
+            self.update_dfs(root, 0, self.len, &range, &act)
+        };
+        self.push_version_root(root)
+    }
+
+    pub fn set(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        index: usize,
+        agg: M::Agg,
+    ) -> PersistentSegmentTreeVersion {
+        assert!(index < self.len);
+        let root = self.set_dfs(self.version_root(version), 0, self.len, index, &agg);
+        self.push_version_root(root)
+    }
+
+    #[must_use]
+    pub fn fold<R>(&self, version: PersistentSegmentTreeVersion, range: R) -> M::Agg
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = range.to_range_bounded(0, self.len).expect("invalid range");
+        if range.is_empty() {
+            M::agg_unit()
+        } else {
+            Self::fold_dfs(
+                self.version_root(version),
+                0,
+                self.len,
+                &range,
+                &M::act_unit(),
+            )
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, version: PersistentSegmentTreeVersion, index: usize) -> M::Agg {
+        assert!(index < self.len);
+        self.fold(version, index..index + 1)
+    }
+
+    #[must_use]
+    pub fn fold_all(&self, version: PersistentSegmentTreeVersion) -> M::Agg {
+        Self::subtree_agg(self.version_root(version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::{RangeChminChmaxAdd, RangeSumRangeChminChmaxAdd, RangeSumRangeLinear},
+        num::{Saturating, montgomery::MInt998244353},
+        tools::{WithEmptySegment as Wes, Xorshift},
+    };
+
+    const N: usize = 20;
+    const Q: usize = 5_000;
+
+    #[test]
+    fn test_persistent_lazy_segment_tree_linear() {
+        type M = MInt998244353;
+        let mut rng = Xorshift::default();
+        let mut segtree = PersistentLazySegmentTree::<RangeSumRangeLinear<M>>::new(N);
+        let initial: Vec<M> = (0..N).map(|_| M::from(rng.random(0..1000u32))).collect();
+        let mut versions = vec![segtree.from_keys(initial.iter().copied())];
+        let mut states = vec![initial];
+        for _ in 0..Q {
+            let i = rng.random(0..versions.len());
+            let mut state = states[i].clone();
+            let (l, r) = rng.random(Wes(N));
+            let version = if rng.gen_bool(0.8) {
+                let a = M::from(rng.random(0..1000u32));
+                let b = M::from(rng.random(0..1000u32));
+                state[l..r].iter_mut().for_each(|x| *x = a * *x + b);
+                segtree.update(versions[i], l..r, (a, b))
+            } else {
+                let k = rng.random(0..N);
+                let x = M::from(rng.random(0..1000u32));
+                state[k] = x;
+                segtree.set(versions[i], k, (x, M::new(1)))
+            };
+            versions.push(version);
+            states.push(state);
+
+            let i = rng.random(0..versions.len());
+            let (l, r) = rng.random(Wes(N));
+            let expected: M = states[i][l..r].iter().sum();
+            assert_eq!(segtree.fold(versions[i], l..r).0, expected);
+            assert_eq!(segtree.fold_all(versions[i]).0, states[i].iter().sum::<M>());
+            let k = rng.random(0..N);
+            assert_eq!(segtree.get(versions[i], k).0, states[i][k]);
+        }
+    }
+
+    #[test]
+    fn test_persistent_lazy_segment_tree_beats() {
+        let mut rng = Xorshift::default();
+        let mut segtree = PersistentLazySegmentTree::<RangeSumRangeChminChmaxAdd<_>>::new(N);
+        let initial: Vec<_> = (0..N)
+            .map(|_| Saturating(rng.random(-100i64..=100)))
+            .collect();
+        let mut versions = vec![segtree.from_keys(initial.iter().copied())];
+        let mut states = vec![initial];
+        for _ in 0..Q {
+            let i = rng.random(0..versions.len());
+            let mut state = states[i].clone();
+            let (l, r) = rng.random(Wes(N));
+            let x = Saturating(rng.random(-100i64..=100));
+            let act = match rng.random(0..3) {
+                0 => {
+                    state[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                    RangeChminChmaxAdd::chmin(x)
+                }
+                1 => {
+                    state[l..r].iter_mut().for_each(|a| *a = (*a).max(x));
+                    RangeChminChmaxAdd::chmax(x)
+                }
+                _ => {
+                    state[l..r].iter_mut().for_each(|a| *a += x);
+                    RangeChminChmaxAdd::add(x)
+                }
+            };
+            versions.push(segtree.update(versions[i], l..r, act));
+            states.push(state);
+
+            let i = rng.random(0..versions.len());
+            let (l, r) = rng.random(Wes(N));
+            assert_eq!(
+                segtree.fold(versions[i], l..r).sum,
+                states[i][l..r].iter().copied().sum::<Saturating<i64>>()
+            );
+        }
+    }
+}
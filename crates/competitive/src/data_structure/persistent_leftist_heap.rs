@@ -0,0 +1,275 @@
+use super::{Comparator, comparator::Less};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    rc::Rc,
+};
+
+struct Node<T> {
+    value: T,
+    /// length of the right spine
+    rank: usize,
+    left: Option<Rc<Node<T>>>,
+    right: Option<Rc<Node<T>>>,
+}
+
+impl<T> Debug for Node<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("value", &self.value)
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        // avoid deep recursion on long left paths
+        let mut stack: Vec<_> = self
+            .left
+            .take()
+            .into_iter()
+            .chain(self.right.take())
+            .collect();
+        while let Some(node) = stack.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+fn rank<T>(node: &Option<Rc<Node<T>>>) -> usize {
+    node.as_ref().map_or(0, |node| node.rank)
+}
+
+/// Persistent meldable heap by leftist heap
+///
+/// Every operation returns a new heap and shares nodes with the old one, e.g. for k-shortest walks.
+/// `push`, `pop` and `meld` take $O(\log n)$ time and space.
+pub struct PersistentLeftistHeap<T, C = Less> {
+    root: Option<Rc<Node<T>>>,
+    len: usize,
+    cmp: C,
+}
+
+impl<T, C> Clone for PersistentLeftistHeap<T, C>
+where
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<T, C> PersistentLeftistHeap<T, C>
+where
+    T: Clone,
+    C: Comparator<T> + Clone,
+{
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    pub fn push(&self, value: T) -> Self {
+        let node = Rc::new(Node {
+            value,
+            rank: 1,
+            left: None,
+            right: None,
+        });
+        Self {
+            root: self.merge(&self.root, &Some(node)),
+            len: self.len + 1,
+            cmp: self.cmp.clone(),
+        }
+    }
+
+    /// Returns the top element and the heap without it.
+    pub fn pop(&self) -> Option<(T, Self)> {
+        self.root.as_ref().map(|root| {
+            let heap = Self {
+                root: self.merge(&root.left, &root.right),
+                len: self.len - 1,
+                cmp: self.cmp.clone(),
+            };
+            (root.value.clone(), heap)
+        })
+    }
+
+    pub fn meld(&self, other: &Self) -> Self {
+        Self {
+            root: self.merge(&self.root, &other.root),
+            len: self.len + other.len,
+            cmp: self.cmp.clone(),
+        }
+    }
+
+    fn merge(&self, a: &Option<Rc<Node<T>>>, b: &Option<Rc<Node<T>>>) -> Option<Rc<Node<T>>> {
+        // copy the right spines top-down, then fix ranks bottom-up
+        let (mut a, mut b) = (a.clone(), b.clone());
+        let mut cmp = self.cmp.clone();
+        let mut spine = vec![];
+        let mut rest = loop {
+            match (a, b) {
+                (None, node) | (node, None) => break node,
+                (Some(x), Some(y)) => {
+                    let (x, y) = if cmp.compare(&x.value, &y.value) == Ordering::Greater {
+                        (y, x)
+                    } else {
+                        (x, y)
+                    };
+                    a = x.right.clone();
+                    b = Some(y);
+                    spine.push((x.value.clone(), x.left.clone()));
+                }
+            }
+        };
+        while let Some((value, left)) = spine.pop() {
+            let (left, right) = if rank(&left) >= rank(&rest) {
+                (left, rest)
+            } else {
+                (rest, left)
+            };
+            rest = Some(Rc::new(Node {
+                value,
+                rank: rank(&right) + 1,
+                left,
+                right,
+            }));
+        }
+        rest
+    }
+
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len);
+        let mut heap = self;
+        while let Some((value, rest)) = heap.pop() {
+            result.push(value);
+            heap = rest;
+        }
+        result
+    }
+}
+
+impl<T, C> Default for PersistentLeftistHeap<T, C>
+where
+    T: Clone,
+    C: Comparator<T> + Clone + Default,
+{
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T> PersistentLeftistHeap<T>
+where
+    T: Clone + Ord,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, C> Debug for PersistentLeftistHeap<T, C>
+where
+    T: Debug,
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentLeftistHeap")
+            .field("len", &self.len)
+            .field("root", &self.root)
+            .field("cmp", &self.cmp)
+            .finish()
+    }
+}
+
+impl<T, C> FromIterator<T> for PersistentLeftistHeap<T, C>
+where
+    T: Clone,
+    C: Comparator<T> + Clone + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::default(), |heap, value| heap.push(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_persistent_leftist_heap() {
+        let mut rng = Xorshift::default();
+        for _ in 0..50 {
+            let mut heaps = vec![PersistentLeftistHeap::<i64>::new()];
+            let mut reference: Vec<Vec<i64>> = vec![vec![]];
+            for _ in 0..500 {
+                let (k, l) = (rng.random(0..heaps.len()), rng.random(0..heaps.len()));
+                match rng.random(0..3) {
+                    0 => {
+                        let value = rng.random(-1_000..1_000);
+                        heaps.push(heaps[k].push(value));
+                        let mut v = reference[k].clone();
+                        v.push(value);
+                        reference.push(v);
+                    }
+                    1 => {
+                        let mut v = reference[k].clone();
+                        v.sort_unstable();
+                        match heaps[k].pop() {
+                            Some((value, heap)) => {
+                                assert_eq!(value, v.remove(0));
+                                heaps.push(heap);
+                                reference.push(v);
+                            }
+                            None => assert!(v.is_empty()),
+                        }
+                    }
+                    _ if reference[k].len() + reference[l].len() <= 1_000 => {
+                        heaps.push(heaps[k].meld(&heaps[l]));
+                        let mut v = reference[k].clone();
+                        v.extend(&reference[l]);
+                        reference.push(v);
+                    }
+                    _ => {}
+                }
+                let (heap, v) = (heaps.last().unwrap(), reference.last().unwrap());
+                assert_eq!(heap.len(), v.len());
+                assert_eq!(heap.peek(), v.iter().min());
+            }
+            for (heap, mut v) in heaps.into_iter().zip(reference) {
+                v.sort_unstable();
+                assert_eq!(heap.into_sorted_vec(), v);
+            }
+        }
+    }
+}
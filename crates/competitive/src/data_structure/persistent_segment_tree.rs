@@ -23,15 +23,15 @@ impl<T> Node<T> {
 pub struct PersistentSegmentTreeVersion(usize);
 
 impl PersistentSegmentTreeVersion {
-    fn base() -> Self {
+    pub(super) fn base() -> Self {
         Self(0)
     }
 
-    fn new(version_id: usize) -> Self {
+    pub(super) fn new(version_id: usize) -> Self {
         Self(version_id)
     }
 
-    fn index(self) -> usize {
+    pub(super) fn index(self) -> usize {
         self.0
     }
 }
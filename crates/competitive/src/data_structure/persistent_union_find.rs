@@ -0,0 +1,92 @@
+use super::{PersistentArray, PersistentSegmentTreeVersion};
+use std::mem::swap;
+
+/// Fully persistent union-find by union by size without path compression
+#[derive(Debug)]
+pub struct PersistentUnionFind {
+    /// (parent, size), the size is valid only for roots
+    cells: PersistentArray<(usize, usize)>,
+}
+
+impl PersistentUnionFind {
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Self {
+            cells: PersistentArray::from_vec((0..n).map(|x| (x, 1)).collect()),
+        }
+    }
+
+    /// The version where every element is a singleton
+    pub fn base(&self) -> PersistentSegmentTreeVersion {
+        self.cells.base()
+    }
+
+    pub fn find(&self, version: PersistentSegmentTreeVersion, mut x: usize) -> usize {
+        loop {
+            let (parent, _) = self.cells.get(version, x);
+            if parent == x {
+                return x;
+            }
+            x = parent;
+        }
+    }
+
+    pub fn same(&self, version: PersistentSegmentTreeVersion, x: usize, y: usize) -> bool {
+        self.find(version, x) == self.find(version, y)
+    }
+
+    pub fn size(&self, version: PersistentSegmentTreeVersion, x: usize) -> usize {
+        self.cells.get(version, self.find(version, x)).1
+    }
+
+    /// Return the version after uniting, which is `version` itself if already united.
+    pub fn unite(
+        &mut self,
+        version: PersistentSegmentTreeVersion,
+        x: usize,
+        y: usize,
+    ) -> PersistentSegmentTreeVersion {
+        let mut x = self.find(version, x);
+        let mut y = self.find(version, y);
+        if x == y {
+            return version;
+        }
+        let (_, mut xsize) = self.cells.get(version, x);
+        let (_, mut ysize) = self.cells.get(version, y);
+        if xsize < ysize {
+            swap(&mut x, &mut y);
+            swap(&mut xsize, &mut ysize);
+        }
+        let version = self.cells.set(version, x, (x, xsize + ysize));
+        self.cells.set(version, y, (x, ysize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_structure::UnionFind, tools::Xorshift};
+
+    #[test]
+    fn test_persistent_union_find() {
+        const N: usize = 20;
+        const Q: usize = 2_000;
+        let mut rng = Xorshift::default();
+        let mut uf = PersistentUnionFind::new(N);
+        let mut versions = vec![uf.base()];
+        let mut states = vec![UnionFind::new(N)];
+        for _ in 0..Q {
+            let i = rng.random(0..versions.len());
+            let (x, y) = (rng.random(0..N), rng.random(0..N));
+            let mut state = states[i].clone();
+            state.unite(x, y);
+            versions.push(uf.unite(versions[i], x, y));
+            states.push(state);
+
+            let i = rng.random(0..versions.len());
+            let (x, y) = (rng.random(0..N), rng.random(0..N));
+            assert_eq!(uf.same(versions[i], x, y), states[i].same(x, y));
+            assert_eq!(uf.size(versions[i], x), states[i].size(x));
+        }
+    }
+}
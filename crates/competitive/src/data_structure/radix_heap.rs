@@ -0,0 +1,193 @@
+use std::mem::take;
+
+pub trait RadixHeapKey: Copy + Ord {
+    const BITS: usize;
+    const MIN: Self;
+    /// the highest different bit between `self` and `last` plus one, 0 if equal
+    fn radix_distance(self, last: Self) -> usize;
+}
+
+macro_rules! unsigned_radix_heap_key {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl RadixHeapKey for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const MIN: Self = <$t>::MIN;
+
+                fn radix_distance(self, last: Self) -> usize {
+                    (<$t>::BITS - (self ^ last).leading_zeros()) as usize
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed_radix_heap_key {
+    ($($t:ty => $u:ty),* $(,)?) => {
+        $(
+            impl RadixHeapKey for $t {
+                const BITS: usize = <$t>::BITS as usize;
+                const MIN: Self = <$t>::MIN;
+
+                fn radix_distance(self, last: Self) -> usize {
+                    (<$t>::BITS - (self as $u ^ last as $u).leading_zeros()) as usize
+                }
+            }
+        )*
+    };
+}
+
+unsigned_radix_heap_key!(u8, u16, u32, u64, u128, usize);
+signed_radix_heap_key!(
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+);
+
+/// Monotone priority queue for integer keys
+///
+/// Popped keys must be non-decreasing: a pushed key must not be less than the last popped key.
+#[derive(Debug, Clone)]
+pub struct RadixHeap<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+    last: K,
+    len: usize,
+}
+
+impl<K, V> Default for RadixHeap<K, V>
+where
+    K: RadixHeapKey,
+{
+    fn default() -> Self {
+        Self {
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+            last: K::MIN,
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> RadixHeap<K, V>
+where
+    K: RadixHeapKey,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The last popped key, or the minimum of `K` if nothing has been popped yet
+    pub fn last(&self) -> K {
+        self.last
+    }
+
+    pub fn push(&mut self, key: K, value: V) {
+        assert!(
+            self.last <= key,
+            "key must not be less than the last popped key"
+        );
+        self.buckets[key.radix_distance(self.last)].push((key, value));
+        self.len += 1;
+    }
+
+    fn pull(&mut self) {
+        if !self.buckets[0].is_empty() {
+            return;
+        }
+        let Some(i) = self.buckets.iter().position(|bucket| !bucket.is_empty()) else {
+            return;
+        };
+        let bucket = take(&mut self.buckets[i]);
+        let last = bucket.iter().map(|&(key, _)| key).min().unwrap();
+        self.last = last;
+        for (key, value) in bucket {
+            self.buckets[key.radix_distance(last)].push((key, value));
+        }
+    }
+
+    /// Returns the minimum key without popping.
+    pub fn peek_key(&mut self) -> Option<K> {
+        self.pull();
+        self.buckets[0].last().map(|&(key, _)| key)
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.pull();
+        let item = self.buckets[0].pop()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    pub fn clear(&mut self) {
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+        self.last = K::MIN;
+        self.len = 0;
+    }
+}
+
+impl<K, V> Extend<(K, V)> for RadixHeap<K, V>
+where
+    K: RadixHeapKey,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for RadixHeap<K, V>
+where
+    K: RadixHeapKey,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    #[test]
+    fn test_radix_heap() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let mut heap = RadixHeap::<i64, usize>::new();
+            let mut reference = BinaryHeap::new();
+            let mut last = rng.random(-1_000..1_000);
+            for i in 0..1_000 {
+                if rng.gen_bool(0.6) {
+                    let key = last + rng.random(0i64..100);
+                    heap.push(key, i);
+                    reference.push(Reverse(key));
+                } else {
+                    assert_eq!(heap.peek_key(), reference.peek().map(|r| r.0));
+                    let item = heap.pop();
+                    assert_eq!(item.map(|t| t.0), reference.pop().map(|r| r.0));
+                    if let Some((key, _)) = item {
+                        last = key;
+                        assert_eq!(heap.last(), key);
+                    }
+                }
+                assert_eq!(heap.len(), reference.len());
+            }
+        }
+    }
+}
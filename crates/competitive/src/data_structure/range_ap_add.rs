@@ -1,3 +1,7 @@
+use super::{AdditiveOperation, BinaryIndexedTree};
+use crate::num::{One, Zero};
+use std::ops::{Add, Mul, Neg, Sub};
+
 #[derive(Debug, Clone)]
 pub struct RangeArithmeticProgressionAdd {
     pub dd: Vec<i64>,
@@ -41,6 +45,63 @@ impl RangeArithmeticProgressionAdd {
     }
 }
 
+/// Range add of polynomials in the index and point get
+///
+/// Each term of degree `j` is kept in a range-add [`BinaryIndexedTree`] as a coefficient of $i^j$.
+#[derive(Debug, Clone)]
+pub struct RangePolynomialAdd<T>
+where
+    T: Clone + Zero + Add<Output = T>,
+{
+    bits: Vec<BinaryIndexedTree<AdditiveOperation<T>>>,
+}
+impl<T> RangePolynomialAdd<T>
+where
+    T: Clone
+        + Zero
+        + One
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Mul<Output = T>
+        + From<i64>,
+{
+    /// polynomials of degree at most `deg`
+    pub fn new(n: usize, deg: usize) -> Self {
+        Self {
+            bits: vec![BinaryIndexedTree::new(n + 1); deg + 1],
+        }
+    }
+    /// add $\sum_j \mathrm{coef}_j (i-l)^j$ into $i \in [l, r)$
+    ///
+    /// `update(l, r, &[a, d])` is the same as adding a, a+d, ..., a+(r-l-1)d
+    pub fn update(&mut self, l: usize, r: usize, coef: &[T]) {
+        assert!(coef.len() <= self.bits.len(), "degree is too large");
+        // Taylor shift by Horner's method: q(x) = p(x - l)
+        let neg_l = -T::from(l as i64);
+        let mut q: Vec<T> = Vec::with_capacity(coef.len());
+        for c in coef.iter().rev() {
+            q.push(T::zero());
+            for j in (1..q.len()).rev() {
+                q[j] = q[j - 1].clone() + q[j].clone() * neg_l.clone();
+            }
+            q[0] = q[0].clone() * neg_l.clone() + c.clone();
+        }
+        for (bit, q) in self.bits.iter_mut().zip(q) {
+            bit.update(l, q.clone());
+            bit.update(r, -q);
+        }
+    }
+    pub fn get(&self, i: usize) -> T {
+        let x = T::from(i as i64);
+        let mut res = T::zero();
+        for bit in self.bits.iter().rev() {
+            res = res * x.clone() + bit.accumulate(i);
+        }
+        res
+    }
+}
+
 #[test]
 fn test_range_ap_add() {
     use crate::tools::{NotEmptySegment as Nes, Xorshift};
@@ -59,3 +120,45 @@ fn test_range_ap_add() {
     ap.build_inplace();
     assert_eq!(ap.dd, v);
 }
+
+#[test]
+fn test_range_polynomial_add() {
+    use crate::{
+        num::montgomery::MInt998244353 as M,
+        tools::{NotEmptySegment as Nes, Xorshift},
+    };
+    const N: usize = 200;
+    const Q: usize = 1000;
+    let mut rng = Xorshift::default();
+    let mut v = vec![M::zero(); N];
+    let mut pa = RangePolynomialAdd::<M>::new(N, 4);
+    for _ in 0..Q {
+        let (l, r) = rng.random(Nes(N));
+        let deg = rng.random(0..5);
+        let coef: Vec<M> = (0..deg)
+            .map(|_| M::from(rng.random(0..1_000_000u32)))
+            .collect();
+        for (i, v) in v[l..r].iter_mut().enumerate() {
+            let x = M::from(i);
+            *v += coef.iter().rev().fold(M::zero(), |acc, &c| acc * x + c);
+        }
+        pa.update(l, r, &coef);
+        let i = rng.random(0..N);
+        assert_eq!(pa.get(i), v[i]);
+    }
+
+    let mut v = vec![0i64; N];
+    let mut pa = RangePolynomialAdd::<i64>::new(N, 1);
+    for ((l, r), a, d) in rng
+        .random_iter((Nes(N), -1000..=1000, -1000..=1000))
+        .take(Q)
+    {
+        for (i, v) in v[l..r].iter_mut().enumerate() {
+            *v += a + i as i64 * d;
+        }
+        pa.update(l, r, &[a, d]);
+    }
+    for (i, &v) in v.iter().enumerate() {
+        assert_eq!(pa.get(i), v);
+    }
+}
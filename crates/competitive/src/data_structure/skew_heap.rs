@@ -0,0 +1,245 @@
+use super::{Comparator, comparator::Less};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Formatter},
+    mem::swap,
+};
+
+#[derive(Clone)]
+struct Node<T> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T> Debug for Node<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("value", &self.value)
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+/// Meldable heap by skew heap
+#[derive(Clone)]
+pub struct SkewHeap<T, C = Less> {
+    root: Option<Box<Node<T>>>,
+    len: usize,
+    cmp: C,
+}
+
+impl<T, C> SkewHeap<T, C>
+where
+    C: Comparator<T>,
+{
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            root: None,
+            len: 0,
+            cmp,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    pub fn push(&mut self, value: T) {
+        let node = Box::new(Node {
+            value,
+            left: None,
+            right: None,
+        });
+        let root = self.root.take();
+        self.root = self.merge(root, Some(node));
+        self.len += 1;
+    }
+
+    /// Move all elements of `other` into `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        let (a, b) = (self.root.take(), other.root.take());
+        self.root = self.merge(a, b);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.root.take().map(|root| {
+            let Node { value, left, right } = *root;
+            self.root = self.merge(left, right);
+            self.len -= 1;
+            value
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.len);
+        while let Some(value) = self.pop() {
+            result.push(value);
+        }
+        result
+    }
+
+    fn merge(
+        &mut self,
+        mut a: Option<Box<Node<T>>>,
+        mut b: Option<Box<Node<T>>>,
+    ) -> Option<Box<Node<T>>> {
+        // merge right spines top-down, then swap children bottom-up
+        let mut spine = vec![];
+        let mut rest = loop {
+            match (a, b) {
+                (None, node) | (node, None) => break node,
+                (Some(mut x), Some(mut y)) => {
+                    if self.cmp.compare(&x.value, &y.value) == Ordering::Greater {
+                        swap(&mut x, &mut y);
+                    }
+                    a = x.right.take();
+                    b = Some(y);
+                    spine.push(x);
+                }
+            }
+        };
+        while let Some(mut node) = spine.pop() {
+            node.right = node.left.take();
+            node.left = rest;
+            rest = Some(node);
+        }
+        rest
+    }
+}
+
+impl<T, C> Default for SkewHeap<T, C>
+where
+    C: Comparator<T> + Default,
+{
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T> SkewHeap<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, C> Debug for SkewHeap<T, C>
+where
+    T: Debug,
+    C: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SkewHeap")
+            .field("len", &self.len)
+            .field("root", &self.root)
+            .field("cmp", &self.cmp)
+            .finish()
+    }
+}
+
+impl<T, C> Extend<T> for SkewHeap<T, C>
+where
+    C: Comparator<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, C> FromIterator<T> for SkewHeap<T, C>
+where
+    C: Comparator<T> + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::default();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T, C> Drop for SkewHeap<T, C> {
+    fn drop(&mut self) {
+        // avoid deep recursion on long left paths
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::{Xorshift, comparator::Greater};
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_skew_heap() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let mut heaps = [
+                SkewHeap::<i64, Greater>::with_comparator(Greater),
+                SkewHeap::default(),
+            ];
+            let mut reference = [BinaryHeap::new(), BinaryHeap::new()];
+            for _ in 0..1_000 {
+                let k = rng.random(0usize..2);
+                match rng.random(0..5) {
+                    0 | 1 => {
+                        let value: i64 = rng.random(-1_000..1_000);
+                        heaps[k].push(value);
+                        reference[k].push(value);
+                    }
+                    2 | 3 => {
+                        assert_eq!(heaps[k].pop(), reference[k].pop());
+                    }
+                    _ => {
+                        let [a, b] = &mut heaps;
+                        let [ra, rb] = &mut reference;
+                        if k == 0 {
+                            a.append(b);
+                            ra.append(rb);
+                        } else {
+                            b.append(a);
+                            rb.append(ra);
+                        }
+                    }
+                }
+                for (heap, reference) in heaps.iter().zip(&reference) {
+                    assert_eq!(heap.peek(), reference.peek());
+                    assert_eq!(heap.len(), reference.len());
+                }
+            }
+            for (heap, reference) in heaps.into_iter().zip(reference) {
+                let mut expected = reference.into_sorted_vec();
+                expected.reverse();
+                assert_eq!(heap.into_sorted_vec(), expected);
+            }
+        }
+    }
+}
@@ -1,12 +1,42 @@
-#[derive(Debug, Clone, Default)]
-pub struct SlopeTrick {
-    left: std::collections::BinaryHeap<i64>,
-    right: std::collections::BinaryHeap<i64>,
-    addl: i64,
-    addr: i64,
-    minval: i64,
+use super::{Signed, Zero};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    mem::swap,
+    ops::{Add, Sub},
+};
+
+/// Convex piecewise linear function
+///
+/// f(x) = min + sum_{l in L} max(0, l-x) + sum_{r in R} max(0, x-r) where max L <= min R
+///
+/// Breakpoints are stored as pairs of position and multiplicity.
+#[derive(Debug, Clone)]
+pub struct SlopeTrick<T = i64> {
+    left: BinaryHeap<(T, usize)>,
+    right: BinaryHeap<Reverse<(T, usize)>>,
+    addl: T,
+    addr: T,
+    minval: T,
 }
-impl SlopeTrick {
+impl<T> Default for SlopeTrick<T>
+where
+    T: Zero + Ord,
+{
+    fn default() -> Self {
+        Self {
+            left: BinaryHeap::new(),
+            right: BinaryHeap::new(),
+            addl: T::zero(),
+            addr: T::zero(),
+            minval: T::zero(),
+        }
+    }
+}
+impl<T> SlopeTrick<T>
+where
+    T: Copy + Ord + Zero + Add<Output = T> + Sub<Output = T>,
+{
     /// Create empty
     pub fn new() -> Self {
         Default::default()
@@ -14,62 +44,113 @@ impl SlopeTrick {
     /// Create valley
     ///
     /// f(x) = max(n(x-a), n(a-x))
-    pub fn valley(a: i64, n: usize) -> Self {
+    pub fn valley(a: T, n: usize) -> Self {
         let mut self_: Self = Default::default();
-        self_.left.extend(std::iter::repeat_n(a, n));
-        self_.right.extend(std::iter::repeat_n(a, n));
+        self_.push_left(a, n);
+        self_.push_right(a, n);
         self_
     }
-    fn push_left(&mut self, x: i64) {
-        self.left.push(x - self.addl);
+    /// x * k by doubling
+    fn mul_count(mut x: T, mut k: usize) -> T {
+        let mut res = T::zero();
+        while k > 0 {
+            if k & 1 == 1 {
+                res = res + x;
+            }
+            k >>= 1;
+            if k > 0 {
+                x = x + x;
+            }
+        }
+        res
+    }
+    fn push_left(&mut self, x: T, count: usize) {
+        if count > 0 {
+            self.left.push((x - self.addl, count));
+        }
+    }
+    fn push_right(&mut self, x: T, count: usize) {
+        if count > 0 {
+            self.right.push(Reverse((x - self.addr, count)));
+        }
+    }
+    fn peek_left(&self) -> Option<T> {
+        self.left.peek().map(|&(x, _)| x + self.addl)
+    }
+    fn peek_right(&self) -> Option<T> {
+        self.right.peek().map(|&Reverse((x, _))| x + self.addr)
+    }
+    fn pop_left(&mut self) -> Option<(T, usize)> {
+        self.left.pop().map(|(x, c)| (x + self.addl, c))
     }
-    fn push_right(&mut self, x: i64) {
-        self.right.push(-(x - self.addr));
+    fn pop_right(&mut self) -> Option<(T, usize)> {
+        self.right.pop().map(|Reverse((x, c))| (x + self.addr, c))
     }
-    fn peek_left(&self) -> Option<i64> {
-        self.left.peek().map(|x| x + self.addl)
+    /// Pop a single breakpoint
+    fn pop_left_one(&mut self) -> Option<T> {
+        let (x, c) = self.pop_left()?;
+        self.push_left(x, c - 1);
+        Some(x)
     }
-    fn peek_right(&self) -> Option<i64> {
-        self.right.peek().map(|x| -x + self.addr)
+    /// Pop a single breakpoint
+    fn pop_right_one(&mut self) -> Option<T> {
+        let (x, c) = self.pop_right()?;
+        self.push_right(x, c - 1);
+        Some(x)
     }
-    fn pop_left(&mut self) -> Option<i64> {
-        self.left.pop().map(|x| x + self.addl)
+    /// The number of breakpoints counted with multiplicity
+    pub fn len(&self) -> usize {
+        self.left.iter().map(|&(_, c)| c).sum::<usize>()
+            + self.right.iter().map(|&Reverse((_, c))| c).sum::<usize>()
     }
-    fn pop_right(&mut self) -> Option<i64> {
-        self.right.pop().map(|x| -x + self.addr)
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
     }
     /// min f(x)
-    pub fn minimum(&self) -> i64 {
+    pub fn minimum(&self) -> T {
         self.minval
     }
     /// argmin_x f(x)
-    pub fn min_range(&self) -> (Option<i64>, Option<i64>) {
+    pub fn min_range(&self) -> (Option<T>, Option<T>) {
         (self.peek_left(), self.peek_right())
     }
+    /// f(x)
+    pub fn eval(&self, x: T) -> T {
+        let zero = T::zero();
+        let left = self
+            .left
+            .iter()
+            .map(|&(l, c)| Self::mul_count((l + self.addl - x).max(zero), c));
+        let right = self
+            .right
+            .iter()
+            .map(|&Reverse((r, c))| Self::mul_count((x - (r + self.addr)).max(zero), c));
+        left.chain(right).fold(self.minval, |acc, y| acc + y)
+    }
     /// f(x) += a
-    pub fn add_const(&mut self, a: i64) {
-        self.minval += a;
+    pub fn add_const(&mut self, a: T) {
+        self.minval = self.minval + a;
     }
     /// f(x) += max(0, (x-a))
-    pub fn add_ramp(&mut self, a: i64) {
+    pub fn add_ramp(&mut self, a: T) {
         if let Some(x) = self.peek_left() {
-            self.minval += (x - a).max(0);
+            self.minval = self.minval + (x - a).max(T::zero());
         }
-        self.push_left(a);
-        let x = self.pop_left().unwrap();
-        self.push_right(x);
+        self.push_left(a, 1);
+        let x = self.pop_left_one().unwrap();
+        self.push_right(x, 1);
     }
     /// f(x) += max(0, (a-x))
-    pub fn add_pmar(&mut self, a: i64) {
+    pub fn add_pmar(&mut self, a: T) {
         if let Some(x) = self.peek_right() {
-            self.minval += (a - x).max(0);
+            self.minval = self.minval + (a - x).max(T::zero());
         }
-        self.push_right(a);
-        let x = self.pop_right().unwrap();
-        self.push_left(x);
+        self.push_right(a, 1);
+        let x = self.pop_right_one().unwrap();
+        self.push_left(x, 1);
     }
     /// f(x) += |x-a|
-    pub fn add_abs(&mut self, a: i64) {
+    pub fn add_abs(&mut self, a: T) {
         self.add_ramp(a);
         self.add_pmar(a);
     }
@@ -78,23 +159,355 @@ impl SlopeTrick {
     /// f'(x) := min f(y) (y >= x)
     pub fn clear_left(&mut self) {
         self.left.clear();
-        self.addl = 0;
+        self.addl = T::zero();
     }
     /// left to right accumulated minimum
     ///
     /// f'(x) := min f(y) (y <= x)
     pub fn clear_right(&mut self) {
         self.right.clear();
-        self.addr = 0;
+        self.addr = T::zero();
     }
     /// f'(x) := f(x-a)
-    pub fn shift(&mut self, a: i64) {
+    pub fn shift(&mut self, a: T) {
         self.slide_minimum(a, a);
     }
     /// f'(x) := min f(y) (x-a <= y <= x-b)
-    pub fn slide_minimum(&mut self, a: i64, b: i64) {
+    pub fn slide_minimum(&mut self, a: T, b: T) {
         assert!(a <= b);
-        self.addl += a;
-        self.addr += b;
+        self.addl = self.addl + a;
+        self.addr = self.addr + b;
+    }
+    /// f(x) += g(x)
+    ///
+    /// Breakpoints of the smaller one are moved into the larger one.
+    pub fn merge(&mut self, mut other: Self) {
+        if self.left.len() + self.right.len() < other.left.len() + other.right.len() {
+            swap(self, &mut other);
+        }
+        self.minval = self.minval + other.minval;
+        for &(l, c) in &other.left {
+            self.push_left(l + other.addl, c);
+        }
+        for &Reverse((r, c)) in &other.right {
+            self.push_right(r + other.addr, c);
+        }
+        // max(0, l-x) + max(0, x-r) = (l-r) + max(0, r-x) + max(0, x-l)
+        while let (Some(l), Some(r)) = (self.peek_left(), self.peek_right()) {
+            if l <= r {
+                break;
+            }
+            let (_, cl) = self.pop_left().unwrap();
+            let (_, cr) = self.pop_right().unwrap();
+            let k = cl.min(cr);
+            self.minval = self.minval + Self::mul_count(l - r, k);
+            self.push_left(l, cl - k);
+            self.push_right(r, cr - k);
+            self.push_left(r, k);
+            self.push_right(l, k);
+        }
+    }
+    /// h(x) := min f(y) + g(x-y)
+    pub fn infimal_convolution(mut self, mut other: Self) -> Self {
+        let mut res = Self::new();
+        res.minval = self.minval + other.minval;
+        while let (Some((x, cx)), Some((y, cy))) = (self.pop_left(), other.pop_left()) {
+            let k = cx.min(cy);
+            res.push_left(x + y, k);
+            self.push_left(x, cx - k);
+            other.push_left(y, cy - k);
+        }
+        while let (Some((x, cx)), Some((y, cy))) = (self.pop_right(), other.pop_right()) {
+            let k = cx.min(cy);
+            res.push_right(x + y, k);
+            self.push_right(x, cx - k);
+            other.push_right(y, cy - k);
+        }
+        res
+    }
+}
+impl<T> SlopeTrick<T>
+where
+    T: Signed + TryFrom<usize> + TryInto<usize>,
+{
+    /// Create f such that f(i) = values\[i\] for 0 <= i < n from a convex sequence
+    ///
+    /// Outside [0, n), f is extended by the boundary slopes clamped to keep its minimum finite.
+    pub fn from_values(values: &[T]) -> Self {
+        assert!(!values.is_empty());
+        let n = values.len();
+        let slope = |i: usize| values[i + 1] - values[i];
+        let count = |d: T| d.try_into().ok().expect("slope must fit usize");
+        let mut self_ = Self::new();
+        self_.minval = values.iter().copied().min().unwrap();
+        let mut cur = if n == 1 {
+            T::zero()
+        } else {
+            slope(0).min(T::zero())
+        };
+        let mut x = T::zero();
+        for i in 0..n {
+            let next = if i + 1 < n {
+                slope(i)
+            } else {
+                cur.max(T::zero())
+            };
+            assert!(cur <= next, "values must be convex");
+            // slopes in (cur, next] crossing 0
+            self_.push_left(x, count(next.min(T::zero()) - cur.min(T::zero())));
+            self_.push_right(x, count(next.max(T::zero()) - cur.max(T::zero())));
+            cur = next;
+            x += T::one();
+        }
+        self_
+    }
+    /// [f(0), f(1), ..., f(n-1)]
+    pub fn to_values(&self, n: usize) -> Vec<T> {
+        let to_t = |c: usize| T::try_from(c).ok().expect("count must fit the value type");
+        let mut left: Vec<(T, T)> = self
+            .left
+            .iter()
+            .map(|&(l, c)| (l + self.addl, to_t(c)))
+            .collect();
+        let mut right: Vec<(T, T)> = self
+            .right
+            .iter()
+            .map(|&Reverse((r, c))| (r + self.addr, to_t(c)))
+            .collect();
+        left.sort_unstable();
+        right.sort_unstable();
+        // sum and count of l > x and r < x
+        let (mut i, mut j) = (0, 0);
+        let mut sum_l: T = left.iter().map(|&(l, c)| l * c).sum();
+        let mut cnt_l: T = left.iter().map(|&(_, c)| c).sum();
+        let (mut sum_r, mut cnt_r) = (T::zero(), T::zero());
+        let mut x = T::zero();
+        let mut res = Vec::with_capacity(n);
+        for _ in 0..n {
+            while i < left.len() && left[i].0 <= x {
+                sum_l -= left[i].0 * left[i].1;
+                cnt_l -= left[i].1;
+                i += 1;
+            }
+            while j < right.len() && right[j].0 < x {
+                sum_r += right[j].0 * right[j].1;
+                cnt_r += right[j].1;
+                j += 1;
+            }
+            res.push(self.minval + sum_l - x * cnt_l + x * cnt_r - sum_r);
+            x += T::one();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::min_plus_convolution_convex_merge, num::Rational, tools::Xorshift};
+
+    const W: i64 = 400;
+
+    fn brute(f: &SlopeTrick) -> Vec<i64> {
+        (-W..=W).map(|x| f.eval(x)).collect()
+    }
+
+    fn random_function(rng: &mut Xorshift) -> SlopeTrick {
+        let mut f = SlopeTrick::new();
+        f.add_const(rng.random(-100..100));
+        for _ in 0..rng.random(0..10) {
+            match rng.random(0..3) {
+                0 => f.add_abs(rng.random(-30..30)),
+                1 => f.add_ramp(rng.random(-30..30)),
+                _ => f.add_pmar(rng.random(-30..30)),
+            }
+        }
+        f
+    }
+
+    #[test]
+    fn test_slope_trick() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let mut f: SlopeTrick = SlopeTrick::new();
+            let mut g = vec![0i64; (2 * W + 1) as usize];
+            for _ in 0..30 {
+                let a = rng.random(-30..30);
+                match rng.random(0..8) {
+                    0 => {
+                        f.add_const(a);
+                        g.iter_mut().for_each(|y| *y += a);
+                    }
+                    1 => {
+                        f.add_ramp(a);
+                        for (x, y) in (-W..).zip(&mut g) {
+                            *y += (x - a).max(0);
+                        }
+                    }
+                    2 => {
+                        f.add_pmar(a);
+                        for (x, y) in (-W..).zip(&mut g) {
+                            *y += (a - x).max(0);
+                        }
+                    }
+                    3 => {
+                        f.add_abs(a);
+                        for (x, y) in (-W..).zip(&mut g) {
+                            *y += (x - a).abs();
+                        }
+                    }
+                    4 => {
+                        f.clear_left();
+                        for i in (0..g.len() - 1).rev() {
+                            g[i] = g[i].min(g[i + 1]);
+                        }
+                    }
+                    5 => {
+                        f.clear_right();
+                        for i in 1..g.len() {
+                            g[i] = g[i].min(g[i - 1]);
+                        }
+                    }
+                    _ => {
+                        let a = rng.random(-3..=3);
+                        let b = rng.random(a..=3);
+                        f.slide_minimum(a, b);
+                        let n = g.len();
+                        g = (0..n as i64)
+                            .map(|i| {
+                                (i - b..=i - a)
+                                    .filter(|&j| 0 <= j && j < n as i64)
+                                    .map(|j| g[j as usize])
+                                    .min()
+                                    .unwrap_or(1 << 50)
+                            })
+                            .collect();
+                    }
+                }
+                assert_eq!(f.minimum(), *g[100..=700].iter().min().unwrap());
+                for x in -100..=100 {
+                    assert_eq!(f.eval(x), g[(x + W) as usize]);
+                }
+                if let (Some(l), Some(r)) = f.min_range() {
+                    assert!(l <= r);
+                    assert_eq!(f.eval(l), f.minimum());
+                    assert_eq!(f.eval(r), f.minimum());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_and_infimal_convolution() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let f = random_function(&mut rng);
+            let g = random_function(&mut rng);
+            let (bf, bg) = (brute(&f), brute(&g));
+
+            let mut h = f.clone();
+            h.merge(g.clone());
+            let expected: Vec<_> = bf.iter().zip(&bg).map(|(x, y)| x + y).collect();
+            assert_eq!(brute(&h), expected);
+            assert_eq!(h.minimum(), *expected.iter().min().unwrap());
+
+            let h = f.infimal_convolution(g);
+            for x in -100..=100 {
+                let expected = (-W..=W)
+                    .filter(|&y| -W <= x - y && x - y <= W)
+                    .map(|y| bf[(y + W) as usize] + bg[(x - y + W) as usize])
+                    .min()
+                    .unwrap();
+                assert_eq!(h.eval(x), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_values() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let n = rng.random(1..20);
+            let m = rng.random(1..20);
+            let convex = |rng: &mut Xorshift, n: usize| {
+                let mut slopes: Vec<i64> = (1..n).map(|_| rng.random(-5..5)).collect();
+                slopes.sort_unstable();
+                let mut values = vec![rng.random(-100..100)];
+                for s in slopes {
+                    values.push(values.last().unwrap() + s);
+                }
+                values
+            };
+            let (a, b) = (convex(&mut rng, n), convex(&mut rng, m));
+            let f = SlopeTrick::from_values(&a);
+            assert_eq!(f.to_values(n), a);
+            assert_eq!(f.minimum(), *a.iter().min().unwrap());
+            for (x, &y) in (0..).zip(&a) {
+                assert_eq!(f.eval(x), y);
+            }
+            let mut g = SlopeTrick::from_values(&b);
+            g.merge(f.clone());
+            let expected: Vec<_> = (0..n.min(m)).map(|i| a[i] + b[i]).collect();
+            assert_eq!(g.to_values(n.min(m)), expected);
+        }
+    }
+
+    #[test]
+    fn test_large_slopes() {
+        const S: i64 = 1_000_000_000_000;
+        let a = [3 * S, S, 0, 2 * S];
+        let f = SlopeTrick::from_values(&a);
+        assert_eq!(f.len(), 4 * S as usize);
+        assert_eq!(f.to_values(a.len()), a);
+        assert_eq!(f.eval(-1), 5 * S);
+        let mut g = SlopeTrick::valley(1, S as usize);
+        g.merge(f.clone());
+        assert_eq!(g.to_values(a.len()), [4 * S, S, S, 4 * S]);
+        let h = f.infimal_convolution(SlopeTrick::valley(0, S as usize));
+        assert_eq!(h.to_values(a.len()), [2 * S, S, 0, S]);
+    }
+
+    #[test]
+    fn test_infimal_convolution_of_values() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            // the same boundary slopes so that the extensions do not go below the inputs
+            let convex = |rng: &mut Xorshift| {
+                let mut slopes: Vec<i64> =
+                    (0..rng.random(0..20)).map(|_| rng.random(-5..5)).collect();
+                slopes.push(-5);
+                slopes.push(5);
+                slopes.sort_unstable();
+                let mut values = vec![rng.random(-100i64..100)];
+                for s in slopes {
+                    values.push(values.last().unwrap() + s);
+                }
+                values
+            };
+            let (a, b) = (convex(&mut rng), convex(&mut rng));
+            let h = SlopeTrick::from_values(&a).infimal_convolution(SlopeTrick::from_values(&b));
+            assert_eq!(
+                h.to_values(a.len() + b.len() - 1),
+                min_plus_convolution_convex_merge(&a, &b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rational() {
+        let r = |x: i64, y: i64| Rational::new(x, y);
+        let mut f = SlopeTrick::<Rational<i64>>::new();
+        f.add_abs(r(1, 2));
+        f.add_abs(r(-1, 3));
+        assert_eq!(f.minimum(), r(5, 6));
+        assert_eq!(f.min_range(), (Some(r(-1, 3)), Some(r(1, 2))));
+        f.add_ramp(r(0, 1));
+        assert_eq!(f.minimum(), r(5, 6));
+        assert_eq!(f.min_range(), (Some(r(-1, 3)), Some(r(0, 1))));
+        f.shift(r(1, 4));
+        assert_eq!(f.eval(r(1, 1)), r(25, 12));
+        let g = SlopeTrick::valley(r(0, 1), 2);
+        let h = f.infimal_convolution(g);
+        assert_eq!(h.minimum(), r(5, 6));
     }
 }
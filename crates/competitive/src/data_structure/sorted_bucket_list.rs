@@ -0,0 +1,630 @@
+use super::{Monoid, RangeBoundsExt};
+use std::{
+    borrow::Borrow,
+    fmt::{self, Debug, Formatter},
+    ops::{Bound, RangeBounds},
+};
+
+const BUCKET_SIZE: usize = 512;
+
+struct Bucket<K, M>
+where
+    M: Monoid,
+{
+    items: Vec<(K, M::T)>,
+    agg: M::T,
+}
+
+impl<K, M> Clone for Bucket<K, M>
+where
+    K: Clone,
+    M: Monoid,
+{
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            agg: self.agg.clone(),
+        }
+    }
+}
+
+impl<K, M> Debug for Bucket<K, M>
+where
+    K: Debug,
+    M: Monoid<T: Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bucket")
+            .field("items", &self.items)
+            .field("agg", &self.agg)
+            .finish()
+    }
+}
+
+impl<K, M> Bucket<K, M>
+where
+    M: Monoid,
+{
+    fn new(items: Vec<(K, M::T)>) -> Self {
+        let mut bucket = Self {
+            items,
+            agg: M::unit(),
+        };
+        bucket.recalc();
+        bucket
+    }
+
+    fn recalc(&mut self) {
+        self.agg = fold_items::<K, M>(&self.items);
+    }
+
+    fn last_key(&self) -> &K {
+        &self.items.last().unwrap().0
+    }
+}
+
+fn fold_items<K, M>(items: &[(K, M::T)]) -> M::T
+where
+    M: Monoid,
+{
+    items
+        .iter()
+        .fold(M::unit(), |acc, (_, value)| M::operate(&acc, value))
+}
+
+/// Ordered multiset by sorted buckets of $O(\sqrt{n})$ size
+///
+/// Each element is a pair of a key and a value, and values are aggregated by the monoid `M` in key order.
+/// Equal keys are kept in insertion order.
+/// Set `M = ()` if aggregation is not needed.
+pub struct SortedBucketList<K, M = ()>
+where
+    M: Monoid,
+{
+    buckets: Vec<Bucket<K, M>>,
+    len: usize,
+}
+
+impl<K, M> Clone for SortedBucketList<K, M>
+where
+    K: Clone,
+    M: Monoid,
+{
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, M> Debug for SortedBucketList<K, M>
+where
+    K: Debug,
+    M: Monoid<T: Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, M> Default for SortedBucketList<K, M>
+where
+    M: Monoid,
+{
+    fn default() -> Self {
+        Self {
+            buckets: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, M> SortedBucketList<K, M>
+where
+    M: Monoid,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    /// (bucket, offset) of the `index`-th element, or (buckets.len(), 0) if `index == len`
+    fn locate(&self, mut index: usize) -> (usize, usize) {
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            if index < bucket.items.len() {
+                return (i, index);
+            }
+            index -= bucket.items.len();
+        }
+        (self.buckets.len(), 0)
+    }
+
+    /// Number of elements in the buckets before `bucket`
+    fn count_before(&self, bucket: usize) -> usize {
+        self.buckets[..bucket]
+            .iter()
+            .map(|bucket| bucket.items.len())
+            .sum()
+    }
+
+    /// Number of elements whose key satisfies `pred`, where `pred` is monotone decreasing in key order.
+    fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&K) -> bool,
+    {
+        let i = self
+            .buckets
+            .partition_point(|bucket| pred(bucket.last_key()));
+        if i == self.buckets.len() {
+            return self.len;
+        }
+        self.count_before(i) + self.buckets[i].items.partition_point(|(key, _)| pred(key))
+    }
+
+    fn bound_to_index<Q>(&self, bound: Bound<&Q>, start: bool) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match (bound, start) {
+            (Bound::Included(key), true) | (Bound::Excluded(key), false) => self.rank(key),
+            (Bound::Excluded(key), true) | (Bound::Included(key), false) => {
+                self.partition_point(|k| k.borrow() <= key)
+            }
+            (Bound::Unbounded, true) => 0,
+            (Bound::Unbounded, false) => self.len,
+        }
+    }
+
+    fn key_range_to_index<Q, R>(&self, range: R) -> (usize, usize)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let l = self.bound_to_index(range.start_bound(), true);
+        let r = self.bound_to_index(range.end_bound(), false);
+        (l, r.max(l))
+    }
+
+    /// Number of elements less than `key`
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.partition_point(|k| k.borrow() < key)
+    }
+
+    /// Number of elements equal to `key`
+    pub fn count<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.partition_point(|k| k.borrow() <= key) - self.rank(key)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.select(self.rank(key))
+            .is_some_and(|(k, _)| k.borrow() == key)
+    }
+
+    /// The `index`-th smallest element (0-indexed)
+    pub fn select(&self, index: usize) -> Option<(&K, &M::T)> {
+        let (i, j) = self.locate(index);
+        self.buckets
+            .get(i)
+            .map(|bucket| (&bucket.items[j].0, &bucket.items[j].1))
+    }
+
+    pub fn first(&self) -> Option<(&K, &M::T)> {
+        self.select(0)
+    }
+
+    pub fn last(&self) -> Option<(&K, &M::T)> {
+        self.len.checked_sub(1).and_then(|index| self.select(index))
+    }
+
+    /// Inserts after the elements with the same key and returns the index of the new element.
+    pub fn insert(&mut self, key: K, value: M::T) -> usize
+    where
+        K: Ord,
+    {
+        self.len += 1;
+        if self.buckets.is_empty() {
+            self.buckets.push(Bucket::new(vec![(key, value)]));
+            return 0;
+        }
+        let i = self
+            .buckets
+            .partition_point(|bucket| bucket.last_key() <= &key)
+            .min(self.buckets.len() - 1);
+        let index = self.count_before(i);
+        let bucket = &mut self.buckets[i];
+        let j = bucket.items.partition_point(|(k, _)| k <= &key);
+        bucket.items.insert(j, (key, value));
+        if bucket.items.len() > BUCKET_SIZE * 2 {
+            let right = bucket.items.split_off(BUCKET_SIZE);
+            bucket.recalc();
+            self.buckets.insert(i + 1, Bucket::new(right));
+        } else {
+            bucket.recalc();
+        }
+        index + j
+    }
+
+    /// Removes the first element equal to `key`.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, M::T)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.rank(key);
+        if !self.select(index)?.0.borrow().eq(key) {
+            return None;
+        }
+        self.remove_at(index)
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> Option<(K, M::T)> {
+        let (i, j) = self.locate(index);
+        let bucket = self.buckets.get_mut(i)?;
+        let item = bucket.items.remove(j);
+        let size = bucket.items.len();
+        self.len -= 1;
+        if size < BUCKET_SIZE / 2 && self.buckets.len() > 1 {
+            self.merge_at(if i + 1 < self.buckets.len() { i } else { i - 1 });
+        } else if size == 0 {
+            self.buckets.clear();
+        } else {
+            self.buckets[i].recalc();
+        }
+        Some(item)
+    }
+
+    /// Merges the `i`-th bucket with the next one, and splits it in half if it gets too large.
+    fn merge_at(&mut self, i: usize) {
+        let right = self.buckets.remove(i + 1).items;
+        let bucket = &mut self.buckets[i];
+        bucket.items.extend(right);
+        if bucket.items.len() > BUCKET_SIZE * 2 {
+            let right = bucket.items.split_off(bucket.items.len() / 2);
+            bucket.recalc();
+            self.buckets.insert(i + 1, Bucket::new(right));
+        } else {
+            bucket.recalc();
+        }
+    }
+
+    /// Replaces the value of the `index`-th element and returns the old one.
+    pub fn set_value_at(&mut self, index: usize, value: M::T) -> Option<M::T> {
+        let (i, j) = self.locate(index);
+        let bucket = self.buckets.get_mut(i)?;
+        let old = std::mem::replace(&mut bucket.items[j].1, value);
+        bucket.recalc();
+        Some(old)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &M::T)> {
+        self.buckets
+            .iter()
+            .flat_map(|bucket| bucket.items.iter().map(|(key, value)| (key, value)))
+    }
+
+    /// Iterates over the elements whose key is in `range`.
+    pub fn range<Q, R>(&self, range: R) -> impl DoubleEndedIterator<Item = (&K, &M::T)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (l, r) = self.key_range_to_index(range);
+        self.range_at(l..r)
+    }
+
+    /// Iterates over the elements whose index is in `range`.
+    pub fn range_at<R>(&self, range: R) -> impl DoubleEndedIterator<Item = (&K, &M::T)>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = range.to_range_bounded(0, self.len).expect("invalid range");
+        let (bl, ol) = self.locate(range.start);
+        let (br, or) = self.locate(range.end);
+        self.buckets[bl..(br + 1).min(self.buckets.len())]
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, bucket)| {
+                let s = if i == 0 { ol } else { 0 };
+                let e = if bl + i == br { or } else { bucket.items.len() };
+                bucket.items[s..e].iter().map(|(key, value)| (key, value))
+            })
+    }
+
+    /// Aggregation of the values whose key is in `range`.
+    pub fn fold<Q, R>(&self, range: R) -> M::T
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (l, r) = self.key_range_to_index(range);
+        self.fold_at(l..r)
+    }
+
+    /// Aggregation of the values whose index is in `range`.
+    pub fn fold_at<R>(&self, range: R) -> M::T
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = range.to_range_bounded(0, self.len).expect("invalid range");
+        let (bl, ol) = self.locate(range.start);
+        let (br, or) = self.locate(range.end);
+        if bl == br {
+            return self
+                .buckets
+                .get(bl)
+                .map_or_else(M::unit, |bucket| fold_items::<K, M>(&bucket.items[ol..or]));
+        }
+        let mut acc = fold_items::<K, M>(&self.buckets[bl].items[ol..]);
+        for bucket in &self.buckets[bl + 1..br] {
+            acc = M::operate(&acc, &bucket.agg);
+        }
+        if br < self.buckets.len() {
+            acc = M::operate(&acc, &fold_items::<K, M>(&self.buckets[br].items[..or]));
+        }
+        acc
+    }
+
+    pub fn fold_all(&self) -> M::T {
+        self.buckets
+            .iter()
+            .fold(M::unit(), |acc, bucket| M::operate(&acc, &bucket.agg))
+    }
+
+    /// Index of the first element such that `f` holds for the aggregation from the beginning to it.
+    ///
+    /// `f` must be monotone: once it holds, it keeps holding for longer prefixes.
+    pub fn find_by_acc_cond<F>(&self, mut f: F) -> Option<usize>
+    where
+        F: FnMut(&M::T) -> bool,
+    {
+        let mut acc = M::unit();
+        let mut index = 0;
+        for bucket in &self.buckets {
+            let next = M::operate(&acc, &bucket.agg);
+            if !f(&next) {
+                acc = next;
+                index += bucket.items.len();
+                continue;
+            }
+            for (_, value) in &bucket.items {
+                acc = M::operate(&acc, value);
+                if f(&acc) {
+                    return Some(index);
+                }
+                index += 1;
+            }
+            unreachable!("bucket aggregation is inconsistent");
+        }
+        None
+    }
+
+    /// Index of the last element such that `f` holds for the aggregation from it to the end.
+    ///
+    /// `f` must be monotone: once it holds, it keeps holding for longer suffixes.
+    pub fn find_by_racc_cond<F>(&self, mut f: F) -> Option<usize>
+    where
+        F: FnMut(&M::T) -> bool,
+    {
+        let mut acc = M::unit();
+        let mut index = self.len;
+        for bucket in self.buckets.iter().rev() {
+            let next = M::operate(&bucket.agg, &acc);
+            if !f(&next) {
+                acc = next;
+                index -= bucket.items.len();
+                continue;
+            }
+            for (_, value) in bucket.items.iter().rev() {
+                acc = M::operate(value, &acc);
+                index -= 1;
+                if f(&acc) {
+                    return Some(index);
+                }
+            }
+            unreachable!("bucket aggregation is inconsistent");
+        }
+        None
+    }
+}
+
+impl<K, M> Extend<(K, M::T)> for SortedBucketList<K, M>
+where
+    K: Ord,
+    M: Monoid,
+{
+    fn extend<I: IntoIterator<Item = (K, M::T)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, M> FromIterator<(K, M::T)> for SortedBucketList<K, M>
+where
+    K: Ord,
+    M: Monoid,
+{
+    fn from_iter<I: IntoIterator<Item = (K, M::T)>>(iter: I) -> Self {
+        let mut items: Vec<_> = iter.into_iter().collect();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        let len = items.len();
+        let mut buckets = Vec::with_capacity(len.div_ceil(BUCKET_SIZE));
+        let mut iter = items.into_iter();
+        loop {
+            let chunk: Vec<_> = iter.by_ref().take(BUCKET_SIZE).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            buckets.push(Bucket::new(chunk));
+        }
+        Self { buckets, len }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::AdditiveOperation, tools::Xorshift};
+
+    #[test]
+    fn test_sorted_bucket_list() {
+        let mut rng = Xorshift::default();
+        for t in 0..20 {
+            let max = if t % 2 == 0 { 50 } else { 1_000_000 };
+            let mut list = SortedBucketList::<i64, AdditiveOperation<i64>>::new();
+            let mut reference: Vec<(i64, i64)> = vec![];
+            for _ in 0..4_000 {
+                let key = rng.random(0..max);
+                match rng.random(0..10) {
+                    0..=4 => {
+                        let value = rng.random(-1_000i64..1_000);
+                        let index = reference.partition_point(|&(k, _)| k <= key);
+                        reference.insert(index, (key, value));
+                        assert_eq!(list.insert(key, value), index);
+                    }
+                    5 => {
+                        let index = reference.partition_point(|&(k, _)| k < key);
+                        let expected = reference
+                            .get(index)
+                            .is_some_and(|&(k, _)| k == key)
+                            .then(|| reference.remove(index));
+                        assert_eq!(list.remove(&key), expected);
+                    }
+                    6 if !reference.is_empty() => {
+                        let index = rng.random(0..reference.len());
+                        assert_eq!(list.remove_at(index), Some(reference.remove(index)));
+                    }
+                    7 if !reference.is_empty() => {
+                        let index = rng.random(0..reference.len());
+                        let value = rng.random(-1_000i64..1_000);
+                        let old = std::mem::replace(&mut reference[index].1, value);
+                        assert_eq!(list.set_value_at(index, value), Some(old));
+                    }
+                    _ => {
+                        let other = rng.random(0..max);
+                        let (l, r) = (key.min(other), key.max(other));
+                        let expected: Vec<_> = reference
+                            .iter()
+                            .filter(|&&(k, _)| l <= k && k < r)
+                            .copied()
+                            .collect();
+                        let result: Vec<_> = list.range(l..r).map(|(&k, &v)| (k, v)).collect();
+                        assert_eq!(result, expected);
+                        assert_eq!(list.fold(l..r), expected.iter().map(|x| x.1).sum());
+                        let (i, j) = (
+                            rng.random(0..=reference.len()),
+                            rng.random(0..=reference.len()),
+                        );
+                        let (i, j) = (i.min(j), i.max(j));
+                        let result: Vec<_> =
+                            list.range_at(i..j).rev().map(|(&k, &v)| (k, v)).collect();
+                        let expected: Vec<_> = reference[i..j].iter().rev().copied().collect();
+                        assert_eq!(result, expected);
+                        assert_eq!(
+                            list.fold_at(i..j),
+                            reference[i..j].iter().map(|x| x.1).sum()
+                        );
+                    }
+                }
+                assert_eq!(list.len(), reference.len());
+                let rank = reference.partition_point(|&(k, _)| k < key);
+                assert_eq!(list.rank(&key), rank);
+                assert_eq!(
+                    list.count(&key),
+                    reference.iter().filter(|&&(k, _)| k == key).count()
+                );
+                assert_eq!(
+                    list.contains(&key),
+                    reference.iter().any(|&(k, _)| k == key)
+                );
+                let index = rng.random(0..=reference.len());
+                assert_eq!(
+                    list.select(index),
+                    reference.get(index).map(|(k, v)| (k, v))
+                );
+            }
+            assert!(
+                list.iter()
+                    .map(|(&k, &v)| (k, v))
+                    .eq(reference.iter().copied())
+            );
+        }
+    }
+
+    #[test]
+    fn test_remove_at_merges_buckets() {
+        let mut rng = Xorshift::default();
+        let mut list: SortedBucketList<usize, AdditiveOperation<i64>> =
+            (0..50_000).map(|k| (k, 1)).collect();
+        while list.len() > 1_000 {
+            list.remove_at(rng.random(0..list.len()));
+            assert!(list.buckets.len() <= list.len().div_ceil(BUCKET_SIZE / 2) + 1);
+        }
+        assert_eq!(list.fold_all(), 1_000);
+        assert!(
+            list.buckets
+                .iter()
+                .all(|bucket| bucket.agg == bucket.items.len() as i64)
+        );
+    }
+
+    #[test]
+    fn test_find_by_acc_cond() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let n = rng.random(0..3_000);
+            let mut items: Vec<(i64, i64)> = (0..n)
+                .map(|_| (rng.random(0..100), rng.random(0..100)))
+                .collect();
+            let list: SortedBucketList<i64, AdditiveOperation<i64>> =
+                items.iter().copied().collect();
+            items.sort_by_key(|x| x.0);
+            assert!(list.iter().map(|(&k, &v)| (k, v)).eq(items.iter().copied()));
+            assert_eq!(list.fold_all(), items.iter().map(|x| x.1).sum());
+            for _ in 0..10 {
+                let bound = rng.random(0..n as i64 * 60 + 1);
+                let mut acc = 0;
+                let expected = items.iter().position(|&(_, v)| {
+                    acc += v;
+                    acc >= bound
+                });
+                assert_eq!(list.find_by_acc_cond(|&s| s >= bound), expected);
+                let mut acc = 0;
+                let expected = items.iter().rposition(|&(_, v)| {
+                    acc += v;
+                    acc >= bound
+                });
+                assert_eq!(list.find_by_racc_cond(|&s| s >= bound), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use super::SemiGroup;
+use std::fmt::{self, Debug, Formatter};
+
+/// Range product over a semigroup with O(1) query and O(sqrt(n)) point update
+///
+/// Layer $k$ splits each block of $2^{s_k}$ elements into $2^{\lceil s_k/2 \rceil}$-sized sub-blocks
+/// with prefix/suffix products and products between sub-blocks.
+/// The top layer stores products between blocks as an index tree placed after the data.
+pub struct SqrtTree<S>
+where
+    S: SemiGroup,
+{
+    n: usize,
+    lg: usize,
+    index_len: usize,
+    layers: Vec<usize>,
+    on_layer: Vec<usize>,
+    /// [0, 2^lg): data, [2^lg, 2^lg + index_len): block products of the top layer
+    v: Vec<S::T>,
+    pref: Vec<Vec<S::T>>,
+    suf: Vec<Vec<S::T>>,
+    between: Vec<Vec<S::T>>,
+}
+
+impl<S> Clone for SqrtTree<S>
+where
+    S: SemiGroup,
+{
+    fn clone(&self) -> Self {
+        Self {
+            n: self.n,
+            lg: self.lg,
+            index_len: self.index_len,
+            layers: self.layers.clone(),
+            on_layer: self.on_layer.clone(),
+            v: self.v.clone(),
+            pref: self.pref.clone(),
+            suf: self.suf.clone(),
+            between: self.between.clone(),
+        }
+    }
+}
+
+impl<S> Debug for SqrtTree<S>
+where
+    S: SemiGroup<T: Debug>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqrtTree")
+            .field("data", &&self.v[..self.n])
+            .field("block_size", &self.block_size())
+            .field("layers", &self.layers)
+            .finish()
+    }
+}
+
+impl<S> SqrtTree<S>
+where
+    S: SemiGroup,
+{
+    pub fn new(data: Vec<S::T>) -> Self {
+        let lg = data.len().next_power_of_two().trailing_zeros() as usize;
+        Self::with_capacity_log(data, lg)
+    }
+
+    fn with_capacity_log(data: Vec<S::T>, lg: usize) -> Self {
+        let n = data.len();
+        let Some(filler) = data.first().cloned() else {
+            return Self {
+                n,
+                lg: 0,
+                index_len: 0,
+                layers: Vec::new(),
+                on_layer: Vec::new(),
+                v: data,
+                pref: Vec::new(),
+                suf: Vec::new(),
+                between: Vec::new(),
+            };
+        };
+        debug_assert!(n <= 1 << lg);
+        let mut layers = vec![];
+        let mut on_layer = vec![0; lg + 1];
+        let mut tlg = lg;
+        while tlg > 1 {
+            on_layer[tlg] = layers.len();
+            layers.push(tlg);
+            tlg = tlg.div_ceil(2);
+        }
+        for i in (0..lg).rev() {
+            on_layer[i] = on_layer[i].max(on_layer[i + 1]);
+        }
+        let cap = 1 << lg;
+        let block_log = lg.div_ceil(2);
+        let size = cap + (cap >> block_log);
+        let mut v = data;
+        v.resize(size, filler.clone());
+        let mut this = Self {
+            n,
+            lg,
+            index_len: n.div_ceil(1 << block_log),
+            pref: vec![vec![filler.clone(); size]; layers.len()],
+            suf: vec![vec![filler.clone(); size]; layers.len()],
+            between: vec![vec![filler; cap + (1 << block_log)]; layers.len().saturating_sub(1)],
+            layers,
+            on_layer,
+            v,
+        };
+        this.build(0, 0, n);
+        this
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> &S::T {
+        assert!(index < self.n);
+        &self.v[index]
+    }
+
+    /// Size of blocks rebuilt together by [`SqrtTree::update_block`]
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        1 << self.lg.div_ceil(2)
+    }
+
+    #[inline]
+    pub fn fold(&self, l: usize, r: usize) -> S::T {
+        assert!(l < r);
+        assert!(r <= self.n);
+        self.fold_close(l, r - 1, 0)
+    }
+
+    pub fn set(&mut self, index: usize, x: S::T) {
+        assert!(index < self.n);
+        self.v[index] = x;
+        self.update(0, 0, self.n, index);
+    }
+
+    /// Modify elements of the `block`-th block of [`SqrtTree::block_size`] at once.
+    pub fn update_block<F>(&mut self, block: usize, f: F)
+    where
+        F: FnOnce(&mut [S::T]),
+    {
+        let l = block * self.block_size();
+        assert!(l < self.n);
+        let r = self.n.min(l + self.block_size());
+        f(&mut self.v[l..r]);
+        if !self.layers.is_empty() {
+            self.build_block(0, l, r);
+            self.build(1, l, r);
+            self.update_between_zero(block);
+        }
+    }
+
+    /// Append an element, rebuilding all when the capacity is doubled.
+    pub fn push(&mut self, x: S::T) {
+        if self.n == 0 || self.n == 1 << self.lg {
+            let lg = if self.n == 0 { 0 } else { self.lg + 1 };
+            let mut data = std::mem::take(&mut self.v);
+            data.truncate(self.n);
+            data.push(x);
+            *self = Self::with_capacity_log(data, lg);
+        } else {
+            self.v[self.n] = x;
+            self.n += 1;
+            self.index_len = self.n.div_ceil(self.block_size());
+            self.update(0, 0, self.n, self.n - 1);
+        }
+    }
+
+    fn build_block(&mut self, layer: usize, l: usize, r: usize) {
+        let (pref, suf, v) = (&mut self.pref[layer], &mut self.suf[layer], &self.v);
+        pref[l] = v[l].clone();
+        for i in l + 1..r {
+            pref[i] = S::operate(&pref[i - 1], &v[i]);
+        }
+        suf[r - 1] = v[r - 1].clone();
+        for i in (l..r - 1).rev() {
+            suf[i] = S::operate(&v[i], &suf[i + 1]);
+        }
+    }
+
+    fn build_between(&mut self, layer: usize, lb: usize, rb: usize) {
+        let block_log = self.layers[layer].div_ceil(2);
+        let count_log = self.layers[layer] / 2;
+        let count = (rb - lb).div_ceil(1 << block_log);
+        let (between, suf) = (&mut self.between[layer - 1], &self.suf[layer]);
+        for i in 0..count {
+            let mut acc = suf[lb + (i << block_log)].clone();
+            between[lb + (i << count_log) + i] = acc.clone();
+            for j in i + 1..count {
+                acc = S::operate(&acc, &suf[lb + (j << block_log)]);
+                between[lb + (i << count_log) + j] = acc.clone();
+            }
+        }
+    }
+
+    fn build_between_zero(&mut self) {
+        let cap = 1 << self.lg;
+        let block_log = self.lg.div_ceil(2);
+        for i in 0..self.index_len {
+            self.v[cap + i] = self.suf[0][i << block_log].clone();
+        }
+        self.build(1, cap, cap + self.index_len);
+    }
+
+    fn update_between_zero(&mut self, block: usize) {
+        let cap = 1 << self.lg;
+        let block_log = self.lg.div_ceil(2);
+        self.v[cap + block] = self.suf[0][block << block_log].clone();
+        self.update(1, cap, cap + self.index_len, cap + block);
+    }
+
+    fn build(&mut self, layer: usize, lb: usize, rb: usize) {
+        if layer >= self.layers.len() {
+            return;
+        }
+        let block_size = 1 << self.layers[layer].div_ceil(2);
+        for l in (lb..rb).step_by(block_size) {
+            let r = rb.min(l + block_size);
+            self.build_block(layer, l, r);
+            self.build(layer + 1, l, r);
+        }
+        if layer == 0 {
+            self.build_between_zero();
+        } else {
+            self.build_between(layer, lb, rb);
+        }
+    }
+
+    fn update(&mut self, layer: usize, lb: usize, rb: usize, x: usize) {
+        if layer >= self.layers.len() {
+            return;
+        }
+        let block_log = self.layers[layer].div_ceil(2);
+        let block = (x - lb) >> block_log;
+        let l = lb + (block << block_log);
+        let r = rb.min(l + (1 << block_log));
+        self.build_block(layer, l, r);
+        if layer == 0 {
+            self.update_between_zero(block);
+        } else {
+            self.build_between(layer, lb, rb);
+        }
+        self.update(layer + 1, l, r, x);
+    }
+
+    fn fold_close(&self, l: usize, r: usize, base: usize) -> S::T {
+        if l == r {
+            return self.v[l].clone();
+        }
+        if l + 1 == r {
+            return S::operate(&self.v[l], &self.v[r]);
+        }
+        let bits = (usize::BITS - ((l - base) ^ (r - base)).leading_zeros()) as usize;
+        let layer = self.on_layer[bits];
+        let size_log = self.layers[layer];
+        let block_log = size_log.div_ceil(2);
+        let count_log = size_log / 2;
+        let lb = (((l - base) >> size_log) << size_log) + base;
+        let lblock = ((l - lb) >> block_log) + 1;
+        let rblock = (r - lb) >> block_log;
+        let mut acc = self.suf[layer][l].clone();
+        if lblock < rblock {
+            let mid = if layer == 0 {
+                let cap = 1 << self.lg;
+                self.fold_close(cap + lblock, cap + rblock - 1, cap)
+            } else {
+                self.between[layer - 1][lb + (lblock << count_log) + rblock - 1].clone()
+            };
+            acc = S::operate(&acc, &mid);
+        }
+        S::operate(&acc, &self.pref[layer][r])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::{AdditiveOperation, ConcatenateOperation, MinOperation},
+        tools::{NotEmptySegment as Nes, Xorshift},
+    };
+
+    fn check_all_ranges<S>(tree: &SqrtTree<S>, data: &[S::T])
+    where
+        S: SemiGroup,
+        S::T: Debug + PartialEq,
+    {
+        assert_eq!(tree.len(), data.len());
+        for l in 0..data.len() {
+            let mut expected = data[l].clone();
+            assert_eq!(tree.fold(l, l + 1), expected);
+            for r in l + 2..=data.len() {
+                expected = S::operate(&expected, &data[r - 1]);
+                assert_eq!(tree.fold(l, r), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_tree_static() {
+        let mut rng = Xorshift::default();
+        let mut sizes: Vec<usize> = (0..=70).collect();
+        sizes.extend((0..10).map(|_| rng.random(70usize..=300)));
+        for n in sizes {
+            let data: Vec<i64> = (0..n).map(|_| rng.random(-1000..=1000)).collect();
+            check_all_ranges(&SqrtTree::<AdditiveOperation<_>>::new(data.clone()), &data);
+            check_all_ranges(&SqrtTree::<MinOperation<_>>::new(data.clone()), &data);
+            let data: Vec<Vec<i32>> = (0..n.min(80)).map(|_| vec![rng.random(0..100)]).collect();
+            check_all_ranges(
+                &SqrtTree::<ConcatenateOperation<_>>::new(data.clone()),
+                &data,
+            );
+        }
+    }
+
+    #[test]
+    fn test_sqrt_tree_update() {
+        const Q: usize = 2_000;
+        let mut rng = Xorshift::default();
+        for n in [1, 2, 3, 5, 16, 17, 100, 1000] {
+            let mut data: Vec<Vec<i32>> = (0..n).map(|_| vec![rng.random(0..100)]).collect();
+            let mut tree = SqrtTree::<ConcatenateOperation<_>>::new(data.clone());
+            for _ in 0..Q {
+                match rng.random(0..3) {
+                    0 => {
+                        let i = rng.random(0..n);
+                        let x = vec![rng.random(0..100)];
+                        data[i] = x.clone();
+                        tree.set(i, x);
+                    }
+                    1 => {
+                        let b = rng.random(0..n.div_ceil(tree.block_size()));
+                        let l = b * tree.block_size();
+                        let r = n.min(l + tree.block_size());
+                        let xs: Vec<_> = (l..r).map(|_| vec![rng.random(0..100)]).collect();
+                        data[l..r].clone_from_slice(&xs);
+                        tree.update_block(b, |block| block.clone_from_slice(&xs));
+                    }
+                    _ => {
+                        let (l, r) = rng.random(Nes(n));
+                        assert_eq!(tree.fold(l, r), data[l..r].concat());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_tree_push() {
+        let mut rng = Xorshift::default();
+        let mut data: Vec<Vec<i32>> = vec![];
+        let mut tree = SqrtTree::<ConcatenateOperation<_>>::new(vec![]);
+        for _ in 0..300 {
+            let x = vec![rng.random(0..100)];
+            data.push(x.clone());
+            tree.push(x);
+            for _ in 0..10 {
+                let (l, r) = rng.random(Nes(data.len()));
+                assert_eq!(tree.fold(l, r), data[l..r].concat());
+            }
+            let i = rng.random(0..data.len());
+            let x = vec![rng.random(0..100)];
+            data[i] = x.clone();
+            tree.set(i, x);
+        }
+        check_all_ranges(&tree, &data);
+    }
+}
@@ -1,5 +1,8 @@
-use super::{IterScan, MarkedIterScan};
-use std::{marker::PhantomData, ops::Range};
+use super::{
+    Adjacencies, AdjacenciesWithEindex, AdjacencyIndex, AdjacencyIndexWithEindex, EIndexedGraph,
+    EdgeSize, GraphBase, IterScan, MarkedIterScan, VertexMap, VertexSize, Vertices,
+};
+use std::{iter::Cloned, marker::PhantomData, ops::Range, slice};
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Adjacency {
@@ -42,6 +45,72 @@ impl AdjacencyListGraph {
     }
 }
 
+impl GraphBase for AdjacencyListGraph {
+    type VIndex = usize;
+}
+impl EIndexedGraph for AdjacencyListGraph {
+    type EIndex = usize;
+}
+impl VertexSize for AdjacencyListGraph {
+    fn vsize(&self) -> usize {
+        self.vsize
+    }
+}
+impl EdgeSize for AdjacencyListGraph {
+    fn esize(&self) -> usize {
+        self.esize
+    }
+}
+impl Vertices for AdjacencyListGraph {
+    type VIter<'g> = Range<usize>;
+    fn vertices(&self) -> Self::VIter<'_> {
+        0..self.vsize
+    }
+}
+impl Adjacencies for AdjacencyListGraph {
+    type AIndex = Adjacency;
+    type AIter<'g> = Cloned<slice::Iter<'g, Adjacency>>;
+    fn adjacencies(&self, vid: Self::VIndex) -> Self::AIter<'_> {
+        self.graph[vid].iter().cloned()
+    }
+}
+impl AdjacenciesWithEindex for AdjacencyListGraph {
+    type AIndex = Adjacency;
+    type AIter<'g> = Cloned<slice::Iter<'g, Adjacency>>;
+    fn adjacencies_with_eindex(&self, vid: Self::VIndex) -> Self::AIter<'_> {
+        self.graph[vid].iter().cloned()
+    }
+}
+impl AdjacencyIndex for Adjacency {
+    type VIndex = usize;
+    fn vindex(&self) -> Self::VIndex {
+        self.to
+    }
+}
+impl AdjacencyIndexWithEindex for Adjacency {
+    type EIndex = usize;
+    fn eindex(&self) -> Self::EIndex {
+        self.id
+    }
+}
+impl<T> VertexMap<T> for AdjacencyListGraph {
+    type Vmap = Vec<T>;
+    fn construct_vmap<F>(&self, f: F) -> Self::Vmap
+    where
+        F: FnMut() -> T,
+    {
+        let mut v = Vec::with_capacity(self.vsize);
+        v.resize_with(self.vsize, f);
+        v
+    }
+    fn vmap_get<'a>(&self, map: &'a Self::Vmap, vid: Self::VIndex) -> &'a T {
+        &map[vid]
+    }
+    fn vmap_get_mut<'a>(&self, map: &'a mut Self::Vmap, vid: Self::VIndex) -> &'a mut T {
+        &mut map[vid]
+    }
+}
+
 pub struct AdjacencyListGraphScanner<U: IterScan<Output = usize>, T: IterScan> {
     vsize: usize,
     esize: usize,
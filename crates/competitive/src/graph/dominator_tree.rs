@@ -0,0 +1,210 @@
+use super::{Adjacencies, AdjacencyIndex, GraphBase, VertexMap};
+
+pub trait DominatorTreeExt: GraphBase {
+    /// Lengauer-Tarjan algorithm in O(m log n)
+    fn dominator_tree(&self, root: Self::VIndex) -> DominatorTree<'_, Self>
+    where
+        Self: Adjacencies + VertexMap<usize> + VertexMap<Option<<Self as GraphBase>::VIndex>>,
+    {
+        // preorder numbering by DFS; a vertex is numbered when it is popped
+        let mut ord = <Self as VertexMap<usize>>::construct_vmap(self, || !0);
+        let mut order = vec![];
+        let mut parent = vec![];
+        let mut stack = vec![(root, !0)];
+        while let Some((u, p)) = stack.pop() {
+            let o = <Self as VertexMap<usize>>::vmap_get_mut(self, &mut ord, u);
+            if *o != !0 {
+                continue;
+            }
+            let i = order.len();
+            *o = i;
+            order.push(u);
+            parent.push(p);
+            for a in self.adjacencies(u) {
+                let v = a.vindex();
+                if *<Self as VertexMap<usize>>::vmap_get(self, &ord, v) == !0 {
+                    stack.push((v, i));
+                }
+            }
+        }
+
+        let n = order.len();
+        let mut pred = vec![vec![]; n];
+        for (i, &u) in order.iter().enumerate() {
+            for a in self.adjacencies(u) {
+                let j = *<Self as VertexMap<usize>>::vmap_get(self, &ord, a.vindex());
+                if j != !0 {
+                    pred[j].push(i);
+                }
+            }
+        }
+
+        let mut semi: Vec<usize> = (0..n).collect();
+        let mut idom: Vec<usize> = (0..n).collect();
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut ancestor = vec![!0; n];
+        let mut bucket = vec![vec![]; n];
+        let mut path = vec![];
+        let mut eval = |v: usize, semi: &[usize], label: &mut [usize], ancestor: &mut [usize]| {
+            if ancestor[v] == !0 {
+                return v;
+            }
+            let mut x = v;
+            while ancestor[ancestor[x]] != !0 {
+                path.push(x);
+                x = ancestor[x];
+            }
+            while let Some(y) = path.pop() {
+                let a = ancestor[y];
+                if semi[label[a]] < semi[label[y]] {
+                    label[y] = label[a];
+                }
+                ancestor[y] = ancestor[a];
+            }
+            label[v]
+        };
+        for w in (1..n).rev() {
+            for &v in &pred[w] {
+                let u = eval(v, &semi, &mut label, &mut ancestor);
+                semi[w] = semi[w].min(semi[u]);
+            }
+            bucket[semi[w]].push(w);
+            let p = parent[w];
+            ancestor[w] = p;
+            for v in std::mem::take(&mut bucket[p]) {
+                let u = eval(v, &semi, &mut label, &mut ancestor);
+                idom[v] = if semi[u] < semi[v] { u } else { p };
+            }
+        }
+        for w in 1..n {
+            if idom[w] != semi[w] {
+                idom[w] = idom[idom[w]];
+            }
+        }
+
+        let mut idom_map = <Self as VertexMap<Option<Self::VIndex>>>::construct_vmap(self, || None);
+        for (&u, &d) in order.iter().zip(&idom) {
+            <Self as VertexMap<Option<Self::VIndex>>>::vmap_set(
+                self,
+                &mut idom_map,
+                u,
+                Some(order[d]),
+            );
+        }
+        DominatorTree {
+            graph: self,
+            root,
+            order,
+            idom: idom_map,
+        }
+    }
+}
+impl<G> DominatorTreeExt for G where G: GraphBase {}
+
+pub struct DominatorTree<'g, G>
+where
+    G: GraphBase + VertexMap<Option<<G as GraphBase>::VIndex>> + ?Sized,
+{
+    graph: &'g G,
+    root: G::VIndex,
+    order: Vec<G::VIndex>,
+    /// `Some(root)` for the root, `None` for unreachable vertices
+    idom: <G as VertexMap<Option<G::VIndex>>>::Vmap,
+}
+impl<G> DominatorTree<'_, G>
+where
+    G: GraphBase + VertexMap<Option<<G as GraphBase>::VIndex>> + ?Sized,
+{
+    pub fn root(&self) -> G::VIndex {
+        self.root
+    }
+    /// Reachable vertices in DFS preorder, every vertex appears after its immediate dominator.
+    pub fn order(&self) -> &[G::VIndex] {
+        &self.order
+    }
+    pub fn is_reachable(&self, v: G::VIndex) -> bool {
+        self.graph.vmap_get(&self.idom, v).is_some()
+    }
+    /// `None` for the root and unreachable vertices
+    pub fn immediate_dominator(&self, v: G::VIndex) -> Option<G::VIndex> {
+        if v == self.root {
+            None
+        } else {
+            *self.graph.vmap_get(&self.idom, v)
+        }
+    }
+    /// Immediate dominators as a vertex map: `Some(root)` for the root, `None` for unreachable vertices.
+    pub fn idom(&self) -> &<G as VertexMap<Option<G::VIndex>>>::Vmap {
+        &self.idom
+    }
+    pub fn into_idom(self) -> <G as VertexMap<Option<G::VIndex>>>::Vmap {
+        self.idom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{AdjacencyListGraph, DirectedSparseGraph},
+        tools::Xorshift,
+    };
+
+    fn reachable(g: &DirectedSparseGraph, root: usize, removed: usize) -> Vec<bool> {
+        let mut visited = vec![false; g.vertices_size()];
+        if root == removed {
+            return visited;
+        }
+        let mut stack = vec![root];
+        visited[root] = true;
+        while let Some(u) = stack.pop() {
+            for a in g.adjacencies(u) {
+                if a.to != removed && !visited[a.to] {
+                    visited[a.to] = true;
+                    stack.push(a.to);
+                }
+            }
+        }
+        visited
+    }
+
+    #[test]
+    fn test_dominator_tree() {
+        let mut rng = Xorshift::default();
+        for _ in 0..300 {
+            let n = rng.random(1usize..=30);
+            let m = rng.random(0..=n * 3);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.random(0..n), rng.random(0..n)))
+                .collect();
+            let root = rng.random(0..n);
+            let g = DirectedSparseGraph::from_edges(n, edges.clone());
+            let dt = g.dominator_tree(root);
+
+            let base = reachable(&g, root, !0);
+            // dom[v]: strict dominators of v
+            let mut dom = vec![vec![]; n];
+            for d in 0..n {
+                let r = reachable(&g, root, d);
+                for v in 0..n {
+                    if v != d && base[v] && !r[v] {
+                        dom[v].push(d);
+                    }
+                }
+            }
+            for v in 0..n {
+                assert_eq!(dt.is_reachable(v), base[v]);
+                let expected = dom[v].iter().copied().max_by_key(|&d| dom[d].len());
+                assert_eq!(dt.immediate_dominator(v), expected);
+            }
+            assert_eq!(dt.idom()[root], Some(root));
+            assert_eq!(dt.order().len(), base.iter().filter(|&&b| b).count());
+
+            let mut h = AdjacencyListGraph::new(n);
+            for &(u, v) in &edges {
+                h.add_edge(u, v);
+            }
+            assert_eq!(h.dominator_tree(root).into_idom(), dt.into_idom());
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{algebra::AbelianMonoid, tree::EulerTourTree};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy)]
+struct EdgeInfo {
+    count: usize,
+    level: usize,
+    is_tree: bool,
+}
+
+/// Fully dynamic connectivity by Holm–de Lichtenberg–Thorup
+///
+/// `forests[i]` is a spanning forest of edges with level at least `i`,
+/// and values are folded in `forests[0]`.
+/// Each operation takes amortized $O(\log^2 n)$ time.
+pub struct DynamicConnectivity<M>
+where
+    M: AbelianMonoid,
+{
+    n: usize,
+    forests: Vec<EulerTourTree<M>>,
+    /// non-tree edges for each level
+    adjacency: Vec<Vec<HashSet<usize>>>,
+    edges: HashMap<(usize, usize), EdgeInfo>,
+}
+
+impl<M> DynamicConnectivity<M>
+where
+    M: AbelianMonoid,
+{
+    pub fn new(n: usize) -> Self {
+        (0..n).map(|_| M::unit()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    fn ensure_level(&mut self, level: usize) {
+        while self.forests.len() <= level {
+            self.forests.push(EulerTourTree::new(self.n));
+            self.adjacency.push(vec![HashSet::new(); self.n]);
+        }
+    }
+
+    fn insert_non_tree(&mut self, level: usize, u: usize, v: usize) {
+        for (x, y) in [(u, v), (v, u)] {
+            if self.adjacency[level][x].is_empty() {
+                self.forests[level].set_vertex_mark(x, true);
+            }
+            self.adjacency[level][x].insert(y);
+        }
+    }
+
+    fn remove_non_tree(&mut self, level: usize, u: usize, v: usize) {
+        for (x, y) in [(u, v), (v, u)] {
+            self.adjacency[level][x].remove(&y);
+            if self.adjacency[level][x].is_empty() {
+                self.forests[level].set_vertex_mark(x, false);
+            }
+        }
+    }
+
+    fn insert_tree(&mut self, level: usize, u: usize, v: usize) {
+        for forest in &mut self.forests[..=level] {
+            forest.link(u, v);
+        }
+        self.forests[level].set_edge_mark(u, v, true);
+    }
+
+    pub fn is_connected(&mut self, u: usize, v: usize) -> bool {
+        self.forests[0].is_connected(u, v)
+    }
+
+    /// Add an edge (u, v), multi edges and self loops are allowed.
+    pub fn link(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        if let Some(info) = self.edges.get_mut(&key) {
+            info.count += 1;
+            return;
+        }
+        let is_tree = u != v && !self.is_connected(u, v);
+        self.edges.insert(
+            key,
+            EdgeInfo {
+                count: 1,
+                level: 0,
+                is_tree,
+            },
+        );
+        if is_tree {
+            self.insert_tree(0, key.0, key.1);
+        } else if u != v {
+            self.insert_non_tree(0, u, v);
+        }
+    }
+
+    /// Remove an edge (u, v), which must exist.
+    pub fn cut(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        let info = self.edges.get_mut(&key).expect("edge not found");
+        info.count -= 1;
+        if info.count > 0 {
+            return;
+        }
+        let info = self.edges.remove(&key).unwrap();
+        if u == v {
+            return;
+        }
+        if !info.is_tree {
+            self.remove_non_tree(info.level, u, v);
+            return;
+        }
+        for forest in &mut self.forests[..=info.level] {
+            forest.cut(key.0, key.1);
+        }
+        for level in (0..=info.level).rev() {
+            if self.replace(level, u, v) {
+                break;
+            }
+        }
+    }
+
+    /// Find a replacement edge of level `level` between the trees of `u` and `v`.
+    fn replace(&mut self, level: usize, u: usize, v: usize) -> bool {
+        let (small, large) =
+            if self.forests[level].component_size(u) <= self.forests[level].component_size(v) {
+                (u, v)
+            } else {
+                (v, u)
+            };
+        self.ensure_level(level + 1);
+        while let Some((x, y)) = self.forests[level].find_marked_edge(small) {
+            self.forests[level].set_edge_mark(x, y, false);
+            self.edges.get_mut(&(x.min(y), x.max(y))).unwrap().level = level + 1;
+            self.forests[level + 1].link(x, y);
+            self.forests[level + 1].set_edge_mark(x, y, true);
+        }
+        while let Some(x) = self.forests[level].find_marked_vertex(small) {
+            while let Some(&y) = self.adjacency[level][x].iter().next() {
+                self.remove_non_tree(level, x, y);
+                let info = self.edges.get_mut(&(x.min(y), x.max(y))).unwrap();
+                if self.forests[level].is_connected(y, large) {
+                    info.is_tree = true;
+                    self.insert_tree(level, x, y);
+                    return true;
+                }
+                info.level = level + 1;
+                self.insert_non_tree(level + 1, x, y);
+            }
+        }
+        false
+    }
+
+    /// The number of vertices in the component containing `v`
+    pub fn component_size(&mut self, v: usize) -> usize {
+        self.forests[0].component_size(v)
+    }
+
+    /// Fold values in the component containing `v`
+    pub fn fold(&mut self, v: usize) -> M::T {
+        self.forests[0].fold(v)
+    }
+
+    pub fn get(&self, v: usize) -> &M::T {
+        self.forests[0].get(v)
+    }
+
+    pub fn set(&mut self, v: usize, x: M::T) {
+        self.forests[0].set(v, x);
+    }
+}
+
+impl<M> FromIterator<M::T> for DynamicConnectivity<M>
+where
+    M: AbelianMonoid,
+{
+    fn from_iter<T: IntoIterator<Item = M::T>>(iter: T) -> Self {
+        let forest: EulerTourTree<M> = iter.into_iter().collect();
+        let n = forest.len();
+        Self {
+            n,
+            forests: vec![forest],
+            adjacency: vec![vec![HashSet::new(); n]],
+            edges: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::AdditiveOperation, data_structure::UnionFind, tools::Xorshift};
+
+    #[test]
+    fn test_dynamic_connectivity() {
+        const Q: usize = 3_000;
+        let mut rng = Xorshift::default();
+        for n in [1, 2, 5, 10, 30] {
+            let mut values: Vec<i64> = (0..n).map(|_| rng.random(-100..100)).collect();
+            let mut dc: DynamicConnectivity<AdditiveOperation<i64>> =
+                values.iter().cloned().collect();
+            let mut edges: Vec<(usize, usize)> = vec![];
+            for _ in 0..Q {
+                let (u, v) = (rng.random(0..n), rng.random(0..n));
+                match rng.random(0..4) {
+                    0 => {
+                        dc.link(u, v);
+                        edges.push((u, v));
+                    }
+                    1 if !edges.is_empty() => {
+                        let (u, v) = edges.swap_remove(rng.random(0..edges.len()));
+                        dc.cut(v, u);
+                    }
+                    2 => {
+                        let x = rng.random(-100..100);
+                        values[u] = x;
+                        dc.set(u, x);
+                    }
+                    _ => {
+                        let mut uf = UnionFind::new(n);
+                        for &(u, v) in &edges {
+                            uf.unite(u, v);
+                        }
+                        assert_eq!(dc.is_connected(u, v), uf.same(u, v));
+                        assert_eq!(dc.component_size(u), uf.size(u));
+                        let sum: i64 = (0..n).filter(|&w| uf.same(u, w)).map(|w| values[w]).sum();
+                        assert_eq!(dc.fold(u), sum);
+                        assert_eq!(*dc.get(u), values[u]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use super::{DirectedSparseGraph, SparseGraph, UndirectedSparseGraph};
+
+impl<D> SparseGraph<D> {
+    /// Hierholzer's algorithm from `s`, `None` if some edges are not reachable.
+    ///
+    /// Return (vertices, edge ids) of the trail.
+    fn hierholzer(&self, s: usize, undirected: bool) -> Option<(Vec<usize>, Vec<usize>)> {
+        let m = self.edges_size();
+        let mut used = vec![false; if undirected { m } else { 0 }];
+        let mut ptr = self.start.clone();
+        let mut vertices = Vec::with_capacity(m + 1);
+        let mut edges = Vec::with_capacity(m);
+        let mut stack = vec![(s, !0)];
+        while let Some(&(u, _)) = stack.last() {
+            let mut next = None;
+            while ptr[u] < self.start[u + 1] {
+                let a = self.elist[ptr[u]];
+                ptr[u] += 1;
+                if undirected {
+                    if used[a.id] {
+                        continue;
+                    }
+                    used[a.id] = true;
+                }
+                next = Some(a);
+                break;
+            }
+            if let Some(a) = next {
+                stack.push((a.to, a.id));
+            } else {
+                let (u, e) = stack.pop().unwrap();
+                vertices.push(u);
+                if e != !0 {
+                    edges.push(e);
+                }
+            }
+        }
+        if edges.len() != m {
+            return None;
+        }
+        vertices.reverse();
+        edges.reverse();
+        Some((vertices, edges))
+    }
+}
+
+impl DirectedSparseGraph {
+    /// Return (vertices, edge ids) of an Eulerian trail which uses every edge exactly once.
+    ///
+    /// A graph without edges has the trail of the single vertex `0`.
+    pub fn eulerian_trail(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.eulerian_trail_with(false)
+    }
+    /// Return (vertices, edge ids) of an Eulerian circuit which uses every edge exactly once.
+    ///
+    /// A graph without edges has the circuit of the single vertex `0`.
+    pub fn eulerian_circuit(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.eulerian_trail_with(true)
+    }
+    fn eulerian_trail_with(&self, circuit: bool) -> Option<(Vec<usize>, Vec<usize>)> {
+        if self.vertices_size() == 0 {
+            return Some((vec![], vec![]));
+        }
+        let mut diff = vec![0i64; self.vertices_size()];
+        for &(u, v) in &self.edges {
+            diff[u] += 1;
+            diff[v] -= 1;
+        }
+        let mut s = None;
+        for u in self.vertices() {
+            match diff[u] {
+                0 => {}
+                1 if !circuit && s.is_none() => s = Some(u),
+                -1 if !circuit => {}
+                _ => return None,
+            }
+        }
+        if diff.iter().filter(|&&d| d == -1).count() > 1 {
+            return None;
+        }
+        let s = s
+            .or_else(|| self.edges.first().map(|&(u, _)| u))
+            .unwrap_or(0);
+        self.hierholzer(s, false)
+    }
+}
+
+impl UndirectedSparseGraph {
+    /// Return (vertices, edge ids) of an Eulerian trail which uses every edge exactly once.
+    ///
+    /// A graph without edges has the trail of the single vertex `0`.
+    pub fn eulerian_trail(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.eulerian_trail_with(false)
+    }
+    /// Return (vertices, edge ids) of an Eulerian circuit which uses every edge exactly once.
+    ///
+    /// A graph without edges has the circuit of the single vertex `0`.
+    pub fn eulerian_circuit(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.eulerian_trail_with(true)
+    }
+    fn eulerian_trail_with(&self, circuit: bool) -> Option<(Vec<usize>, Vec<usize>)> {
+        if self.vertices_size() == 0 {
+            return Some((vec![], vec![]));
+        }
+        // a self-loop appears twice in the adjacencies
+        let odd: Vec<usize> = self
+            .vertices()
+            .filter(|&u| self.adjacencies(u).len() % 2 == 1)
+            .collect();
+        if odd.len() > if circuit { 0 } else { 2 } {
+            return None;
+        }
+        let s = odd
+            .first()
+            .copied()
+            .or_else(|| self.edges.first().map(|&(u, _)| u))
+            .unwrap_or(0);
+        self.hierholzer(s, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    fn random_edges(rng: &mut Xorshift, n: usize) -> Vec<(usize, usize)> {
+        let m = rng.random(0..=n * 2);
+        (0..m)
+            .map(|_| (rng.random(0..n), rng.random(0..n)))
+            .collect()
+    }
+
+    fn brute(n: usize, edges: &[(usize, usize)], undirected: bool, circuit: bool) -> bool {
+        fn dfs(
+            u: usize,
+            edges: &[(usize, usize)],
+            used: &mut [bool],
+            rest: usize,
+            undirected: bool,
+            goal: Option<usize>,
+        ) -> bool {
+            if rest == 0 {
+                return goal.is_none_or(|g| g == u);
+            }
+            for (i, &(x, y)) in edges.iter().enumerate() {
+                if used[i] {
+                    continue;
+                }
+                let to = if x == u {
+                    y
+                } else if undirected && y == u {
+                    x
+                } else {
+                    continue;
+                };
+                used[i] = true;
+                let ok = dfs(to, edges, used, rest - 1, undirected, goal);
+                used[i] = false;
+                if ok {
+                    return true;
+                }
+            }
+            false
+        }
+        if edges.is_empty() {
+            return true;
+        }
+        (0..n).any(|s| {
+            let goal = if circuit { Some(s) } else { None };
+            dfs(
+                s,
+                edges,
+                &mut vec![false; edges.len()],
+                edges.len(),
+                undirected,
+                goal,
+            )
+        })
+    }
+
+    fn check(
+        edges: &[(usize, usize)],
+        res: &(Vec<usize>, Vec<usize>),
+        undirected: bool,
+        circuit: bool,
+    ) {
+        let (vs, es) = res;
+        assert_eq!(es.len(), edges.len());
+        assert_eq!(vs.len(), edges.len() + 1);
+        let mut sorted = es.clone();
+        sorted.sort_unstable();
+        assert!(sorted.iter().copied().eq(0..edges.len()));
+        for (w, &e) in vs.windows(2).zip(es) {
+            let (x, y) = edges[e];
+            assert!((x, y) == (w[0], w[1]) || undirected && (y, x) == (w[0], w[1]));
+        }
+        if circuit {
+            assert_eq!(vs.first(), vs.last());
+        }
+    }
+
+    #[test]
+    fn test_eulerian_trail_directed() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let n = rng.random(1usize..=5);
+            let edges = random_edges(&mut rng, n);
+            let g = DirectedSparseGraph::from_edges(n, edges.clone());
+            for circuit in [false, true] {
+                let res = if circuit {
+                    g.eulerian_circuit()
+                } else {
+                    g.eulerian_trail()
+                };
+                assert_eq!(res.is_some(), brute(n, &edges, false, circuit));
+                if let Some(res) = res {
+                    check(&edges, &res, false, circuit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_eulerian_trail_undirected() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let n = rng.random(1usize..=5);
+            let edges = random_edges(&mut rng, n);
+            let g = UndirectedSparseGraph::from_edges(n, edges.clone());
+            for circuit in [false, true] {
+                let res = if circuit {
+                    g.eulerian_circuit()
+                } else {
+                    g.eulerian_trail()
+                };
+                assert_eq!(res.is_some(), brute(n, &edges, true, circuit));
+                if let Some(res) = res {
+                    check(&edges, &res, true, circuit);
+                }
+            }
+        }
+    }
+}
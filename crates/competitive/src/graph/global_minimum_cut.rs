@@ -0,0 +1,105 @@
+use super::{UndirectedSparseGraph, Zero};
+use std::ops::{Add, AddAssign};
+
+impl UndirectedSparseGraph {
+    /// Stoer-Wagner algorithm in O(n^3)
+    ///
+    /// Return (minimum cut weight, one side of the cut), `None` if less than two vertices.
+    pub fn global_minimum_cut<C>(&self, weight: &[C]) -> Option<(C, Vec<bool>)>
+    where
+        C: Copy + Zero + Ord + Add<Output = C> + AddAssign,
+    {
+        let n = self.vertices_size();
+        if n < 2 {
+            return None;
+        }
+        let mut w = vec![vec![C::zero(); n]; n];
+        for (&(u, v), &c) in self.edges.iter().zip(weight) {
+            if u != v {
+                w[u][v] += c;
+                w[v][u] += c;
+            }
+        }
+        // groups[u]: original vertices merged into u
+        let mut groups: Vec<Vec<usize>> = (0..n).map(|u| vec![u]).collect();
+        let mut alive: Vec<usize> = (0..n).collect();
+        let mut best: Option<(C, Vec<usize>)> = None;
+        while alive.len() > 1 {
+            let k = alive.len();
+            let mut key = vec![C::zero(); k];
+            let mut added = vec![false; k];
+            let (mut prev, mut last) = (0, 0);
+            for _ in 0..k {
+                let i = (0..k)
+                    .filter(|&i| !added[i])
+                    .max_by_key(|&i| key[i])
+                    .unwrap();
+                added[i] = true;
+                prev = last;
+                last = i;
+                for j in 0..k {
+                    if !added[j] {
+                        key[j] += w[alive[i]][alive[j]];
+                    }
+                }
+            }
+            if best.as_ref().is_none_or(|(b, _)| key[last] < *b) {
+                best = Some((key[last], groups[alive[last]].clone()));
+            }
+            let (s, t) = (alive[prev], alive[last]);
+            let g = std::mem::take(&mut groups[t]);
+            groups[s].extend(g);
+            for &x in &alive {
+                let c = w[t][x];
+                w[s][x] += c;
+                w[x][s] += c;
+            }
+            w[s][s] = C::zero();
+            alive.swap_remove(last);
+        }
+        best.map(|(c, group)| {
+            let mut cut = vec![false; n];
+            for u in group {
+                cut[u] = true;
+            }
+            (c, cut)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_global_minimum_cut() {
+        let mut rng = Xorshift::default();
+        for _ in 0..300 {
+            let n = rng.random(1usize..=10);
+            let m = rng.random(0..=n * 3);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.random(0..n), rng.random(0..n)))
+                .collect();
+            let weight: Vec<u64> = (0..m).map(|_| rng.random(0..100)).collect();
+            let graph = UndirectedSparseGraph::from_edges(n, edges.clone());
+            let cut_weight = |cut: &[bool]| -> u64 {
+                edges
+                    .iter()
+                    .zip(&weight)
+                    .filter(|&(&(u, v), _)| cut[u] != cut[v])
+                    .map(|(_, &c)| c)
+                    .sum()
+            };
+            let expected = (1..(1usize << n) - 1)
+                .map(|s| cut_weight(&(0..n).map(|i| s >> i & 1 == 1).collect::<Vec<_>>()))
+                .min();
+            let result = graph.global_minimum_cut(&weight);
+            assert_eq!(result.as_ref().map(|r| r.0), expected);
+            if let Some((c, cut)) = result {
+                assert!(cut.iter().any(|&b| b) && cut.iter().any(|&b| !b));
+                assert_eq!(cut_weight(&cut), c);
+            }
+        }
+    }
+}
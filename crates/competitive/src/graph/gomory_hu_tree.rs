@@ -0,0 +1,95 @@
+use super::{Bounded, Dinic, UndirectedSparseGraph, Zero};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+impl UndirectedSparseGraph {
+    /// Gomory-Hu tree by Gusfield's algorithm with n-1 maximum flows
+    ///
+    /// Return (tree, edge weights), where the minimum cut between `u` and `v` equals the
+    /// minimum weight on the tree path. Different connected components are joined by edges of
+    /// weight zero.
+    pub fn gomory_hu_tree<C>(&self, capacity: &[C]) -> (UndirectedSparseGraph, Vec<C>)
+    where
+        C: Copy + Zero + Ord + Bounded + Add<Output = C> + Sub<Output = C> + AddAssign + SubAssign,
+    {
+        let n = self.vertices_size();
+        let mut builder = Dinic::builder(n, self.edges_size() * 2);
+        for (&(u, v), &c) in self.edges.iter().zip(capacity) {
+            builder.add_edge(u, v, c);
+            builder.add_edge(v, u, c);
+        }
+        let graph = builder.gen_graph();
+        let mut dinic = builder.build(&graph);
+        let mut parent = vec![0; n];
+        let mut edges = Vec::with_capacity(n.saturating_sub(1));
+        let mut weight = Vec::with_capacity(n.saturating_sub(1));
+        for s in 1..n {
+            let t = parent[s];
+            // an undirected edge is a pair of opposing directed edges
+            for (eid, &c) in capacity.iter().enumerate() {
+                dinic.change_edge(eid * 2, c, C::zero());
+                dinic.change_edge(eid * 2 + 1, c, C::zero());
+            }
+            let flow = dinic.maximum_flow(s, t);
+            let cut = dinic.minimum_cut(s);
+            for u in s + 1..n {
+                if cut[u] && parent[u] == t {
+                    parent[u] = s;
+                }
+            }
+            edges.push((s, t));
+            weight.push(flow);
+        }
+        (UndirectedSparseGraph::from_edges(n, edges), weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::MinOperation, tools::Xorshift, tree::HeavyLightDecomposition};
+
+    #[test]
+    fn test_gomory_hu_tree() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let n = rng.random(1usize..=15);
+            let m = rng.random(0..=n * 3);
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (rng.random(0..n), rng.random(0..n)))
+                .collect();
+            let capacity: Vec<i64> = (0..m).map(|_| rng.random(0..100)).collect();
+            let graph = UndirectedSparseGraph::from_edges(n, edges.clone());
+            let (tree, weight) = graph.gomory_hu_tree(&capacity);
+            assert_eq!(tree.edges_size(), n - 1);
+
+            let hld = HeavyLightDecomposition::new(0, &tree);
+            let mut value = vec![i64::MAX; n];
+            for (&(u, v), &w) in tree.edges.iter().zip(&weight) {
+                let child = if hld.parent(u) == Some(v) { u } else { v };
+                value[hld.index(child)] = w;
+            }
+            let fold = |l: usize, r: usize| value[l..r].iter().copied().min().unwrap_or(i64::MAX);
+            for s in 0..n {
+                for t in s + 1..n {
+                    let mut builder = Dinic::builder(n, m * 2);
+                    for (&(u, v), &c) in edges.iter().zip(&capacity) {
+                        builder.add_edge(u, v, c);
+                        builder.add_edge(v, u, c);
+                    }
+                    let g = builder.gen_graph();
+                    let expected = builder.build(&g).maximum_flow(s, t);
+                    let result = hld.fold_edges::<MinOperation<i64>, _, _>(s, t, fold, fold);
+                    assert_eq!(result, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gomory_hu_tree_large_capacity() {
+        let graph = UndirectedSparseGraph::from_edges(3, vec![(0, 1), (1, 2)]);
+        let (tree, weight) = graph.gomory_hu_tree(&[i64::MAX, i64::MAX]);
+        assert_eq!(tree.edges, vec![(1, 0), (2, 1)]);
+        assert_eq!(weight, vec![i64::MAX, i64::MAX]);
+    }
+}
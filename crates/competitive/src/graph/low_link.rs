@@ -6,6 +6,10 @@ pub struct LowLink<'a> {
     pub ord: Vec<usize>,
     pub articulation: Vec<usize>,
     pub bridge: Vec<(usize, usize)>,
+    /// parent in the DFS tree, `usize::MAX` for roots
+    parent: Vec<usize>,
+    /// vertices in DFS preorder
+    order: Vec<usize>,
 }
 impl<'a> LowLink<'a> {
     pub fn new(graph: &'a UndirectedSparseGraph) -> Self {
@@ -15,36 +19,255 @@ impl<'a> LowLink<'a> {
             ord: vec![usize::MAX; graph.vertices_size()],
             articulation: vec![],
             bridge: vec![],
+            parent: vec![usize::MAX; graph.vertices_size()],
+            order: Vec::with_capacity(graph.vertices_size()),
         };
         for u in graph.vertices() {
             if self_.ord[u] == usize::MAX {
-                self_.dfs(u, graph.vertices_size(), &mut 0);
+                self_.dfs(u, usize::MAX);
             }
         }
         self_
     }
-    fn dfs(&mut self, u: usize, p: usize, now_ord: &mut usize) {
-        self.low[u] = *now_ord;
-        self.ord[u] = *now_ord;
-        *now_ord += 1;
+    /// pe: index of the edge to the parent, parallel edges are not skipped
+    fn dfs(&mut self, u: usize, pe: usize) {
+        self.low[u] = self.order.len();
+        self.ord[u] = self.order.len();
+        self.order.push(u);
         let mut is_articulation = false;
         let mut cnt = 0;
         for a in self.graph.adjacencies(u) {
             if self.ord[a.to] == usize::MAX {
                 cnt += 1;
-                self.dfs(a.to, u, now_ord);
+                self.parent[a.to] = u;
+                self.dfs(a.to, a.id);
                 self.low[u] = self.low[u].min(self.low[a.to]);
-                is_articulation |= p < self.graph.vertices_size() && self.ord[u] <= self.low[a.to];
+                is_articulation |= pe != usize::MAX && self.ord[u] <= self.low[a.to];
                 if self.ord[u] < self.low[a.to] {
                     self.bridge.push((u.min(a.to), u.max(a.to)));
                 }
-            } else if a.to != p {
+            } else if a.id != pe {
                 self.low[u] = self.low[u].min(self.ord[a.to]);
             }
         }
-        is_articulation |= p == self.graph.vertices_size() && cnt > 1;
+        is_articulation |= pe == usize::MAX && cnt > 1;
         if is_articulation {
             self.articulation.push(u);
         }
     }
+    fn is_bridge_to_parent(&self, v: usize) -> bool {
+        let p = self.parent[v];
+        p == usize::MAX || self.ord[p] < self.low[v]
+    }
+    /// Return (number of components, component index of each vertex)
+    pub fn two_edge_connected_component_ids(&self) -> (usize, Vec<usize>) {
+        let mut comp = vec![0; self.graph.vertices_size()];
+        let mut csize = 0;
+        for &v in &self.order {
+            if self.is_bridge_to_parent(v) {
+                comp[v] = csize;
+                csize += 1;
+            } else {
+                comp[v] = comp[self.parent[v]];
+            }
+        }
+        (csize, comp)
+    }
+    pub fn two_edge_connected_components(&self) -> Vec<Vec<usize>> {
+        let (csize, comp) = self.two_edge_connected_component_ids();
+        let mut groups = vec![vec![]; csize];
+        for u in self.graph.vertices() {
+            groups[comp[u]].push(u);
+        }
+        groups
+    }
+    /// Contract each 2-edge-connected component into a vertex.
+    ///
+    /// Return (forest whose edges are the bridges, component index of each vertex)
+    pub fn bridge_tree(&self) -> (UndirectedSparseGraph, Vec<usize>) {
+        let (csize, comp) = self.two_edge_connected_component_ids();
+        let edges = self
+            .order
+            .iter()
+            .filter(|&&v| self.parent[v] != usize::MAX && self.is_bridge_to_parent(v))
+            .map(|&v| (comp[self.parent[v]], comp[v]))
+            .collect();
+        (UndirectedSparseGraph::from_edges(csize, edges), comp)
+    }
+    /// Vertex sets of biconnected components, an isolated vertex forms a component by itself
+    pub fn biconnected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.graph.vertices_size();
+        // block[v]: the component containing v and its parent
+        let mut block = vec![usize::MAX; n];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for &v in &self.order {
+            let p = self.parent[v];
+            if p == usize::MAX {
+                if self.graph.adjacencies(v).all(|a| a.to == v) {
+                    groups.push(vec![v]);
+                }
+            } else if self.ord[p] <= self.low[v] {
+                block[v] = groups.len();
+                groups.push(vec![p, v]);
+            } else {
+                block[v] = block[p];
+                groups[block[p]].push(v);
+            }
+        }
+        groups
+    }
+    /// Bipartite forest of original vertices `0..n` and components `n..n+k`,
+    /// where the `i`-th component of [`LowLink::biconnected_components`] is the vertex `n+i`.
+    pub fn block_cut_tree(&self) -> UndirectedSparseGraph {
+        let n = self.graph.vertices_size();
+        let groups = self.biconnected_components();
+        let edges = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(i, g)| g.iter().map(move |&v| (n + i, v)))
+            .collect();
+        UndirectedSparseGraph::from_edges(n + groups.len(), edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_structure::UnionFind, tools::Xorshift};
+
+    fn random_graph(rng: &mut Xorshift) -> (usize, Vec<(usize, usize)>) {
+        let n = rng.random(1usize..=20);
+        let m = rng.random(0..=n * 2);
+        let edges = (0..m)
+            .map(|_| (rng.random(0..n), rng.random(0..n)))
+            .collect();
+        (n, edges)
+    }
+
+    fn count_components(n: usize, edges: impl Iterator<Item = (usize, usize)>) -> usize {
+        let mut uf = UnionFind::new(n);
+        let mut c = n;
+        for (u, v) in edges {
+            if uf.unite(u, v) {
+                c -= 1;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_two_edge_connected_components() {
+        let mut rng = Xorshift::default();
+        for _ in 0..500 {
+            let (n, edges) = random_graph(&mut rng);
+            let graph = UndirectedSparseGraph::from_edges(n, edges.clone());
+            let lowlink = LowLink::new(&graph);
+            let base = count_components(n, edges.iter().cloned());
+            let is_bridge: Vec<bool> = (0..edges.len())
+                .map(|i| {
+                    let c = count_components(
+                        n,
+                        edges
+                            .iter()
+                            .enumerate()
+                            .filter(|&(j, _)| j != i)
+                            .map(|(_, &e)| e),
+                    );
+                    c > base
+                })
+                .collect();
+            let mut expected: Vec<_> = edges
+                .iter()
+                .zip(&is_bridge)
+                .filter(|&(_, &b)| b)
+                .map(|(&(u, v), _)| (u.min(v), u.max(v)))
+                .collect();
+            let mut bridge = lowlink.bridge.clone();
+            expected.sort_unstable();
+            bridge.sort_unstable();
+            assert_eq!(bridge, expected);
+
+            let (csize, comp) = lowlink.two_edge_connected_component_ids();
+            let mut uf = UnionFind::new(n);
+            for (&(u, v), &b) in edges.iter().zip(&is_bridge) {
+                if !b {
+                    uf.unite(u, v);
+                }
+            }
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(uf.same(u, v), comp[u] == comp[v]);
+                }
+            }
+            assert_eq!(lowlink.two_edge_connected_components().len(), csize);
+
+            let (tree, tcomp) = lowlink.bridge_tree();
+            assert_eq!(tcomp, comp);
+            assert_eq!(tree.vertices_size(), csize);
+            assert_eq!(tree.edges_size(), bridge.len());
+            assert_eq!(count_components(csize, tree.edges.iter().cloned()), base);
+        }
+    }
+
+    #[test]
+    fn test_biconnected_components() {
+        let mut rng = Xorshift::default();
+        for _ in 0..500 {
+            let (n, edges) = random_graph(&mut rng);
+            let graph = UndirectedSparseGraph::from_edges(n, edges.clone());
+            let lowlink = LowLink::new(&graph);
+            let groups = lowlink.biconnected_components();
+
+            // every vertex appears in (1 + [articulation]) .. components, isolated ones once
+            let mut cnt = vec![0; n];
+            for g in &groups {
+                for &v in g {
+                    cnt[v] += 1;
+                }
+            }
+            let mut articulation = vec![false; n];
+            for &v in &lowlink.articulation {
+                articulation[v] = true;
+            }
+            for v in 0..n {
+                assert!(cnt[v] >= 1);
+                assert_eq!(cnt[v] > 1, articulation[v]);
+            }
+
+            // every non-loop edge lies in exactly one component, and each component is
+            // connected without any single vertex
+            let mut id = vec![vec![]; n];
+            for (i, g) in groups.iter().enumerate() {
+                for &v in g {
+                    id[v].push(i);
+                }
+            }
+            for &(u, v) in &edges {
+                if u != v {
+                    let common = id[u].iter().filter(|i| id[v].contains(i)).count();
+                    assert_eq!(common, 1);
+                }
+            }
+            for g in &groups {
+                if g.len() <= 2 {
+                    continue;
+                }
+                for &x in g {
+                    let sub: Vec<_> = g.iter().copied().filter(|&v| v != x).collect();
+                    let inner = edges
+                        .iter()
+                        .filter(|&&(u, v)| sub.contains(&u) && sub.contains(&v))
+                        .cloned();
+                    assert_eq!(count_components(n, inner), n - sub.len() + 1);
+                }
+            }
+
+            let tree = lowlink.block_cut_tree();
+            assert_eq!(tree.vertices_size(), n + groups.len());
+            let tree_components =
+                count_components(tree.vertices_size(), tree.edges.iter().cloned());
+            assert_eq!(tree_components, count_components(n, edges.iter().cloned()));
+            assert_eq!(tree.edges_size() + tree_components, tree.vertices_size());
+        }
+    }
 }
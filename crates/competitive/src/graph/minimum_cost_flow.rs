@@ -1,14 +1,23 @@
-use super::BidirectionalSparseGraph;
+use super::{BidirectionalSparseGraph, Bounded, Zero};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
 
 #[derive(Debug, Clone)]
-pub struct PrimalDualBuilder {
+pub struct PrimalDualBuilder<C, D> {
     vsize: usize,
     edges: Vec<(usize, usize)>,
-    capacities: Vec<u64>,
-    costs: Vec<i64>,
+    capacities: Vec<C>,
+    costs: Vec<D>,
     has_negedge: bool,
 }
-impl PrimalDualBuilder {
+impl<C, D> PrimalDualBuilder<C, D>
+where
+    C: Copy + Zero + PartialOrd,
+    D: Copy + Zero + PartialOrd + Neg<Output = D>,
+{
     pub fn new(vsize: usize, esize_expect: usize) -> Self {
         Self {
             vsize,
@@ -18,11 +27,12 @@ impl PrimalDualBuilder {
             has_negedge: false,
         }
     }
-    pub fn add_edge(&mut self, from: usize, to: usize, cap: u64, cost: i64) {
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: C, cost: D) {
+        assert!(cap >= C::zero());
         self.edges.push((from, to));
         self.capacities.push(cap);
-        self.capacities.push(0);
-        self.has_negedge |= cost < 0;
+        self.capacities.push(C::zero());
+        self.has_negedge |= cost < D::zero();
         self.costs.push(cost);
         self.costs.push(-cost);
     }
@@ -30,7 +40,7 @@ impl PrimalDualBuilder {
         let edges = std::mem::take(&mut self.edges);
         BidirectionalSparseGraph::from_edges(self.vsize, edges)
     }
-    pub fn build(self, graph: &BidirectionalSparseGraph) -> PrimalDual<'_> {
+    pub fn build(self, graph: &BidirectionalSparseGraph) -> PrimalDual<'_, C, D> {
         let PrimalDualBuilder {
             vsize,
             capacities,
@@ -42,117 +52,321 @@ impl PrimalDualBuilder {
             graph,
             capacities,
             costs,
-            potential: std::iter::repeat_n(0, vsize).collect(),
+            potential: vec![D::zero(); vsize],
             dist: Vec::with_capacity(vsize),
-            prev_vertex: std::iter::repeat_n(0, vsize).collect(),
-            prev_edge: std::iter::repeat_n(0, vsize).collect(),
+            visited: Vec::with_capacity(vsize),
+            prev_vertex: vec![0; vsize],
+            prev_edge: vec![0; vsize],
             has_negedge,
         }
     }
 }
-impl Extend<(usize, usize, u64, i64)> for PrimalDualBuilder {
-    fn extend<T: IntoIterator<Item = (usize, usize, u64, i64)>>(&mut self, iter: T) {
+impl<C, D> Extend<(usize, usize, C, D)> for PrimalDualBuilder<C, D>
+where
+    C: Copy + Zero + PartialOrd,
+    D: Copy + Zero + PartialOrd + Neg<Output = D>,
+{
+    fn extend<T: IntoIterator<Item = (usize, usize, C, D)>>(&mut self, iter: T) {
         for (from, to, cap, cost) in iter {
             self.add_edge(from, to, cap, cost)
         }
     }
 }
 
+/// Minimum cost flow by successive shortest paths with potentials.
+///
+/// Negative costs are allowed unless the graph has a negative cycle.
 #[derive(Debug)]
-pub struct PrimalDual<'a> {
+pub struct PrimalDual<'a, C, D> {
     graph: &'a BidirectionalSparseGraph,
-    capacities: Vec<u64>,
-    costs: Vec<i64>,
-    potential: Vec<i64>,
-    dist: Vec<i64>,
+    capacities: Vec<C>,
+    costs: Vec<D>,
+    potential: Vec<D>,
+    dist: Vec<D>,
+    visited: Vec<bool>,
     prev_vertex: Vec<usize>,
     prev_edge: Vec<usize>,
     has_negedge: bool,
 }
-impl PrimalDual<'_> {
-    pub fn builder(vsize: usize, esize_expect: usize) -> PrimalDualBuilder {
+impl<C, D> PrimalDual<'_, C, D>
+where
+    C: Copy + Zero + Ord + Bounded + Add<Output = C> + Sub<Output = C> + AddAssign + SubAssign,
+    D: Copy
+        + Zero
+        + Ord
+        + Bounded
+        + Add<Output = D>
+        + Sub<Output = D>
+        + AddAssign
+        + Neg<Output = D>
+        + Mul<Output = D>
+        + TryFrom<C>,
+{
+    pub fn builder(vsize: usize, esize_expect: usize) -> PrimalDualBuilder<C, D> {
         PrimalDualBuilder::new(vsize, esize_expect)
     }
-    fn bellman_ford(&mut self, s: usize) {
+    /// Initialize potentials with shortest distances from `s`, unreachable vertices are zero.
+    fn initial_potential(&mut self, s: usize) {
+        let n = self.graph.vertices_size();
         self.potential.clear();
-        self.potential.resize(self.graph.vertices_size(), i64::MAX);
-        self.potential[s] = 0;
-        for _ in 1..self.graph.vertices_size() {
-            let mut end = true;
-            for u in self.graph.vertices() {
+        self.potential.resize(n, D::maximum());
+        self.potential[s] = D::zero();
+        if let Some(order) = self.residual_topological_order() {
+            // DAG: relax in topological order
+            for u in order {
+                if self.potential[u] == D::maximum() {
+                    continue;
+                }
                 for a in self.graph.adjacencies(u) {
-                    let ncost = self.potential[u].saturating_add(self.costs[a.id]);
-                    if self.capacities[a.id] > 0 && self.potential[a.to] > ncost {
-                        self.potential[a.to] = ncost;
-                        end = false;
+                    if self.capacities[a.id] > C::zero() {
+                        let ncost = self.potential[u] + self.costs[a.id];
+                        if self.potential[a.to] > ncost {
+                            self.potential[a.to] = ncost;
+                        }
                     }
                 }
             }
-            if end {
-                break;
+        } else {
+            // Bellman-Ford
+            let mut updated = true;
+            for _ in 0..n {
+                if !updated {
+                    break;
+                }
+                updated = false;
+                for u in self.graph.vertices() {
+                    if self.potential[u] == D::maximum() {
+                        continue;
+                    }
+                    for a in self.graph.adjacencies(u) {
+                        if self.capacities[a.id] > C::zero() {
+                            let ncost = self.potential[u] + self.costs[a.id];
+                            if self.potential[a.to] > ncost {
+                                self.potential[a.to] = ncost;
+                                updated = true;
+                            }
+                        }
+                    }
+                }
+            }
+            assert!(!updated, "negative cycle is reachable from the source");
+        }
+        for p in self.potential.iter_mut() {
+            if *p == D::maximum() {
+                *p = D::zero();
             }
         }
     }
+    fn residual_topological_order(&self) -> Option<Vec<usize>> {
+        let n = self.graph.vertices_size();
+        let mut indeg = vec![0usize; n];
+        for u in self.graph.vertices() {
+            for a in self.graph.adjacencies(u) {
+                if self.capacities[a.id] > C::zero() {
+                    indeg[a.to] += 1;
+                }
+            }
+        }
+        let mut order: Vec<usize> = self.graph.vertices().filter(|&u| indeg[u] == 0).collect();
+        let mut i = 0;
+        while i < order.len() {
+            let u = order[i];
+            i += 1;
+            for a in self.graph.adjacencies(u) {
+                if self.capacities[a.id] > C::zero() {
+                    indeg[a.to] -= 1;
+                    if indeg[a.to] == 0 {
+                        order.push(a.to);
+                    }
+                }
+            }
+        }
+        (order.len() == n).then_some(order)
+    }
     fn dijkstra(&mut self, s: usize, t: usize) -> bool {
-        use std::{cmp::Reverse, collections::BinaryHeap};
+        let n = self.graph.vertices_size();
         self.dist.clear();
-        self.dist.resize(self.graph.vertices_size(), i64::MAX);
-        self.dist[s] = 0;
+        self.dist.resize(n, D::maximum());
+        self.dist[s] = D::zero();
+        self.visited.clear();
+        self.visited.resize(n, false);
         let mut heap = BinaryHeap::new();
-        heap.push((Reverse(0), s));
+        heap.push((Reverse(D::zero()), s));
         while let Some((Reverse(d), u)) = heap.pop() {
-            if self.dist[u] < d {
+            if self.visited[u] {
                 continue;
             }
-            if !self.has_negedge && u == t {
+            self.visited[u] = true;
+            if u == t {
                 break;
             }
             for a in self.graph.adjacencies(u) {
-                let ncost = (self.dist[u].saturating_add(self.costs[a.id]))
-                    .saturating_add(self.potential[u].saturating_sub(self.potential[a.to]));
-                if self.capacities[a.id] > 0 && self.dist[a.to] > ncost {
-                    self.dist[a.to] = ncost;
-                    self.prev_vertex[a.to] = u;
-                    self.prev_edge[a.to] = a.id;
-                    heap.push((Reverse(ncost), a.to));
+                if self.capacities[a.id] > C::zero() && !self.visited[a.to] {
+                    let ncost = d + self.costs[a.id] + self.potential[u] - self.potential[a.to];
+                    if self.dist[a.to] > ncost {
+                        self.dist[a.to] = ncost;
+                        self.prev_vertex[a.to] = u;
+                        self.prev_edge[a.to] = a.id;
+                        heap.push((Reverse(ncost), a.to));
+                    }
                 }
             }
         }
-        self.dist[t] != i64::MAX
+        if !self.visited[t] {
+            return false;
+        }
+        // vertices not settled before `t` keep reduced costs non-negative with `dist[t]`
+        let dt = self.dist[t];
+        for ((p, &d), &v) in self.potential.iter_mut().zip(&self.dist).zip(&self.visited) {
+            *p += if v { d } else { dt };
+        }
+        true
     }
-    /// Return (flow, cost).
-    pub fn minimum_cost_flow_limited(&mut self, s: usize, t: usize, limit: u64) -> (u64, i64) {
-        let mut flow = 0;
-        let mut cost = 0;
+    /// Return the breakpoints (flow, cost) of the piecewise linear convex function
+    /// from flow to minimum cost, starting with (0, 0).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a negative cycle is reachable from `s`, or the flow does not fit the cost type.
+    pub fn slope_limited(&mut self, s: usize, t: usize, limit: C) -> Vec<(C, D)> {
+        let mut flow = C::zero();
+        let mut cost = D::zero();
+        let mut slope = vec![(flow, cost)];
+        let mut prev_unit: Option<D> = None;
         if self.has_negedge {
-            self.bellman_ford(s);
+            self.initial_potential(s);
         }
         while flow < limit && self.dijkstra(s, t) {
-            for (p, d) in self.potential.iter_mut().zip(self.dist.iter()) {
-                *p = p.saturating_add(*d);
-            }
             let mut f = limit - flow;
             let mut v = t;
             while v != s {
                 f = f.min(self.capacities[self.prev_edge[v]]);
                 v = self.prev_vertex[v];
             }
-            flow += f;
-            cost += f as i64 * self.potential[t];
             let mut v = t;
             while v != s {
                 self.capacities[self.prev_edge[v]] -= f;
                 self.capacities[self.prev_edge[v] ^ 1] += f;
                 v = self.prev_vertex[v];
             }
+            let unit = self.potential[t] - self.potential[s];
+            flow += f;
+            cost += unit * D::try_from(f).ok().expect("flow must fit the cost type");
+            if prev_unit == Some(unit) {
+                slope.pop();
+            }
+            slope.push((flow, cost));
+            prev_unit = Some(unit);
         }
-        (flow, cost)
+        slope
+    }
+    pub fn slope(&mut self, s: usize, t: usize) -> Vec<(C, D)> {
+        self.slope_limited(s, t, C::maximum())
+    }
+    /// Return (flow, cost).
+    pub fn minimum_cost_flow_limited(&mut self, s: usize, t: usize, limit: C) -> (C, D) {
+        *self.slope_limited(s, t, limit).last().unwrap()
     }
     /// Return (flow, cost).
-    pub fn minimum_cost_flow(&mut self, s: usize, t: usize) -> (u64, i64) {
-        self.minimum_cost_flow_limited(s, t, u64::MAX)
+    pub fn minimum_cost_flow(&mut self, s: usize, t: usize) -> (C, D) {
+        self.minimum_cost_flow_limited(s, t, C::maximum())
     }
-    pub fn get_flow(&self, eid: usize) -> u64 {
+    pub fn get_flow(&self, eid: usize) -> C {
         self.capacities[eid * 2 + 1]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::NetworkSimplex, tools::Xorshift};
+
+    #[test]
+    fn test_primal_dual() {
+        let mut rng = Xorshift::default();
+        for _ in 0..500 {
+            let n = rng.random(2usize..=8);
+            let m = rng.random(0..=n * 3);
+            // costs are shifted by random potentials to make negative edges without negative cycles
+            let h: Vec<i64> = (0..n).map(|_| rng.random(-10..=10)).collect();
+            let dag = rng.gen_bool(0.5);
+            let edges: Vec<(usize, usize, i64, i64)> = (0..m)
+                .map(|_| {
+                    let (mut u, mut v) = (rng.random(0..n), rng.random(0..n));
+                    if dag && u > v {
+                        std::mem::swap(&mut u, &mut v);
+                    }
+                    (u, v, rng.random(0..=5), rng.random(0i64..=10) + h[u] - h[v])
+                })
+                .collect();
+            let (s, t) = (0, n - 1);
+            let mut builder = PrimalDualBuilder::new(n, m);
+            builder.extend(edges.iter().cloned());
+            let graph = builder.gen_graph();
+            let mut pd = builder.build(&graph);
+            let slope = pd.slope(s, t);
+            let max_flow = slope.last().unwrap().0;
+
+            let mut builder = PrimalDualBuilder::<u64, i64>::new(n, m);
+            builder.extend(
+                edges
+                    .iter()
+                    .map(|&(u, v, cap, cost)| (u, v, cap as u64, cost)),
+            );
+            let graph = builder.gen_graph();
+            let (f, c) = builder.build(&graph).minimum_cost_flow(s, t);
+            assert_eq!((f as i64, c), *slope.last().unwrap());
+
+            for (i, &(u, v, cap, _)) in edges.iter().enumerate() {
+                let f = pd.get_flow(i);
+                assert!(0 <= f && f <= cap, "{u} {v}");
+            }
+            for w in slope.windows(3) {
+                let d1 = (w[1].1 - w[0].1) * (w[2].0 - w[1].0);
+                let d2 = (w[2].1 - w[1].1) * (w[1].0 - w[0].0);
+                assert!(d1 < d2);
+            }
+            // compare with min cost flow of every amount
+            for flow in 0..=max_flow + 1 {
+                let mut ns = NetworkSimplex::<i64, i64>::new(n);
+                for &(u, v, cap, cost) in &edges {
+                    ns.add_edge(u, v, 0, cap, cost);
+                }
+                ns.add_supply(s, flow);
+                ns.add_demand(t, flow);
+                let expected = ns.solve_minimize().map(|sol| sol.cost);
+                let result = if flow <= max_flow {
+                    let i = slope.partition_point(|&(f, _)| f < flow);
+                    Some(if slope[i].0 == flow {
+                        slope[i].1
+                    } else {
+                        let (f0, c0) = slope[i - 1];
+                        let (f1, c1) = slope[i];
+                        c0 + (c1 - c0) / (f1 - f0) * (flow - f0)
+                    })
+                } else {
+                    None
+                };
+                assert_eq!(result, expected);
+
+                let mut builder = PrimalDualBuilder::new(n, m);
+                builder.extend(edges.iter().cloned());
+                let graph = builder.gen_graph();
+                let (f, c) = builder.build(&graph).minimum_cost_flow_limited(s, t, flow);
+                assert_eq!(f, flow.min(max_flow));
+                if let Some(e) = expected {
+                    assert_eq!(c, e);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "negative cycle")]
+    fn test_primal_dual_negative_cycle() {
+        let mut builder = PrimalDualBuilder::<u32, i32>::new(3, 3);
+        builder.extend([(0, 1, 1, 1), (1, 2, 1, -2), (2, 1, 1, 1)]);
+        let graph = builder.gen_graph();
+        builder.build(&graph).minimum_cost_flow(0, 2);
+    }
+}
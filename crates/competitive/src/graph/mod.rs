@@ -3,7 +3,7 @@
 use crate::{
     algebra::{AddMulOperation, AdditiveOperation, DotProduct, Group, Monoid, MonoidAct, SemiRing},
     algorithm::BitDpExt,
-    data_structure::{MergingUnionFind, PairingHeap, UnionFind},
+    data_structure::{MergingUnionFind, PairingHeap, RadixHeap, RadixHeapKey, UnionFind},
     num::{Bounded, One, Zero},
     tools::{IterScan, MarkedIterScan, PartialIgnoredOrd, comparator},
 };
@@ -16,8 +16,12 @@ pub use self::assignment::minimum_assignment;
 pub use self::bipartite_matching::BipartiteMatching;
 #[codesnip::entry("ClosureGraph")]
 pub use self::closure::{ClosureGraph, UsizeGraph};
+#[codesnip::entry("DominatorTree")]
+pub use self::dominator_tree::{DominatorTree, DominatorTreeExt};
 #[codesnip::entry("dulmage_mendelsohn_decomposition")]
 pub use self::dulmage_mendelsohn_decomposition::dulmage_mendelsohn_decomposition;
+#[codesnip::entry("DynamicConnectivity")]
+pub use self::dynamic_connectivity::DynamicConnectivity;
 #[codesnip::entry("EdgeListGraph")]
 pub use self::edge_list::{EdgeListGraph, EdgeListGraphScanner};
 #[codesnip::entry("GeneralMatching")]
@@ -49,7 +53,10 @@ pub use self::strongly_connected_component::StronglyConnectedComponent;
 #[codesnip::entry("TwoSatisfiability")]
 pub use self::two_satisfiability::TwoSatisfiability;
 
-#[cfg_attr(nightly, codesnip::entry("AdjacencyListGraph", include("scanner")))]
+#[cfg_attr(
+    nightly,
+    codesnip::entry("AdjacencyListGraph", include("scanner", "GraphBase"))
+)]
 mod adjacency_list;
 #[cfg_attr(nightly, codesnip::entry("minimum_assignment"))]
 mod assignment;
@@ -57,6 +64,8 @@ mod assignment;
 mod bipartite_matching;
 #[cfg_attr(nightly, codesnip::entry("ClosureGraph", include("GraphBase")))]
 mod closure;
+#[cfg_attr(nightly, codesnip::entry("DominatorTree", include("GraphBase")))]
+mod dominator_tree;
 #[cfg_attr(
     nightly,
     codesnip::entry(
@@ -65,12 +74,26 @@ mod closure;
     )
 )]
 mod dulmage_mendelsohn_decomposition;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("DynamicConnectivity", include("algebra", "EulerTourTree"))
+)]
+mod dynamic_connectivity;
 #[cfg_attr(nightly, codesnip::entry("EdgeListGraph", include("scanner")))]
 mod edge_list;
+#[cfg_attr(nightly, codesnip::entry("eulerian_trail", include("SparseGraph")))]
+mod eulerian_trail;
 #[cfg_attr(nightly, codesnip::entry("GeneralMatching"))]
 mod general_matching;
 #[cfg_attr(nightly, codesnip::entry("GeneralWeightedMatching"))]
 mod general_weighted_matching;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("global_minimum_cut", include("SparseGraph", "zero_one"))
+)]
+mod global_minimum_cut;
+#[cfg_attr(nightly, codesnip::entry("gomory_hu_tree", include("Dinic")))]
+mod gomory_hu_tree;
 #[cfg_attr(nightly, codesnip::entry("GraphBase"))]
 mod graph_base;
 #[cfg_attr(nightly, codesnip::entry("graphvis", include("SparseGraph")))]
@@ -84,7 +107,10 @@ mod low_link;
     codesnip::entry("Dinic", include("SparseGraph", "bounded", "zero_one"))
 )]
 mod maximum_flow;
-#[cfg_attr(nightly, codesnip::entry("PrimalDual", include("SparseGraph")))]
+#[cfg_attr(
+    nightly,
+    codesnip::entry("PrimalDual", include("SparseGraph", "bounded", "zero_one"))
+)]
 mod minimum_cost_flow;
 #[cfg_attr(
     nightly,
@@ -108,7 +134,7 @@ mod project_selection_problem;
     nightly,
     codesnip::entry(
         "shortest_path",
-        include("GraphBase", "ring", "PartialIgnoredOrd", "bounded")
+        include("GraphBase", "ring", "PartialIgnoredOrd", "bounded", "RadixHeap")
     )
 )]
 pub mod shortest_path;
@@ -140,6 +140,43 @@ where
     }
 }
 
+pub trait QueuePolicy<K, V> {
+    type Queue: Default;
+    fn push(queue: &mut Self::Queue, key: K, value: V);
+    fn pop(queue: &mut Self::Queue) -> Option<(K, V)>;
+}
+
+pub enum BinaryHeapQueue {}
+impl<K, V> QueuePolicy<K, V> for BinaryHeapQueue
+where
+    K: Ord,
+{
+    type Queue = BinaryHeap<PartialIgnoredOrd<Reverse<K>, V>>;
+    fn push(queue: &mut Self::Queue, key: K, value: V) {
+        queue.push(PartialIgnoredOrd(Reverse(key), value));
+    }
+    fn pop(queue: &mut Self::Queue) -> Option<(K, V)> {
+        queue
+            .pop()
+            .map(|PartialIgnoredOrd(Reverse(key), value)| (key, value))
+    }
+}
+
+/// Monotone integer keys only, e.g. non-negative integer weights
+pub enum RadixHeapQueue {}
+impl<K, V> QueuePolicy<K, V> for RadixHeapQueue
+where
+    K: RadixHeapKey,
+{
+    type Queue = RadixHeap<K, V>;
+    fn push(queue: &mut Self::Queue, key: K, value: V) {
+        queue.push(key, value);
+    }
+    fn pop(queue: &mut Self::Queue) -> Option<(K, V)> {
+        queue.pop()
+    }
+}
+
 pub struct ShortestPathWithParent<G, S, P = RecordParent>
 where
     G: GraphBase + VertexMap<S::T>,
@@ -180,6 +217,7 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -193,6 +231,7 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -204,6 +243,7 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -217,6 +257,7 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -229,6 +270,7 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -243,12 +285,13 @@ pub trait ShortestPathExt: GraphBase {
         ShortestPathBuilder {
             graph: self,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 }
 impl<G> ShortestPathExt for G where G: GraphBase {}
 
-pub struct ShortestPathBuilder<'a, G, S, P = NoParent>
+pub struct ShortestPathBuilder<'a, G, S, P = NoParent, Q = BinaryHeapQueue>
 where
     G: GraphBase,
     S: ShortestPathSemiRing,
@@ -256,14 +299,27 @@ where
 {
     graph: &'a G,
     _marker: PhantomData<fn() -> (S, P)>,
+    _queue: PhantomData<fn() -> Q>,
 }
 
-impl<'a, G, S, P> ShortestPathBuilder<'a, G, S, P>
+impl<'a, G, S, P, Q> ShortestPathBuilder<'a, G, S, P, Q>
 where
     G: GraphBase,
     S: ShortestPathSemiRing,
     P: ParentPolicy<G>,
 {
+    /// Use [`RadixHeap`] as the priority queue of Dijkstra's algorithm.
+    pub fn with_radix_heap(self) -> ShortestPathBuilder<'a, G, S, P, RadixHeapQueue>
+    where
+        S::T: RadixHeapKey,
+    {
+        ShortestPathBuilder {
+            graph: self.graph,
+            _marker: PhantomData,
+            _queue: PhantomData,
+        }
+    }
+
     fn bfs_distance_core<M, I>(&self, sources: I, weight: &'a M) -> ShortestPathWithParent<G, S, P>
     where
         G: VertexMap<S::T> + AdjacencyView<'a, M, S::T>,
@@ -300,16 +356,17 @@ where
     where
         G: VertexMap<S::T> + AdjacencyView<'a, M, S::T>,
         I: IntoIterator<Item = G::VIndex>,
+        Q: QueuePolicy<S::T, G::VIndex>,
     {
         let graph = self.graph;
         let mut dist = graph.construct_vmap(S::inf);
         let mut parent = P::init(graph);
-        let mut heap = BinaryHeap::new();
+        let mut heap = Q::Queue::default();
         for source in sources.into_iter() {
             *graph.vmap_get_mut(&mut dist, source) = S::source();
-            heap.push(PartialIgnoredOrd(Reverse(S::source()), source));
+            Q::push(&mut heap, S::source(), source);
         }
-        while let Some(PartialIgnoredOrd(Reverse(d), u)) = heap.pop() {
+        while let Some((d, u)) = Q::pop(&mut heap) {
             if graph.vmap_get(&dist, u) != &d {
                 continue;
             }
@@ -319,7 +376,7 @@ where
                 let nd = S::mul(&d, &a.avalue());
                 if S::add_assign(graph.vmap_get_mut(&mut dist, v), &nd) {
                     P::save_parent(graph, &mut parent, u, v);
-                    heap.push(PartialIgnoredOrd(Reverse(nd), v));
+                    Q::push(&mut heap, nd, v);
                 }
             }
         }
@@ -375,18 +432,19 @@ where
     }
 }
 
-impl<'a, G, S> ShortestPathBuilder<'a, G, S, NoParent>
+impl<'a, G, S, Q> ShortestPathBuilder<'a, G, S, NoParent, Q>
 where
     G: GraphBase,
     S: ShortestPathSemiRing,
 {
-    pub fn with_parent(self) -> ShortestPathBuilder<'a, G, S, RecordParent>
+    pub fn with_parent(self) -> ShortestPathBuilder<'a, G, S, RecordParent, Q>
     where
         G: VertexMap<Option<<G as GraphBase>::VIndex>>,
     {
         ShortestPathBuilder {
             graph: self.graph,
             _marker: PhantomData,
+            _queue: PhantomData,
         }
     }
 
@@ -402,6 +460,7 @@ where
     where
         G: VertexMap<S::T> + AdjacencyView<'a, M, S::T>,
         I: IntoIterator<Item = G::VIndex>,
+        Q: QueuePolicy<S::T, G::VIndex>,
     {
         self.dijkstra_core::<M, I>(sources, weight).dist
     }
@@ -457,7 +516,7 @@ where
     }
 }
 
-impl<'a, G, S> ShortestPathBuilder<'a, G, S, RecordParent>
+impl<'a, G, S, Q> ShortestPathBuilder<'a, G, S, RecordParent, Q>
 where
     G: GraphBase + VertexMap<Option<<G as GraphBase>::VIndex>>,
     S: ShortestPathSemiRing,
@@ -474,6 +533,7 @@ where
     where
         G: VertexMap<S::T> + AdjacencyView<'a, M, S::T>,
         I: IntoIterator<Item = G::VIndex>,
+        Q: QueuePolicy<S::T, G::VIndex>,
     {
         self.dijkstra_core::<M, I>(sources, weight)
     }
@@ -526,6 +586,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dijkstra_radix_heap() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            rand!(rng, n: 1..100, m: 1..200, edges: [(0..n, 0..n); m], w: [0..100_000u64; m]);
+            let g = DirectedSparseGraph::from_edges(n, edges);
+            for src in 0..n {
+                let binary_heap = g.standard_sp_additive().dijkstra([src], &|eid| w[eid]);
+                let radix_heap = g
+                    .standard_sp_additive()
+                    .with_radix_heap()
+                    .dijkstra([src], &|eid| w[eid]);
+                assert_eq!(binary_heap, radix_heap);
+                let with_parent = g
+                    .standard_sp_additive()
+                    .with_radix_heap()
+                    .with_parent()
+                    .dijkstra([src], &|eid| w[eid]);
+                assert_eq!(binary_heap, with_parent.dist);
+                for target in 0..n {
+                    if let Some(path) = with_parent.path_to(&g, target) {
+                        assert_eq!(path[0], src);
+                        assert_eq!(*path.last().unwrap(), target);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_spfa() {
         let mut rng = Xorshift::default();
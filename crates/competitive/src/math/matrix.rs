@@ -257,6 +257,39 @@ where
         determinant
     }
 
+    /// Determinant by fraction-free elimination (Bareiss algorithm).
+    ///
+    /// Every division is exact, so this works for integral domains such as integers.
+    pub fn determinant_bareiss(&mut self) -> R::T {
+        assert_eq!(self.shape.0, self.shape.1);
+        let n = self.shape.0;
+        let mut prev = R::one();
+        let mut neg = false;
+        for c in 0..n {
+            let Some(pivot) = (c..n).find(|&i| !R::is_zero(&self[i][c])) else {
+                return R::zero();
+            };
+            if c != pivot {
+                self.data.swap(c, pivot);
+                neg = !neg;
+            }
+            let (upper, lower) = self.data.split_at_mut(c + 1);
+            let pivot = &upper[c];
+            for target in lower {
+                for j in (c + 1)..n {
+                    let x = R::sub(
+                        &R::mul(&target[j], &pivot[c]),
+                        &R::mul(&target[c], &pivot[j]),
+                    );
+                    target[j] = R::div(&x, &prev);
+                }
+                target[c] = R::zero();
+            }
+            prev = self[c][c].clone();
+        }
+        if neg { R::neg(&prev) } else { prev }
+    }
+
     pub fn solve_system_of_linear_equations(
         &self,
         b: &[R::T],
@@ -1083,4 +1116,24 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_determinant_bareiss() {
+        use crate::num::BigInt;
+        let mut rng = Xorshift::default();
+        DynMIntU32::set_mod(1_000_000_007);
+        for _ in 0..100 {
+            let n = rng.random(0..=12);
+            let a: Vec<Vec<i64>> = rand_value!(rng, [[-1_000_000_000i64..=1_000_000_000; n]; n]);
+            let mut x =
+                Matrix::<AddMulOperation<BigInt>>::new_with((n, n), |i, j| BigInt::from(a[i][j]));
+            let y = Matrix::<R>::new_with((n, n), |i, j| DynMIntU32::from(a[i][j]));
+            let expected = y.clone().determinant();
+            let result = x
+                .determinant_bareiss()
+                .rem_euclid(&BigInt::from(1_000_000_007));
+            assert_eq!(DynMIntU32::from(result.to_i64().unwrap()), expected);
+            assert_eq!(y.clone().determinant_bareiss(), expected);
+        }
+    }
 }
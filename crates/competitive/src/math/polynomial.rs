@@ -47,7 +47,7 @@ mod polynomial_impls {
             &mut self.data[index]
         }
     }
-    impl<T: Copy + Add<Output = T>> Add<&Polynomial<T>> for &Polynomial<T> {
+    impl<T: Clone + Add<Output = T>> Add<&Polynomial<T>> for &Polynomial<T> {
         type Output = Polynomial<T>;
         fn add(self, rhs: &Polynomial<T>) -> Self::Output {
             let (x, y) = if self.length() < rhs.length() {
@@ -57,12 +57,12 @@ mod polynomial_impls {
             };
             let mut x = x.clone();
             for j in 0..y.length() {
-                x[j] = x[j] + y[j];
+                x[j] = x[j].clone() + y[j].clone();
             }
             x
         }
     }
-    impl<T: Copy + Sub<Output = T>> Sub<&Polynomial<T>> for &Polynomial<T> {
+    impl<T: Clone + Sub<Output = T>> Sub<&Polynomial<T>> for &Polynomial<T> {
         type Output = Polynomial<T>;
         fn sub(self, rhs: &Polynomial<T>) -> Self::Output {
             let (x, y) = if self.length() < rhs.length() {
@@ -72,25 +72,24 @@ mod polynomial_impls {
             };
             let mut x = x.clone();
             for j in 0..y.length() {
-                x[j] = x[j] - y[j];
+                x[j] = x[j].clone() - y[j].clone();
             }
             x
         }
     }
-    impl<T: Copy + Zero + Add<Output = T> + Mul<Output = T>> Mul<&Polynomial<T>> for &Polynomial<T> {
+    impl<T: Clone + Zero + Add<Output = T> + Mul<Output = T>> Mul<&Polynomial<T>> for &Polynomial<T> {
         type Output = Polynomial<T>;
         fn mul(self, rhs: &Polynomial<T>) -> Self::Output {
-            let mut res =
-                Polynomial::from_vec(vec![Zero::zero(); self.length() + rhs.length() - 1]);
+            let mut res = Polynomial::from_vec(vec![T::zero(); self.length() + rhs.length() - 1]);
             for i in 0..self.length() {
                 for j in 0..rhs.length() {
-                    res[i + j] = res[i + j] + self[i] * rhs[j];
+                    res[i + j] = res[i + j].clone() + self[i].clone() * rhs[j].clone();
                 }
             }
             res
         }
     }
-    impl<T: Copy + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Div<&Polynomial<T>>
+    impl<T: Clone + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Div<&Polynomial<T>>
         for &Polynomial<T>
     {
         type Output = Polynomial<T>;
@@ -98,33 +97,33 @@ mod polynomial_impls {
             let mut x = self.clone();
             let mut res = Polynomial::from_vec(vec![]);
             for i in (rhs.length() - 1..x.length()).rev() {
-                let t = x[i] / rhs[rhs.length() - 1];
-                res.data.push(t);
+                let t = x[i].clone() / rhs[rhs.length() - 1].clone();
+                res.data.push(t.clone());
                 for j in 0..rhs.length() {
-                    x[i - j] = x[i - j] - t * rhs[rhs.length() - 1 - j];
+                    x[i - j] = x[i - j].clone() - t.clone() * rhs[rhs.length() - 1 - j].clone();
                 }
             }
             res.data.reverse();
             res
         }
     }
-    impl<T: Copy + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Rem<&Polynomial<T>>
+    impl<T: Clone + Zero + Sub<Output = T> + Mul<Output = T> + Div<Output = T>> Rem<&Polynomial<T>>
         for &Polynomial<T>
     {
         type Output = Polynomial<T>;
         fn rem(self, rhs: &Polynomial<T>) -> Self::Output {
             let mut x = self.clone();
             for i in (rhs.length() - 1..x.length()).rev() {
-                let t = x[i] / rhs[rhs.length() - 1];
+                let t = x[i].clone() / rhs[rhs.length() - 1].clone();
                 for j in 0..rhs.length() {
-                    x[i - j] = x[i - j] - t * rhs[rhs.length() - 1 - j];
+                    x[i - j] = x[i - j].clone() - t.clone() * rhs[rhs.length() - 1 - j].clone();
                 }
             }
             x.data.truncate(rhs.length() - 1);
             x
         }
     }
-    impl<T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>> Polynomial<T> {
+    impl<T: Clone + Zero + One + Add<Output = T> + Mul<Output = T>> Polynomial<T> {
         pub fn pow(&self, mut n: usize) -> Self {
             let mut x = self.clone();
             let mut res = Self::one();
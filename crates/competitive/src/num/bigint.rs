@@ -0,0 +1,201 @@
+use super::{IterScan, One, Zero};
+use crate::{
+    algebra::DotProduct,
+    math::{ConvolveSteps, U64Convolve},
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+};
+
+pub use self::{
+    convert::ParseBigIntError,
+    integer::{UnboundedBinaryRepr, UnboundedIntBase, UnboundedSigned, UnboundedUnsigned},
+};
+
+mod convert;
+mod integer;
+mod limbs;
+mod ops;
+
+/// Arbitrary-precision unsigned integer in base $2^{64}$.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// little-endian without leading zeros
+    limbs: Vec<u64>,
+}
+
+/// Arbitrary-precision signed integer.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /// always `false` for zero
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigUint {
+    pub fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        limbs::trim(&mut limbs);
+        Self { limbs }
+    }
+
+    /// Little-endian base $2^{64}$ digits without leading zeros.
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    pub fn into_limbs(self) -> Vec<u64> {
+        self.limbs
+    }
+}
+
+impl BigInt {
+    pub fn from_parts(negative: bool, magnitude: BigUint) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    /// Return `(is_negative, |self|)`.
+    pub fn into_parts(self) -> (bool, BigUint) {
+        (self.negative, self.magnitude)
+    }
+
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+}
+
+impl Zero for BigUint {
+    fn zero() -> Self {
+        Self { limbs: vec![] }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+}
+
+impl One for BigUint {
+    fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+
+    fn is_one(&self) -> bool {
+        self.limbs == [1]
+    }
+}
+
+impl Zero for BigInt {
+    fn zero() -> Self {
+        Self {
+            negative: false,
+            magnitude: BigUint::zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+}
+
+impl One for BigInt {
+    fn one() -> Self {
+        Self {
+            negative: false,
+            magnitude: BigUint::one(),
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        !self.negative && self.magnitude.is_one()
+    }
+}
+
+impl DotProduct for BigUint {}
+impl DotProduct for BigInt {}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        limbs::cmp(&self.limbs, &other.limbs)
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl IterScan for BigUint {
+    type Output = Self;
+    fn scan<'a, I: Iterator<Item = &'a str>>(iter: &mut I) -> Option<Self::Output> {
+        iter.next()?.parse().ok()
+    }
+}
+
+impl IterScan for BigInt {
+    type Output = Self;
+    fn scan<'a, I: Iterator<Item = &'a str>>(iter: &mut I) -> Option<Self::Output> {
+        iter.next()?.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_cmp() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(i128::MIN..=i128::MAX) >> rng.random(0u32..128);
+            let b = rng.random(i128::MIN..=i128::MAX) >> rng.random(0u32..128);
+            assert_eq!(BigInt::from(a).cmp(&BigInt::from(b)), a.cmp(&b));
+            let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+            assert_eq!(BigUint::from(a).cmp(&BigUint::from(b)), a.cmp(&b));
+        }
+    }
+
+    #[test]
+    fn test_scan() {
+        let mut iter = "123 -456789012345678901234567890 x".split_ascii_whitespace();
+        assert_eq!(BigUint::scan(&mut iter), Some(BigUint::from(123u32)));
+        assert_eq!(
+            BigInt::scan(&mut iter),
+            Some(BigInt::from(-456789012345678901234567890i128))
+        );
+        assert_eq!(BigInt::scan(&mut iter), None);
+    }
+}
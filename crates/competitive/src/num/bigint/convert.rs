@@ -0,0 +1,395 @@
+use super::*;
+use crate::tools::FastOutput;
+use std::{io::Write, str::FromStr};
+
+/// $10^{19}$, the largest power of 10 less than $2^{64}$
+const DECIMAL_RADIX: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_RADIX_LEN: usize = 19;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError {
+    kind: BigIntErrorKind,
+}
+
+impl ParseBigIntError {
+    fn empty() -> Self {
+        Self {
+            kind: BigIntErrorKind::Empty,
+        }
+    }
+    fn invalid_digit() -> Self {
+        Self {
+            kind: BigIntErrorKind::InvalidDigit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BigIntErrorKind {
+    Empty,
+    InvalidDigit,
+}
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            BigIntErrorKind::Empty => write!(f, "empty string"),
+            BigIntErrorKind::InvalidDigit => write!(f, "invalid digit"),
+        }
+    }
+}
+
+fn strip_sign(s: &str) -> (bool, &str) {
+    if let Some(s) = s.strip_prefix('-') {
+        (true, s)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    }
+}
+
+/// Parse decimal digits by merging chunks in O(M(n) log n).
+fn parse_decimal(s: &[u8]) -> Result<Vec<u64>, ParseBigIntError> {
+    if s.is_empty() {
+        return Err(ParseBigIntError::empty());
+    }
+    if !s.iter().all(u8::is_ascii_digit) {
+        return Err(ParseBigIntError::invalid_digit());
+    }
+    let mut chunks: Vec<Vec<u64>> = s
+        .rchunks(DECIMAL_RADIX_LEN)
+        .map(|c| {
+            let x = c.iter().fold(0u64, |acc, &c| acc * 10 + (c - b'0') as u64);
+            if x == 0 { vec![] } else { vec![x] }
+        })
+        .collect();
+    let mut base = vec![DECIMAL_RADIX];
+    while chunks.len() > 1 {
+        chunks = chunks
+            .chunks(2)
+            .map(|c| match c {
+                [lo, hi] => limbs::add(lo, &limbs::mul(hi, &base)),
+                [lo] => lo.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        base = limbs::mul(&base, &base);
+    }
+    Ok(chunks.pop().unwrap())
+}
+
+/// Convert to base $10^{19}$ digits in little-endian by splitting recursively.
+fn to_decimal_chunks(a: &[u64]) -> Vec<u64> {
+    fn rec(a: &[u64], k: usize, powers: &[Vec<u64>], out: &mut Vec<u64>) {
+        if k == 0 {
+            out.push(a.first().copied().unwrap_or(0));
+        } else if a.is_empty() {
+            out.resize(out.len() + (1 << k), 0);
+        } else {
+            let (q, r) = limbs::divmod(a, &powers[k - 1]);
+            rec(&r, k - 1, powers, out);
+            rec(&q, k - 1, powers, out);
+        }
+    }
+    let mut powers = vec![vec![DECIMAL_RADIX]];
+    while limbs::cmp(powers.last().unwrap(), a) != Ordering::Greater {
+        let p = powers.last().unwrap();
+        powers.push(limbs::mul(p, p));
+    }
+    let mut out = vec![];
+    rec(a, powers.len() - 1, &powers, &mut out);
+    limbs::trim(&mut out);
+    out
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix('+').unwrap_or(s);
+        Ok(Self {
+            limbs: parse_decimal(s.as_bytes())?,
+        })
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = strip_sign(s);
+        Ok(Self::from_parts(
+            negative,
+            BigUint {
+                limbs: parse_decimal(s.as_bytes())?,
+            },
+        ))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chunks = to_decimal_chunks(&self.limbs);
+        let mut iter = chunks.iter().rev();
+        write!(f, "{}", iter.next().copied().unwrap_or(0))?;
+        for c in iter {
+            write!(f, "{:019}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        Display::fmt(&self.magnitude, f)
+    }
+}
+
+impl<W> FastOutput<W>
+where
+    W: Write,
+{
+    pub fn biguint(&mut self, x: &BigUint) {
+        self.decimal_chunks(&to_decimal_chunks(&x.limbs));
+    }
+
+    pub fn bigint(&mut self, x: &BigInt) {
+        if x.negative {
+            self.byte(b'-');
+        }
+        self.biguint(&x.magnitude);
+    }
+}
+
+impl BigUint {
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        let src = src.strip_prefix('+').unwrap_or(src);
+        if src.is_empty() {
+            return Err(ParseBigIntError::empty());
+        }
+        let mut limbs = vec![];
+        for c in src.chars() {
+            let d = c
+                .to_digit(radix)
+                .ok_or_else(ParseBigIntError::invalid_digit)?;
+            limbs = limbs::add(&limbs::mul_small(&limbs, radix as u64), &[d as u64]);
+            limbs::trim(&mut limbs);
+        }
+        Ok(Self { limbs })
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let mut digits = vec![];
+        let mut a = self.limbs.clone();
+        while !a.is_empty() {
+            let (q, r) = limbs::div_small(&a, radix as u64);
+            digits.push(std::char::from_digit(r as u32, radix).unwrap());
+            a = q;
+        }
+        digits.iter().rev().collect()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [x] => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [x] => Some(x as u128),
+            [x, y] => Some((y as u128) << 64 | x as u128),
+            _ => None,
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.limbs
+            .iter()
+            .rev()
+            .fold(0., |acc, &x| acc * 18446744073709551616. + x as f64)
+    }
+}
+
+impl BigInt {
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        let (negative, src) = strip_sign(src);
+        if src.starts_with(['+', '-']) {
+            return Err(ParseBigIntError::invalid_digit());
+        }
+        Ok(Self::from_parts(
+            negative,
+            BigUint::from_str_radix(src, radix)?,
+        ))
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let s = self.magnitude.to_str_radix(radix);
+        if self.negative { format!("-{}", s) } else { s }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let x = self.magnitude.to_u64()?;
+        if self.negative {
+            0i64.checked_sub_unsigned(x)
+        } else {
+            i64::try_from(x).ok()
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let x = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(x)
+        } else {
+            i128::try_from(x).ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let x = self.magnitude.to_f64();
+        if self.negative { -x } else { x }
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for BigUint {
+                fn from(val: $t) -> Self {
+                    let val = val as u128;
+                    Self::from_limbs(vec![val as u64, (val >> 64) as u64])
+                }
+            }
+
+            impl From<$t> for BigInt {
+                fn from(val: $t) -> Self {
+                    Self::from_parts(false, BigUint::from(val))
+                }
+            }
+        )*
+    };
+}
+impl_from_unsigned!(u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_from_signed {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for BigInt {
+                fn from(val: $t) -> Self {
+                    Self::from_parts(val < 0, BigUint::from(val.unsigned_abs()))
+                }
+            }
+        )*
+    };
+}
+impl_from_signed!(i8 i16 i32 i64 i128 isize);
+
+impl From<BigUint> for BigInt {
+    fn from(val: BigUint) -> Self {
+        Self::from_parts(false, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+    use test_case::test_case;
+
+    #[test_case("0", Ok(0); "zero")]
+    #[test_case("-0", Ok(0); "minus zero")]
+    #[test_case("+12", Ok(12); "plus")]
+    #[test_case("-12", Ok(-12); "minus")]
+    #[test_case("000123", Ok(123); "leading zeros")]
+    #[test_case("", Err(ParseBigIntError::empty()); "empty")]
+    #[test_case("-", Err(ParseBigIntError::empty()); "sign only")]
+    #[test_case("1a", Err(ParseBigIntError::invalid_digit()); "invalid")]
+    #[test_case("--1", Err(ParseBigIntError::invalid_digit()); "double sign")]
+    fn test_from_str(s: &str, expected: Result<i128, ParseBigIntError>) {
+        assert_eq!(s.parse::<BigInt>(), expected.map(BigInt::from));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(i128::MIN..=i128::MAX) >> rng.random(0u32..128);
+            let x = BigInt::from(a);
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(a.to_string().parse::<BigInt>(), Ok(x.clone()));
+            assert_eq!(x.to_i128(), Some(a));
+            let radix = rng.random(2..=36);
+            let s = x.to_str_radix(radix);
+            assert_eq!(BigInt::from_str_radix(&s, radix), Ok(x));
+        }
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!((BigInt::from(i128::MAX) + BigInt::one()).to_i128(), None);
+        assert_eq!(BigUint::from_str_radix("fF", 16), Ok(BigUint::from(255u32)));
+    }
+
+    #[test]
+    fn test_display_large() {
+        let mut rng = Xorshift::default();
+        for _ in 0..20 {
+            let n = rng.random(1usize..=2000);
+            let mut s: String = (0..n)
+                .map(|_| char::from(b'0' + rng.random(0u8..10)))
+                .collect();
+            if s.starts_with('0') {
+                s.replace_range(..1, "1");
+            }
+            let x: BigUint = s.parse().unwrap();
+            assert_eq!(x.to_string(), s);
+            let mut y = BigUint::zero();
+            for c in s.bytes() {
+                y = y * BigUint::from(10u32) + BigUint::from(c - b'0');
+            }
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn test_fast_output() {
+        let mut rng = Xorshift::default();
+        let mut buf = Vec::new();
+        let mut expected = String::new();
+        {
+            let mut fo = FastOutput::with_capacity(32, &mut buf);
+            for _ in 0..200 {
+                let n = rng.random(0usize..=8);
+                let limbs = (0..n)
+                    .map(|_| match rng.random(0..3) {
+                        0 => 0,
+                        1 => DECIMAL_RADIX * rng.random(0u64..=1),
+                        _ => rng.random(..),
+                    })
+                    .collect();
+                let x = BigInt::from_parts(rng.gen_bool(0.5), BigUint::from_limbs(limbs));
+                fo.bigint(&x);
+                fo.byte(b' ');
+                fo.biguint(x.magnitude());
+                fo.byte(b'\n');
+                expected += &format!("{} {}\n", x, x.magnitude());
+            }
+            for k in 0..60 {
+                let x = BigUint::from(10u32).pow(k);
+                fo.biguint(&x);
+                fo.byte(b'\n');
+                expected += &format!("{}\n", x);
+            }
+            fo.flush();
+        }
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+}
@@ -0,0 +1,711 @@
+//! Integer operations for arbitrary-precision integers.
+//!
+//! `IntBase`, `Unsigned`, `Signed` and `BinaryRepr` require `Copy` and `Bounded`, so their counterparts taking
+//! operands by reference are provided as separate traits, which forward to the inherent methods.
+
+use super::*;
+use std::{
+    iter::{Product, Sum},
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div,
+        DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub,
+        SubAssign,
+    },
+    str::FromStr,
+};
+
+/// Trait for basic integer operations without `Copy` and `Bounded`.
+pub trait UnboundedIntBase:
+    Clone
+    + Zero
+    + One
+    + Eq
+    + Ord
+    + Default
+    + FromStr
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + RemAssign
+    + Sum
+    + Product
+{
+    type Error;
+    fn div_euclid(&self, rhs: &Self) -> Self;
+    fn rem_euclid(&self, rhs: &Self) -> Self;
+    fn pow(&self, exp: u32) -> Self;
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, Self::Error>;
+    fn ilog(&self, base: &Self) -> u32;
+    fn ilog2(&self) -> u32;
+    fn ilog10(&self) -> u32;
+    fn isqrt(&self) -> Self;
+}
+
+/// Trait for unsigned integer operations without `Copy` and `Bounded`.
+pub trait UnboundedUnsigned: UnboundedIntBase {
+    type Signed: UnboundedSigned<Unsigned = Self>;
+    fn signed(self) -> Self::Signed;
+    fn abs_diff(&self, other: &Self) -> Self;
+    fn div_ceil(&self, rhs: &Self) -> Self;
+    fn is_power_of_two(&self) -> bool;
+    fn next_power_of_two(&self) -> Self;
+    fn is_multiple_of(&self, rhs: &Self) -> bool;
+    fn next_multiple_of(&self, rhs: &Self) -> Self;
+    fn gcd(&self, other: &Self) -> Self;
+    fn lcm(&self, other: &Self) -> Self;
+    fn mod_inv(&self, modulo: &Self) -> Self;
+    fn mod_add(&self, rhs: &Self, modulo: &Self) -> Self;
+    fn mod_sub(&self, rhs: &Self, modulo: &Self) -> Self;
+    fn mod_mul(&self, rhs: &Self, modulo: &Self) -> Self;
+    fn mod_neg(&self, modulo: &Self) -> Self;
+}
+
+/// Trait for signed integer operations without `Copy` and `Bounded`.
+pub trait UnboundedSigned: UnboundedIntBase + Neg<Output = Self> {
+    type Unsigned: UnboundedUnsigned<Signed = Self>;
+    fn unsigned_abs(&self) -> Self::Unsigned;
+    fn abs(&self) -> Self;
+    fn abs_diff(&self, other: &Self) -> Self::Unsigned;
+    fn is_negative(&self) -> bool;
+    fn is_positive(&self) -> bool;
+    fn signum(&self) -> Self;
+    /// Return `(g, x, y)` such that `self * x + other * y = g = gcd(self, other)`.
+    fn extgcd(&self, other: &Self) -> (Self::Unsigned, Self, Self);
+}
+
+/// Trait for operations of integer in binary representation without a fixed width.
+pub trait UnboundedBinaryRepr:
+    Sized
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+    + BitAndAssign
+    + BitOrAssign
+    + BitXorAssign
+    + ShlAssign<usize>
+    + ShrAssign<usize>
+{
+    /// Number of bits required to represent `self`.
+    fn bits(&self) -> usize;
+    fn bit(&self, i: usize) -> bool;
+    fn count_ones(&self) -> u64;
+    /// `None` if `self` is zero.
+    fn trailing_zeros(&self) -> Option<u64>;
+}
+
+macro_rules! impl_unbounded_int_base {
+    ($($t:ty)*) => {
+        $(
+            impl UnboundedIntBase for $t {
+                type Error = ParseBigIntError;
+                fn div_euclid(&self, rhs: &Self) -> Self { self.div_euclid(rhs) }
+                fn rem_euclid(&self, rhs: &Self) -> Self { self.rem_euclid(rhs) }
+                fn pow(&self, exp: u32) -> Self { self.pow(exp) }
+                fn from_str_radix(src: &str, radix: u32) -> Result<Self, Self::Error> { Self::from_str_radix(src, radix) }
+                fn ilog(&self, base: &Self) -> u32 { self.ilog(base) }
+                fn ilog2(&self) -> u32 { self.ilog2() }
+                fn ilog10(&self) -> u32 { self.ilog10() }
+                fn isqrt(&self) -> Self { self.isqrt() }
+            }
+        )*
+    };
+}
+impl_unbounded_int_base!(BigUint BigInt);
+
+impl UnboundedUnsigned for BigUint {
+    type Signed = BigInt;
+    fn signed(self) -> Self::Signed {
+        self.signed()
+    }
+    fn abs_diff(&self, other: &Self) -> Self {
+        self.abs_diff(other)
+    }
+    fn div_ceil(&self, rhs: &Self) -> Self {
+        self.div_ceil(rhs)
+    }
+    fn is_power_of_two(&self) -> bool {
+        self.is_power_of_two()
+    }
+    fn next_power_of_two(&self) -> Self {
+        self.next_power_of_two()
+    }
+    fn is_multiple_of(&self, rhs: &Self) -> bool {
+        self.is_multiple_of(rhs)
+    }
+    fn next_multiple_of(&self, rhs: &Self) -> Self {
+        self.next_multiple_of(rhs)
+    }
+    fn gcd(&self, other: &Self) -> Self {
+        self.gcd(other)
+    }
+    fn lcm(&self, other: &Self) -> Self {
+        self.lcm(other)
+    }
+    fn mod_inv(&self, modulo: &Self) -> Self {
+        self.mod_inv(modulo)
+    }
+    fn mod_add(&self, rhs: &Self, modulo: &Self) -> Self {
+        self.mod_add(rhs, modulo)
+    }
+    fn mod_sub(&self, rhs: &Self, modulo: &Self) -> Self {
+        self.mod_sub(rhs, modulo)
+    }
+    fn mod_mul(&self, rhs: &Self, modulo: &Self) -> Self {
+        self.mod_mul(rhs, modulo)
+    }
+    fn mod_neg(&self, modulo: &Self) -> Self {
+        self.mod_neg(modulo)
+    }
+}
+
+impl UnboundedSigned for BigInt {
+    type Unsigned = BigUint;
+    fn unsigned_abs(&self) -> Self::Unsigned {
+        self.unsigned_abs()
+    }
+    fn abs(&self) -> Self {
+        self.abs()
+    }
+    fn abs_diff(&self, other: &Self) -> Self::Unsigned {
+        self.abs_diff(other)
+    }
+    fn is_negative(&self) -> bool {
+        self.is_negative()
+    }
+    fn is_positive(&self) -> bool {
+        self.is_positive()
+    }
+    fn signum(&self) -> Self {
+        self.signum()
+    }
+    fn extgcd(&self, other: &Self) -> (Self::Unsigned, Self, Self) {
+        self.extgcd(other)
+    }
+}
+
+impl UnboundedBinaryRepr for BigUint {
+    fn bits(&self) -> usize {
+        self.bits()
+    }
+    fn bit(&self, i: usize) -> bool {
+        self.bit(i)
+    }
+    fn count_ones(&self) -> u64 {
+        self.count_ones()
+    }
+    fn trailing_zeros(&self) -> Option<u64> {
+        self.trailing_zeros()
+    }
+}
+
+impl BigUint {
+    /// Return `(self / rhs, self % rhs)`.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = limbs::divmod(&self.limbs, &rhs.limbs);
+        (Self { limbs: q }, Self { limbs: r })
+    }
+
+    pub fn div_euclid(&self, rhs: &Self) -> Self {
+        self / rhs
+    }
+
+    pub fn rem_euclid(&self, rhs: &Self) -> Self {
+        self % rhs
+    }
+
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut res = Self::one();
+        let mut x = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= &x;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                x = &x * &x;
+            }
+        }
+        res
+    }
+
+    /// Number of bits required to represent `self`.
+    pub fn bits(&self) -> usize {
+        self.limbs
+            .last()
+            .map_or(0, |x| self.limbs.len() * 64 - x.leading_zeros() as usize)
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 64)
+            .is_some_and(|x| x >> (i % 64) & 1 == 1)
+    }
+
+    pub fn set_bit(&mut self, i: usize, value: bool) {
+        if value {
+            if self.limbs.len() <= i / 64 {
+                self.limbs.resize(i / 64 + 1, 0);
+            }
+            self.limbs[i / 64] |= 1 << (i % 64);
+        } else if i / 64 < self.limbs.len() {
+            self.limbs[i / 64] &= !(1 << (i % 64));
+            limbs::trim(&mut self.limbs);
+        }
+    }
+
+    pub fn count_ones(&self) -> u64 {
+        self.limbs.iter().map(|x| x.count_ones() as u64).sum()
+    }
+
+    /// `None` if `self` is zero.
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        let i = self.limbs.iter().position(|&x| x != 0)?;
+        Some(i as u64 * 64 + self.limbs[i].trailing_zeros() as u64)
+    }
+
+    pub fn ilog2(&self) -> u32 {
+        assert!(
+            !self.is_zero(),
+            "argument of integer logarithm must be positive"
+        );
+        (self.bits() - 1) as u32
+    }
+
+    pub fn ilog(&self, base: &Self) -> u32 {
+        assert!(
+            !self.is_zero(),
+            "argument of integer logarithm must be positive"
+        );
+        assert!(
+            base.bits() >= 2,
+            "base of integer logarithm must be at least 2"
+        );
+        let mut powers = vec![base.clone()];
+        while powers.last().unwrap().bits() * 2 <= self.bits() + 1 {
+            let p = powers.last().unwrap();
+            powers.push(p * p);
+        }
+        let mut res = 0u32;
+        let mut acc = Self::one();
+        for (i, p) in powers.iter().enumerate().rev() {
+            let next = &acc * p;
+            if &next <= self {
+                acc = next;
+                res += 1 << i;
+            }
+        }
+        res
+    }
+
+    pub fn ilog10(&self) -> u32 {
+        self.ilog(&Self::from(10u32))
+    }
+
+    /// Floor of the square root.
+    pub fn isqrt(&self) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+        let mut x = Self::one() << self.bits().div_ceil(2);
+        loop {
+            let y = (&x + self / &x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    pub fn abs_diff(&self, other: &Self) -> Self {
+        if self >= other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+
+    pub fn div_ceil(&self, rhs: &Self) -> Self {
+        let (q, r) = self.div_rem(rhs);
+        if r.is_zero() { q } else { q + Self::one() }
+    }
+
+    pub fn is_power_of_two(&self) -> bool {
+        self.count_ones() == 1
+    }
+
+    pub fn next_power_of_two(&self) -> Self {
+        if self.is_zero() || self.is_power_of_two() {
+            self.clone().max(Self::one())
+        } else {
+            Self::one() << self.bits()
+        }
+    }
+
+    pub fn is_multiple_of(&self, rhs: &Self) -> bool {
+        if rhs.is_zero() {
+            self.is_zero()
+        } else {
+            (self % rhs).is_zero()
+        }
+    }
+
+    pub fn next_multiple_of(&self, rhs: &Self) -> Self {
+        let r = self % rhs;
+        if r.is_zero() {
+            self.clone()
+        } else {
+            self + (rhs - r)
+        }
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    pub fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() && other.is_zero() {
+            Self::zero()
+        } else {
+            self / self.gcd(other) * other
+        }
+    }
+
+    pub fn signed(self) -> BigInt {
+        BigInt::from_parts(false, self)
+    }
+
+    pub fn mod_add(&self, rhs: &Self, modulo: &Self) -> Self {
+        debug_assert!(self < modulo && rhs < modulo);
+        let s = self + rhs;
+        if &s >= modulo { s - modulo } else { s }
+    }
+
+    pub fn mod_sub(&self, rhs: &Self, modulo: &Self) -> Self {
+        debug_assert!(self < modulo && rhs < modulo);
+        if self >= rhs {
+            self - rhs
+        } else {
+            self + modulo - rhs
+        }
+    }
+
+    pub fn mod_mul(&self, rhs: &Self, modulo: &Self) -> Self {
+        self * rhs % modulo
+    }
+
+    pub fn mod_neg(&self, modulo: &Self) -> Self {
+        debug_assert!(self < modulo);
+        if self.is_zero() {
+            Self::zero()
+        } else {
+            modulo - self
+        }
+    }
+
+    pub fn mod_pow(&self, exp: &Self, modulo: &Self) -> Self {
+        let mut res = Self::one() % modulo;
+        let x = self % modulo;
+        for i in (0..exp.bits()).rev() {
+            res = res.mod_mul(&res, modulo);
+            if exp.bit(i) {
+                res = res.mod_mul(&x, modulo);
+            }
+        }
+        res
+    }
+
+    pub fn mod_inv(&self, modulo: &Self) -> Self {
+        debug_assert!(!modulo.is_zero(), "modulo must be non-zero");
+        let (g, x, _) = BigInt::from(self.clone()).extgcd(&BigInt::from(modulo.clone()));
+        debug_assert!(g.is_one(), "not coprime");
+        x.rem_euclid(&BigInt::from(modulo.clone())).magnitude
+    }
+}
+
+impl BigInt {
+    /// Return `(self / rhs, self % rhs)` rounding toward zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = self.magnitude.div_rem(&rhs.magnitude);
+        (
+            Self::from_parts(self.negative != rhs.negative, q),
+            Self::from_parts(self.negative, r),
+        )
+    }
+
+    pub fn div_euclid(&self, rhs: &Self) -> Self {
+        let (q, r) = self.div_rem(rhs);
+        if r.negative {
+            if rhs.negative {
+                q + Self::one()
+            } else {
+                q - Self::one()
+            }
+        } else {
+            q
+        }
+    }
+
+    pub fn rem_euclid(&self, rhs: &Self) -> Self {
+        let r = self % rhs;
+        if r.negative {
+            Self::from_parts(false, &rhs.magnitude - r.magnitude)
+        } else {
+            r
+        }
+    }
+
+    pub fn pow(&self, exp: u32) -> Self {
+        Self::from_parts(self.negative && exp & 1 == 1, self.magnitude.pow(exp))
+    }
+
+    pub fn ilog(&self, base: &Self) -> u32 {
+        assert!(
+            !self.negative,
+            "argument of integer logarithm must be positive"
+        );
+        assert!(
+            !base.negative,
+            "base of integer logarithm must be at least 2"
+        );
+        self.magnitude.ilog(&base.magnitude)
+    }
+
+    pub fn ilog2(&self) -> u32 {
+        assert!(
+            !self.negative,
+            "argument of integer logarithm must be positive"
+        );
+        self.magnitude.ilog2()
+    }
+
+    pub fn ilog10(&self) -> u32 {
+        assert!(
+            !self.negative,
+            "argument of integer logarithm must be positive"
+        );
+        self.magnitude.ilog10()
+    }
+
+    /// Floor of the square root.
+    pub fn isqrt(&self) -> Self {
+        assert!(
+            !self.negative,
+            "argument of integer square root cannot be negative"
+        );
+        Self::from_parts(false, self.magnitude.isqrt())
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.magnitude.clone())
+    }
+
+    pub fn unsigned_abs(&self) -> BigUint {
+        self.magnitude.clone()
+    }
+
+    pub fn abs_diff(&self, other: &Self) -> BigUint {
+        (self - other).magnitude
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_positive(&self) -> bool {
+        !self.negative && !self.is_zero()
+    }
+
+    pub fn signum(&self) -> Self {
+        match (self.negative, self.is_zero()) {
+            (true, _) => -Self::one(),
+            (false, true) => Self::zero(),
+            (false, false) => Self::one(),
+        }
+    }
+
+    /// Return `Some(|self|)` if `self` is non-negative.
+    pub fn unsigned(self) -> Option<BigUint> {
+        if self.negative {
+            None
+        } else {
+            Some(self.magnitude)
+        }
+    }
+
+    /// Return `(g, x, y)` such that `self * x + other * y = g = gcd(self, other)`.
+    pub fn extgcd(&self, other: &Self) -> (BigUint, Self, Self) {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        let (mut u, mut v, mut x, mut y) = (Self::one(), Self::zero(), Self::zero(), Self::one());
+        while !a.is_zero() {
+            let k = &b / &a;
+            x -= &k * &u;
+            y -= &k * &v;
+            b -= &k * &a;
+            std::mem::swap(&mut x, &mut u);
+            std::mem::swap(&mut y, &mut v);
+            std::mem::swap(&mut b, &mut a);
+        }
+        if b.negative {
+            b = -b;
+            x = -x;
+            y = -y;
+        }
+        (b.magnitude, x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{num::Unsigned, tools::Xorshift};
+
+    #[test]
+    fn test_unsigned_small() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = (rng.random(1u64..=u64::MAX) >> rng.random(0u32..64)).max(1);
+            let b = (rng.random(1u64..=u64::MAX) >> rng.random(0u32..64)).max(1);
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+            assert_eq!(x.gcd(&y), BigUint::from(a.gcd(b)));
+            assert_eq!(
+                x.lcm(&y),
+                BigUint::from(a as u128 / a.gcd(b) as u128 * b as u128)
+            );
+            assert_eq!(x.isqrt(), BigUint::from(a.isqrt()));
+            assert_eq!(x.ilog2(), a.ilog2());
+            assert_eq!(x.ilog10(), a.ilog10());
+            if b >= 2 {
+                assert_eq!(x.ilog(&y), a.ilog(b));
+            }
+            assert_eq!(x.div_ceil(&y), BigUint::from(a.div_ceil(b)));
+            assert_eq!(
+                x.next_multiple_of(&y),
+                BigUint::from((a as u128).next_multiple_of(b as u128))
+            );
+            assert_eq!(x.is_multiple_of(&y), a.is_multiple_of(b));
+            assert_eq!(x.count_ones(), a.count_ones() as u64);
+            assert_eq!(x.trailing_zeros(), Some(a.trailing_zeros() as u64));
+            assert_eq!(
+                x.next_power_of_two(),
+                BigUint::from((a as u128).next_power_of_two())
+            );
+            let m = b.max(2);
+            let mm = BigUint::from(m);
+            let (xa, xb) = (BigUint::from(a % m), BigUint::from(b % m));
+            assert_eq!(
+                xa.mod_add(&xb, &mm),
+                BigUint::from((a % m).mod_add(b % m, m))
+            );
+            assert_eq!(
+                xa.mod_sub(&xb, &mm),
+                BigUint::from((a % m).mod_sub(b % m, m))
+            );
+            assert_eq!(
+                xa.mod_mul(&xb, &mm),
+                BigUint::from((a % m).mod_mul(b % m, m))
+            );
+            if a.gcd(m) == 1 {
+                assert_eq!(
+                    x.mod_inv(&mm),
+                    BigUint::from((a as u128).mod_inv(m as u128))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_small() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(i64::MIN + 1..=i64::MAX) >> rng.random(0u32..64);
+            let b = rng.random(i64::MIN + 1..=i64::MAX) >> rng.random(0u32..64);
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            if b != 0 {
+                assert_eq!(x.div_euclid(&y), BigInt::from(a.div_euclid(b)));
+                assert_eq!(x.rem_euclid(&y), BigInt::from(a.rem_euclid(b)));
+            }
+            assert_eq!(x.signum(), BigInt::from(a.signum()));
+            assert_eq!(x.abs(), BigInt::from(a.abs()));
+            let (g, s, t) = x.extgcd(&y);
+            assert_eq!(g, BigUint::from(a.unsigned_abs().gcd(b.unsigned_abs())));
+            assert_eq!(x * s + y * t, BigInt::from(g));
+        }
+    }
+
+    #[test]
+    fn test_traits() {
+        fn mod_inv<T>(a: &T, modulo: &T) -> T
+        where
+            T: UnboundedUnsigned,
+        {
+            let modulo = modulo.clone().signed();
+            let (g, x, _) = a.clone().signed().extgcd(&modulo);
+            assert!(g.is_one());
+            x.rem_euclid(&modulo).unsigned_abs()
+        }
+        fn low_bits<T>(a: &T, k: usize) -> T
+        where
+            T: UnboundedUnsigned + UnboundedBinaryRepr,
+        {
+            a.clone() ^ ((a.clone() >> k) << k)
+        }
+
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(1u64..=u64::MAX) >> rng.random(1u32..64);
+            let m = (rng.random(1u64..=u64::MAX) >> rng.random(1u32..64)).max(2);
+            let (x, mm) = (BigUint::from(a), BigUint::from(m));
+            if a.gcd(m) == 1 {
+                assert_eq!(mod_inv(&x, &mm), BigUint::from(a.mod_inv(m)));
+            }
+            let k = rng.random(0usize..64);
+            let y = low_bits(&x, k);
+            assert_eq!(y, BigUint::from(a & ((1u64 << k) - 1)));
+            assert_eq!(
+                UnboundedBinaryRepr::bits(&y),
+                64 - (a & ((1u64 << k) - 1)).leading_zeros() as usize
+            );
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let mut rng = Xorshift::default();
+        for _ in 0..50 {
+            let n = rng.random(1usize..=40);
+            let a = BigUint::from_limbs(rng.random_iter(..).take(n).collect());
+            let s = a.isqrt();
+            assert!(&s * &s <= a && (&s + BigUint::one()).pow(2) > a);
+            let b = BigUint::from_limbs(rng.random_iter(..).take(n / 2 + 1).collect());
+            let g = a.gcd(&b);
+            assert!((&a % &g).is_zero() && (&b % &g).is_zero());
+            assert!((&a / &g).gcd(&(&b / &g)).is_one());
+            let p: BigUint = "170141183460469231731687303715884105727".parse().unwrap();
+            if !(&a % &p).is_zero() {
+                assert!((&a % &p).mod_inv(&p).mod_mul(&(&a % &p), &p).is_one());
+                assert!(a.mod_pow(&(&p - BigUint::one()), &p).is_one());
+            }
+        }
+        let ten = BigUint::from(10u32);
+        for k in 1..100 {
+            let p = ten.pow(k);
+            assert_eq!(p.ilog10(), k);
+            assert_eq!((&p - BigUint::one()).ilog10(), k - 1);
+        }
+    }
+}
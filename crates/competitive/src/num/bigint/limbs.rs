@@ -0,0 +1,368 @@
+//! Arithmetic on trimmed little-endian base-$2^{64}$ limbs.
+
+use super::*;
+
+const MUL_NAIVE_THRESHOLD: usize = 64;
+const DIV_NEWTON_THRESHOLD: usize = 256;
+const RECIPROCAL_KNUTH_THRESHOLD: usize = 64;
+
+pub(super) fn trim(a: &mut Vec<u64>) {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+}
+
+pub(super) fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub(super) fn add(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut c = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (s, c1) = x.overflowing_add(b.get(i).copied().unwrap_or(0));
+        let (s, c2) = s.overflowing_add(carry as u64);
+        c.push(s);
+        carry = c1 || c2;
+    }
+    if carry {
+        c.push(1);
+    }
+    c
+}
+
+/// `a - b` for `a >= b`
+pub(super) fn sub(a: &[u64], b: &[u64]) -> Vec<u64> {
+    debug_assert!(cmp(a, b) != Ordering::Less);
+    let mut c = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (s, b1) = x.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (s, b2) = s.overflowing_sub(borrow as u64);
+        c.push(s);
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow);
+    trim(&mut c);
+    c
+}
+
+pub(super) fn mul_small(a: &[u64], m: u64) -> Vec<u64> {
+    if m == 0 {
+        return vec![];
+    }
+    let mut c = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u64;
+    for &x in a {
+        let t = x as u128 * m as u128 + carry as u128;
+        c.push(t as u64);
+        carry = (t >> 64) as u64;
+    }
+    if carry > 0 {
+        c.push(carry);
+    }
+    c
+}
+
+/// Return `(a / d, a % d)`.
+pub(super) fn div_small(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+    assert!(d != 0, "attempt to divide by zero");
+    let mut q = vec![0u64; a.len()];
+    let mut rem = 0u128;
+    for (q, &x) in q.iter_mut().zip(a).rev() {
+        let t = rem << 64 | x as u128;
+        *q = (t / d as u128) as u64;
+        rem = t % d as u128;
+    }
+    trim(&mut q);
+    (q, rem as u64)
+}
+
+pub(super) fn shl(a: &[u64], bits: usize) -> Vec<u64> {
+    if a.is_empty() {
+        return vec![];
+    }
+    let (w, s) = (bits / 64, bits % 64);
+    let mut c = vec![0u64; w];
+    c.reserve(a.len() + 1);
+    if s == 0 {
+        c.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+        for &x in a {
+            c.push(x << s | carry);
+            carry = x >> (64 - s);
+        }
+        if carry > 0 {
+            c.push(carry);
+        }
+    }
+    c
+}
+
+pub(super) fn shr(a: &[u64], bits: usize) -> Vec<u64> {
+    let (w, s) = (bits / 64, bits % 64);
+    if a.len() <= w {
+        return vec![];
+    }
+    let a = &a[w..];
+    let mut c: Vec<u64> = if s == 0 {
+        a.to_vec()
+    } else {
+        (0..a.len())
+            .map(|i| a[i] >> s | a.get(i + 1).map_or(0, |&x| x << (64 - s)))
+            .collect()
+    };
+    trim(&mut c);
+    c
+}
+
+fn mul_naive(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut c = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = c[i + j] as u128 + x as u128 * y as u128 + carry as u128;
+            c[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        c[i + b.len()] = carry;
+    }
+    trim(&mut c);
+    c
+}
+
+// split into 16-bit digits so that every coefficient of the convolution is less than 2^64
+fn mul_ntt(a: &[u64], b: &[u64]) -> Vec<u64> {
+    fn split(x: &[u64]) -> Vec<u64> {
+        x.iter()
+            .flat_map(|&x| [x & 0xffff, x >> 16 & 0xffff, x >> 32 & 0xffff, x >> 48])
+            .collect()
+    }
+    let d = U64Convolve::convolve(split(a), split(b));
+    let mut c = vec![0u64; a.len() + b.len()];
+    let mut carry = 0u128;
+    for i in 0..c.len() * 4 {
+        let t = d.get(i).copied().unwrap_or(0) as u128 + carry;
+        c[i / 4] |= ((t & 0xffff) as u64) << (i % 4 * 16);
+        carry = t >> 16;
+    }
+    debug_assert_eq!(carry, 0);
+    trim(&mut c);
+    c
+}
+
+pub(super) fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        vec![]
+    } else if a.len().min(b.len()) <= MUL_NAIVE_THRESHOLD {
+        mul_naive(a, b)
+    } else {
+        mul_ntt(a, b)
+    }
+}
+
+/// Knuth's algorithm D.
+fn divmod_knuth(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let n = b.len();
+    let m = a.len() - n;
+    let s = b[n - 1].leading_zeros() as usize;
+    let vn = shl(b, s);
+    let mut un = shl(a, s);
+    un.resize(a.len() + 1, 0);
+    let (vt, vs) = (vn[n - 1] as u128, vn[n - 2] as u128);
+    let mut q = vec![0u64; m + 1];
+    for j in (0..=m).rev() {
+        let num = (un[j + n] as u128) << 64 | un[j + n - 1] as u128;
+        let mut qhat = num / vt;
+        let mut rhat = num % vt;
+        while qhat >> 64 != 0 || qhat * vs > (rhat << 64 | un[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += vt;
+            if rhat >> 64 != 0 {
+                break;
+            }
+        }
+        let mut carry = 0u64;
+        let mut borrow = false;
+        for i in 0..n {
+            let p = qhat * vn[i] as u128 + carry as u128;
+            carry = (p >> 64) as u64;
+            let (t, b1) = un[i + j].overflowing_sub(p as u64);
+            let (t, b2) = t.overflowing_sub(borrow as u64);
+            un[i + j] = t;
+            borrow = b1 || b2;
+        }
+        let (t, b1) = un[j + n].overflowing_sub(carry);
+        let (t, b2) = t.overflowing_sub(borrow as u64);
+        un[j + n] = t;
+        if b1 || b2 {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (t, c1) = un[i + j].overflowing_add(vn[i]);
+                let (t, c2) = t.overflowing_add(carry as u64);
+                un[i + j] = t;
+                carry = c1 || c2;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = qhat as u64;
+    }
+    un.truncate(n);
+    trim(&mut q);
+    (q, shr(&un, s))
+}
+
+/// Approximation of $\lfloor 2^{64k} / b \rfloor$ by Newton's method, `k >= b.len()`.
+fn reciprocal(b: &[u64], k: usize) -> Vec<u64> {
+    let n = b.len();
+    let p = k - n;
+    if p <= RECIPROCAL_KNUTH_THRESHOLD {
+        let mut a = vec![0u64; k];
+        a.push(1);
+        return divmod(&a, b).0;
+    }
+    if n > p + 2 {
+        let t = p + 2;
+        return reciprocal(&b[n - t..], t + p);
+    }
+    let h = p / 2 + 2;
+    let x = shl(&reciprocal(b, n + h), (p - h) * 64);
+    let bx = mul(b, &x);
+    let mut one = vec![0u64; k];
+    one.push(1);
+    if cmp(&bx, &one) != Ordering::Greater {
+        let e = sub(&one, &bx);
+        add(&x, &shr(&mul(&x, &e), k * 64))
+    } else {
+        let e = sub(&bx, &one);
+        let d = add(&shr(&mul(&x, &e), k * 64), &[1]);
+        if cmp(&x, &d) == Ordering::Greater {
+            sub(&x, &d)
+        } else {
+            vec![]
+        }
+    }
+}
+
+fn divmod_newton(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let k = a.len();
+    let x = reciprocal(b, k);
+    let mut q = shr(&mul(a, &x), k * 64);
+    let mut qb = mul(&q, b);
+    while cmp(&qb, a) == Ordering::Greater {
+        q = sub(&q, &[1]);
+        qb = sub(&qb, b);
+    }
+    let mut r = sub(a, &qb);
+    while cmp(&r, b) != Ordering::Less {
+        q = add(&q, &[1]);
+        r = sub(&r, b);
+    }
+    (q, r)
+}
+
+/// Return `(a / b, a % b)`.
+pub(super) fn divmod(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "attempt to divide by zero");
+    if cmp(a, b) == Ordering::Less {
+        (vec![], a.to_vec())
+    } else if b.len() == 1 {
+        let (q, r) = div_small(a, b[0]);
+        (q, if r == 0 { vec![] } else { vec![r] })
+    } else if b.len().min(a.len() - b.len()) <= DIV_NEWTON_THRESHOLD {
+        divmod_knuth(a, b)
+    } else {
+        divmod_newton(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    fn random_limbs(rng: &mut Xorshift, n: usize) -> Vec<u64> {
+        let mut a: Vec<u64> = (0..n)
+            .map(|_| match rng.random(0..4) {
+                0 => 0,
+                1 => !0,
+                _ => rng.random(..),
+            })
+            .collect();
+        trim(&mut a);
+        a
+    }
+
+    #[test]
+    fn test_mul() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let n = rng.random(0usize..=300);
+            let m = rng.random(0usize..=300);
+            let a = random_limbs(&mut rng, n);
+            let b = random_limbs(&mut rng, m);
+            let c = mul(&a, &b);
+            assert_eq!(c, mul_naive(&a, &b));
+            if !a.is_empty() && !b.is_empty() {
+                assert_eq!(mul_ntt(&a, &b), c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_divmod_knuth() {
+        let mut rng = Xorshift::default();
+        for _ in 0..2000 {
+            let n = rng.random(0usize..=10);
+            let m = rng.random(1usize..=10);
+            let a = random_limbs(&mut rng, n);
+            let b = random_limbs(&mut rng, m);
+            if b.is_empty() {
+                continue;
+            }
+            let (q, r) = divmod(&a, &b);
+            assert_eq!(cmp(&r, &b), Ordering::Less);
+            assert_eq!(add(&mul(&q, &b), &r), a);
+        }
+    }
+
+    #[test]
+    fn test_divmod_newton() {
+        let mut rng = Xorshift::default();
+        for _ in 0..30 {
+            let m = rng.random(2usize..=400);
+            let n = m + rng.random(0usize..=400);
+            let a = random_limbs(&mut rng, n);
+            let b = random_limbs(&mut rng, m);
+            if b.len() < 2 || cmp(&a, &b) == Ordering::Less {
+                continue;
+            }
+            let (q, r) = divmod_newton(&a, &b);
+            assert_eq!((q.clone(), r.clone()), divmod_knuth(&a, &b));
+            assert_eq!(add(&mul(&q, &b), &r), a);
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let n = rng.random(0usize..=4);
+            let a = random_limbs(&mut rng, n);
+            let s = rng.random(0usize..=200);
+            let b = shl(&a, s);
+            assert_eq!(shr(&b, s), a);
+            let mut p = vec![0u64; s / 64];
+            p.push(1 << (s % 64));
+            assert_eq!(b, mul(&a, &p));
+        }
+    }
+}
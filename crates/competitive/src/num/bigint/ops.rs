@@ -0,0 +1,364 @@
+use super::*;
+use std::{
+    iter::{Product, Sum},
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div,
+        DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub,
+        SubAssign,
+    },
+};
+
+macro_rules! impl_binop {
+    (impl $Trait:ident for $t:ty, $method:ident, $f:expr) => {
+        impl $Trait<$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> Self::Output {
+                $f(&self, &rhs)
+            }
+        }
+
+        impl $Trait<&$t> for $t {
+            type Output = $t;
+
+            fn $method(self, rhs: &$t) -> Self::Output {
+                $f(&self, rhs)
+            }
+        }
+
+        impl $Trait<$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: $t) -> Self::Output {
+                $f(self, &rhs)
+            }
+        }
+
+        impl $Trait<&$t> for &$t {
+            type Output = $t;
+
+            fn $method(self, rhs: &$t) -> Self::Output {
+                $f(self, rhs)
+            }
+        }
+    };
+}
+macro_rules! impl_binop_assign {
+    (impl $Trait:ident for $t:ty, $method:ident, $f:expr) => {
+        impl $Trait<$t> for $t {
+            fn $method(&mut self, rhs: $t) {
+                *self = $f(self, &rhs);
+            }
+        }
+
+        impl $Trait<&$t> for $t {
+            fn $method(&mut self, rhs: &$t) {
+                *self = $f(self, rhs);
+            }
+        }
+    };
+}
+
+fn add_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    BigUint {
+        limbs: limbs::add(&lhs.limbs, &rhs.limbs),
+    }
+}
+
+fn sub_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    assert!(lhs >= rhs, "attempt to subtract with overflow");
+    BigUint {
+        limbs: limbs::sub(&lhs.limbs, &rhs.limbs),
+    }
+}
+
+fn mul_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    BigUint {
+        limbs: limbs::mul(&lhs.limbs, &rhs.limbs),
+    }
+}
+
+fn div_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    lhs.div_rem(rhs).0
+}
+
+fn rem_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    lhs.div_rem(rhs).1
+}
+
+fn bitwise(lhs: &BigUint, rhs: &BigUint, f: impl Fn(u64, u64) -> u64) -> BigUint {
+    let n = lhs.limbs.len().max(rhs.limbs.len());
+    BigUint::from_limbs(
+        (0..n)
+            .map(|i| {
+                f(
+                    lhs.limbs.get(i).copied().unwrap_or(0),
+                    rhs.limbs.get(i).copied().unwrap_or(0),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn bitand_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    bitwise(lhs, rhs, |x, y| x & y)
+}
+
+fn bitor_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    bitwise(lhs, rhs, |x, y| x | y)
+}
+
+fn bitxor_uint(lhs: &BigUint, rhs: &BigUint) -> BigUint {
+    bitwise(lhs, rhs, |x, y| x ^ y)
+}
+
+impl_binop!(impl Add for BigUint, add, add_uint);
+impl_binop!(impl Sub for BigUint, sub, sub_uint);
+impl_binop!(impl Mul for BigUint, mul, mul_uint);
+impl_binop!(impl Div for BigUint, div, div_uint);
+impl_binop!(impl Rem for BigUint, rem, rem_uint);
+impl_binop!(impl BitAnd for BigUint, bitand, bitand_uint);
+impl_binop!(impl BitOr for BigUint, bitor, bitor_uint);
+impl_binop!(impl BitXor for BigUint, bitxor, bitxor_uint);
+impl_binop_assign!(impl AddAssign for BigUint, add_assign, add_uint);
+impl_binop_assign!(impl SubAssign for BigUint, sub_assign, sub_uint);
+impl_binop_assign!(impl MulAssign for BigUint, mul_assign, mul_uint);
+impl_binop_assign!(impl DivAssign for BigUint, div_assign, div_uint);
+impl_binop_assign!(impl RemAssign for BigUint, rem_assign, rem_uint);
+impl_binop_assign!(impl BitAndAssign for BigUint, bitand_assign, bitand_uint);
+impl_binop_assign!(impl BitOrAssign for BigUint, bitor_assign, bitor_uint);
+impl_binop_assign!(impl BitXorAssign for BigUint, bitxor_assign, bitxor_uint);
+
+fn add_signed(lhs_negative: bool, lhs: &BigUint, rhs_negative: bool, rhs: &BigUint) -> BigInt {
+    if lhs_negative == rhs_negative {
+        BigInt::from_parts(lhs_negative, lhs + rhs)
+    } else if lhs >= rhs {
+        BigInt::from_parts(lhs_negative, lhs - rhs)
+    } else {
+        BigInt::from_parts(rhs_negative, rhs - lhs)
+    }
+}
+
+fn add_int(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    add_signed(lhs.negative, &lhs.magnitude, rhs.negative, &rhs.magnitude)
+}
+
+fn sub_int(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    add_signed(lhs.negative, &lhs.magnitude, !rhs.negative, &rhs.magnitude)
+}
+
+fn mul_int(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    BigInt::from_parts(
+        lhs.negative != rhs.negative,
+        &lhs.magnitude * &rhs.magnitude,
+    )
+}
+
+fn div_int(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    lhs.div_rem(rhs).0
+}
+
+fn rem_int(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    lhs.div_rem(rhs).1
+}
+
+impl_binop!(impl Add for BigInt, add, add_int);
+impl_binop!(impl Sub for BigInt, sub, sub_int);
+impl_binop!(impl Mul for BigInt, mul, mul_int);
+impl_binop!(impl Div for BigInt, div, div_int);
+impl_binop!(impl Rem for BigInt, rem, rem_int);
+impl_binop_assign!(impl AddAssign for BigInt, add_assign, add_int);
+impl_binop_assign!(impl SubAssign for BigInt, sub_assign, sub_int);
+impl_binop_assign!(impl MulAssign for BigInt, mul_assign, mul_int);
+impl_binop_assign!(impl DivAssign for BigInt, div_assign, div_int);
+impl_binop_assign!(impl RemAssign for BigInt, rem_assign, rem_int);
+
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+fn shl_uint(lhs: &BigUint, rhs: usize) -> BigUint {
+    BigUint {
+        limbs: limbs::shl(&lhs.limbs, rhs),
+    }
+}
+
+fn shr_uint(lhs: &BigUint, rhs: usize) -> BigUint {
+    BigUint {
+        limbs: limbs::shr(&lhs.limbs, rhs),
+    }
+}
+
+fn shl_int(lhs: &BigInt, rhs: usize) -> BigInt {
+    BigInt::from_parts(lhs.negative, &lhs.magnitude << rhs)
+}
+
+/// rounding toward negative infinity as primitive integers
+fn shr_int(lhs: &BigInt, rhs: usize) -> BigInt {
+    if lhs.negative {
+        let m = (&lhs.magnitude - BigUint::one()) >> rhs;
+        BigInt::from_parts(true, m + BigUint::one())
+    } else {
+        BigInt::from_parts(false, &lhs.magnitude >> rhs)
+    }
+}
+
+macro_rules! impl_shift {
+    ($($t:ty, $Trait:ident, $method:ident, $TraitAssign:ident, $method_assign:ident, $f:ident;)*) => {
+        $(
+            impl $Trait<usize> for $t {
+                type Output = $t;
+
+                fn $method(self, rhs: usize) -> Self::Output {
+                    $f(&self, rhs)
+                }
+            }
+
+            impl $Trait<usize> for &$t {
+                type Output = $t;
+
+                fn $method(self, rhs: usize) -> Self::Output {
+                    $f(self, rhs)
+                }
+            }
+
+            impl $TraitAssign<usize> for $t {
+                fn $method_assign(&mut self, rhs: usize) {
+                    *self = $f(self, rhs);
+                }
+            }
+        )*
+    };
+}
+impl_shift!(
+    BigUint, Shl, shl, ShlAssign, shl_assign, shl_uint;
+    BigUint, Shr, shr, ShrAssign, shr_assign, shr_uint;
+    BigInt, Shl, shl, ShlAssign, shl_assign, shl_int;
+    BigInt, Shr, shr, ShrAssign, shr_assign, shr_int;
+);
+
+macro_rules! impl_sum_product {
+    ($($t:ty)*) => {
+        $(
+            impl Sum for $t {
+                fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::zero(), |acc, x| acc + x)
+                }
+            }
+
+            impl<'a> Sum<&'a $t> for $t {
+                fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> Self {
+                    iter.fold(Self::zero(), |acc, x| acc + x)
+                }
+            }
+
+            impl Product for $t {
+                fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::one(), |acc, x| acc * x)
+                }
+            }
+
+            impl<'a> Product<&'a $t> for $t {
+                fn product<I: Iterator<Item = &'a $t>>(iter: I) -> Self {
+                    iter.fold(Self::one(), |acc, x| acc * x)
+                }
+            }
+        )*
+    };
+}
+impl_sum_product!(BigUint BigInt);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    fn random_i128(rng: &mut Xorshift) -> i128 {
+        rng.random(i128::MIN..=i128::MAX) >> rng.random(0u32..128)
+    }
+
+    #[test]
+    fn test_arith_small() {
+        let mut rng = Xorshift::default();
+        for _ in 0..3000 {
+            let a = random_i128(&mut rng) >> 1;
+            let b = random_i128(&mut rng) >> 1;
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            assert_eq!(&x + &y, BigInt::from(a + b));
+            assert_eq!(&x - &y, BigInt::from(a - b));
+            assert_eq!(-&x, BigInt::from(-a));
+            if let Some(c) = a.checked_mul(b) {
+                assert_eq!(&x * &y, BigInt::from(c));
+            }
+            if b != 0 {
+                assert_eq!(&x / &y, BigInt::from(a / b));
+                assert_eq!(&x % &y, BigInt::from(a % b));
+            }
+            let s = rng.random(0usize..100);
+            assert_eq!(&x >> s, BigInt::from(a >> s.min(127)));
+            let (ua, ub) = (a.unsigned_abs(), b.unsigned_abs());
+            let (x, y) = (BigUint::from(ua), BigUint::from(ub));
+            assert_eq!(&x & &y, BigUint::from(ua & ub));
+            assert_eq!(&x | &y, BigUint::from(ua | ub));
+            assert_eq!(&x ^ &y, BigUint::from(ua ^ ub));
+            if ua >= ub {
+                assert_eq!(&x - &y, BigUint::from(ua - ub));
+            }
+            if let Some(c) = ua.checked_shl(s as u32).filter(|c| c >> s == ua) {
+                assert_eq!(&x << s, BigUint::from(c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_arith_large() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let n = rng.random(0usize..=200);
+            let m = rng.random(1usize..=200);
+            let a = BigInt::from_parts(
+                rng.gen_bool(0.5),
+                BigUint::from_limbs(rng.random_iter(..).take(n).collect()),
+            );
+            let b = BigInt::from_parts(
+                rng.gen_bool(0.5),
+                BigUint::from_limbs(rng.random_iter(..).take(m).collect()),
+            );
+            if b.is_zero() {
+                continue;
+            }
+            let (q, r) = a.div_rem(&b);
+            assert!(r.magnitude < b.magnitude);
+            assert!(r.is_zero() || r.negative == a.negative);
+            assert_eq!(&q * &b + &r, a);
+            assert_eq!(&a + &b - &b, a);
+            assert_eq!((&a << 100) >> 100, a);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sub_overflow() {
+        let _ = BigUint::one() - BigUint::from(2u32);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let a: Vec<_> = (1..=30u32).map(BigUint::from).collect();
+        let expected: BigUint = "265252859812191058636308480000000".parse().unwrap();
+        assert_eq!(a.iter().product::<BigUint>(), expected);
+        assert_eq!(a.into_iter().sum::<BigUint>(), BigUint::from(465u32));
+    }
+}
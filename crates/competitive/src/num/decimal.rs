@@ -1,8 +1,78 @@
 use super::{IterScan, One, Zero};
-use std::{cmp::Ordering, ops::Neg};
+use crate::{
+    algebra::DotProduct,
+    math::{ConvolveSteps, U64Convolve},
+};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Neg,
+};
+
+pub use self::round::RoundingMode;
+
+macro_rules! impl_binop {
+    (impl $Trait:ident for Decimal, $method:ident, $macro:ident) => {
+        impl $Trait<Decimal> for Decimal {
+            type Output = Decimal;
+
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                let precision = self.precision.max(rhs.precision);
+                $macro!(self, self, rhs, rhs).inherit_precision(precision)
+            }
+        }
+
+        impl $Trait<&Decimal> for Decimal {
+            type Output = Decimal;
+
+            fn $method(self, rhs: &Decimal) -> Self::Output {
+                let precision = self.precision.max(rhs.precision);
+                $macro!(self, self, rhs, rhs.clone()).inherit_precision(precision)
+            }
+        }
+
+        impl $Trait<Decimal> for &Decimal {
+            type Output = Decimal;
+
+            fn $method(self, rhs: Decimal) -> Self::Output {
+                let precision = self.precision.max(rhs.precision);
+                $macro!(self, self.clone(), rhs, rhs).inherit_precision(precision)
+            }
+        }
+
+        impl $Trait<&Decimal> for &Decimal {
+            type Output = Decimal;
+
+            fn $method(self, rhs: &Decimal) -> Self::Output {
+                let precision = self.precision.max(rhs.precision);
+                $macro!(self, self.clone(), rhs, rhs.clone()).inherit_precision(precision)
+            }
+        }
+    };
+}
+macro_rules! impl_binop_assign {
+    (impl $Trait:ident for Decimal, $method:ident, $op:tt) => {
+        impl $Trait for Decimal {
+            fn $method(&mut self, rhs: Decimal) {
+                let lhs = ::std::mem::replace(self, ZERO);
+                *self = lhs $op rhs;
+            }
+        }
+
+        impl $Trait<&Decimal> for Decimal {
+            fn $method(&mut self, rhs: &Decimal) {
+                let lhs = ::std::mem::replace(self, ZERO);
+                *self = lhs $op rhs;
+            }
+        }
+    };
+}
 
 pub mod addsub;
 pub mod convert;
+pub mod div;
+pub mod mul;
+pub mod round;
 
 #[derive(PartialEq, PartialOrd, Eq, Ord, Copy, Clone, Debug, Hash)]
 enum Sign {
@@ -27,6 +97,7 @@ const ZERO: Decimal = Decimal {
     sign: Sign::Zero,
     integer: Vec::new(),
     decimal: Vec::new(),
+    precision: None,
 };
 
 const POW10: [u64; RADIX_LEN + 1] = [
@@ -54,11 +125,32 @@ const POW10: [u64; RADIX_LEN + 1] = [
 const RADIX: u64 = POW10[RADIX_LEN];
 const RADIX_LEN: usize = 18;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Arbitrary-precision decimal number.
+///
+/// `/` rounds toward zero to the number of fractional digits carried by the operands, see [`Decimal::with_precision`].
+#[derive(Clone, Debug)]
 pub struct Decimal {
     sign: Sign,
     integer: Vec<u64>,
     decimal: Vec<u64>,
+    /// fractional digits kept by `/`, not a part of the value
+    precision: Option<usize>,
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.sign == other.sign && self.integer == other.integer && self.decimal == other.decimal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sign.hash(state);
+        self.integer.hash(state);
+        self.decimal.hash(state);
+    }
 }
 
 impl Default for Decimal {
@@ -83,10 +175,13 @@ impl One for Decimal {
             sign: Sign::Plus,
             integer: vec![1],
             decimal: Vec::new(),
+            precision: None,
         }
     }
 }
 
+impl DotProduct for Decimal {}
+
 impl PartialOrd for Decimal {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -109,13 +204,26 @@ impl Neg for Decimal {
     fn neg(self) -> Self::Output {
         Self {
             sign: -self.sign,
-            integer: self.integer,
-            decimal: self.decimal,
+            ..self
         }
     }
 }
 
 impl Decimal {
+    /// Set the number of fractional digits kept by `/`.
+    ///
+    /// Arithmetic results carry the larger precision of the operands.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+    fn inherit_precision(mut self, precision: Option<usize>) -> Self {
+        self.precision = precision;
+        self
+    }
     fn cmp_absolute_parts(&self, other: &Self) -> Ordering {
         self.integer
             .len()
@@ -123,6 +231,28 @@ impl Decimal {
             .then_with(|| self.integer.iter().rev().cmp(other.integer.iter().rev()))
             .then_with(|| self.decimal.iter().cmp(other.decimal.iter()))
     }
+    /// Return `(limbs, scale)` such that `|self| = limbs * RADIX^-scale`.
+    fn to_scaled(&self) -> (Vec<u64>, usize) {
+        let mut limbs = Vec::with_capacity(self.decimal.len() + self.integer.len());
+        limbs.extend(self.decimal.iter().rev());
+        limbs.extend(&self.integer);
+        (limbs, self.decimal.len())
+    }
+    fn from_scaled(sign: Sign, mut limbs: Vec<u64>, scale: usize) -> Self {
+        if limbs.len() < scale {
+            limbs.resize(scale, 0);
+        }
+        let integer = limbs.split_off(scale);
+        limbs.reverse();
+        let mut res = Decimal {
+            sign,
+            integer,
+            decimal: limbs,
+            precision: None,
+        };
+        res.normalize();
+        res
+    }
     fn normalize(&mut self) {
         if let Some(&0) = self.decimal.last() {
             let len = self
@@ -155,6 +285,10 @@ impl Decimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        algebra::AddMulOperation,
+        math::{Matrix, Polynomial},
+    };
     use test_case::test_case;
 
     #[test_case("0", "0", Ordering::Equal; "zero")]
@@ -186,4 +320,51 @@ mod tests {
         let expected = expected.parse::<Decimal>().unwrap();
         assert_eq!(-a, expected);
     }
+
+    #[test]
+    fn test_matrix() {
+        let parse = |a: &[&[&str]]| {
+            Matrix::<AddMulOperation<Decimal>>::from_vec(
+                a.iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|x| x.parse::<Decimal>().unwrap().with_precision(60))
+                            .collect()
+                    })
+                    .collect(),
+            )
+        };
+        let mut a = parse(&[&["0", "1.5", "2"], &["0.5", "3", "-1"], &["4", "0.25", "8"]]);
+        assert_eq!(
+            a.determinant().round(50, RoundingMode::HalfEven),
+            "-35.75".parse().unwrap()
+        );
+        let b = parse(&[&["2", "1"], &["4", "4"]]);
+        let expected = parse(&[&["1", "-0.25"], &["-1", "0.5"]]);
+        assert_eq!(b.inverse(), Some(expected));
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let parse = |a: &[&str]| {
+            Polynomial::from_vec(
+                a.iter()
+                    .map(|x| x.parse::<Decimal>().unwrap().with_precision(20))
+                    .collect(),
+            )
+        };
+        // (x + 0.5) (2x - 1.5) = 2x^2 - 0.5x - 0.75
+        let p = parse(&["0.5", "1"]);
+        let q = parse(&["-1.5", "2"]);
+        let pq = &p * &q;
+        assert_eq!(pq, parse(&["-0.75", "-0.5", "2"]));
+        assert_eq!(&pq / &q, p);
+        assert_eq!(&(&pq + &p) % &q, parse(&["1.25"]));
+        assert_eq!(&pq - &pq, parse(&["0", "0", "0"]));
+        assert_eq!(pq.assign("0.1".parse().unwrap()), "-0.78".parse().unwrap());
+        assert_eq!(
+            &parse(&["1"]) / &parse(&["3"]),
+            parse(&["0.33333333333333333333"])
+        );
+    }
 }
@@ -1,8 +1,5 @@
 use super::*;
-use std::{
-    mem::replace,
-    ops::{Add, AddAssign, Sub, SubAssign},
-};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 fn add_carry(carry: bool, lhs: u64, rhs: u64, out: &mut u64) -> bool {
     let mut sum = lhs + rhs + carry as u64;
@@ -215,62 +212,9 @@ macro_rules! sub {
     };
 }
 
-macro_rules! impl_binop {
-    (impl $Trait:ident for Decimal, $method:ident, $macro:ident) => {
-        impl $Trait<Decimal> for Decimal {
-            type Output = Decimal;
-
-            fn $method(self, rhs: Decimal) -> Self::Output {
-                $macro!(self, self, rhs, rhs)
-            }
-        }
-
-        impl $Trait<&Decimal> for Decimal {
-            type Output = Decimal;
-
-            fn $method(self, rhs: &Decimal) -> Self::Output {
-                $macro!(self, self, rhs, rhs.clone())
-            }
-        }
-
-        impl $Trait<Decimal> for &Decimal {
-            type Output = Decimal;
-
-            fn $method(self, rhs: Decimal) -> Self::Output {
-                $macro!(self, self.clone(), rhs, rhs)
-            }
-        }
-
-        impl $Trait<&Decimal> for &Decimal {
-            type Output = Decimal;
-
-            fn $method(self, rhs: &Decimal) -> Self::Output {
-                $macro!(self, self.clone(), rhs, rhs.clone())
-            }
-        }
-    };
-}
 impl_binop!(impl Add for Decimal, add, add);
 impl_binop!(impl Sub for Decimal, sub, sub);
 
-macro_rules! impl_binop_assign {
-    (impl $Trait:ident for Decimal, $method:ident, $op:tt) => {
-        impl $Trait for Decimal {
-            fn $method(&mut self, rhs: Decimal) {
-                let lhs = replace(self, ZERO);
-                *self = lhs $op rhs;
-            }
-        }
-
-        impl $Trait<&Decimal> for Decimal {
-            fn $method(&mut self, rhs: &Decimal) {
-                let lhs = replace(self, ZERO);
-                *self = lhs $op rhs;
-            }
-        }
-    };
-}
-
 impl_binop_assign!(impl AddAssign for Decimal, add_assign, +);
 impl_binop_assign!(impl SubAssign for Decimal, sub_assign, -);
 
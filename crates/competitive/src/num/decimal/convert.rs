@@ -95,6 +95,7 @@ impl FromStr for Decimal {
             sign,
             integer,
             decimal,
+            precision: None,
         })
     }
 }
@@ -165,6 +166,7 @@ macro_rules! impl_from_unsigned {
                         sign: Sign::Plus,
                         integer,
                         decimal: Vec::new(),
+                        precision: None,
                     }
                 }
             }
@@ -213,47 +215,47 @@ mod tests {
 
     #[test_case(
         "0",
-        Ok(Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] });
+        Ok(Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None });
         "zero"
     )]
     #[test_case(
         "1",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![], precision: None });
         "plus integer"
     )]
     #[test_case(
         "+1",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![], precision: None });
         "plus integer with plus"
     )]
     #[test_case(
         "-1",
-        Ok(Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![] });
+        Ok(Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![], precision: None });
         "minus integer"
     )]
     #[test_case(
         "1.2",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![200000000000000000] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![200000000000000000], precision: None });
         "plus decimal"
     )]
     #[test_case(
         "-1.2",
-        Ok(Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![200000000000000000] });
+        Ok(Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![200000000000000000], precision: None });
         "minus decimal"
     )]
     #[test_case(
         "000000000000000000001.00000000000000000000",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![], precision: None });
         "zero padding"
     )]
     #[test_case(
         ".1",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![], decimal: vec![100000000000000000] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![], decimal: vec![100000000000000000], precision: None });
         "without integer"
     )]
     #[test_case(
         "12345678901234567890.12345678901234567890",
-        Ok(Decimal { sign: Sign::Plus, integer: vec![345678901234567890, 12], decimal: vec![123456789012345678, 900000000000000000] });
+        Ok(Decimal { sign: Sign::Plus, integer: vec![345678901234567890, 12], decimal: vec![123456789012345678, 900000000000000000], precision: None });
         "long"
     )]
     #[test_case(
@@ -276,42 +278,42 @@ mod tests {
     }
 
     #[test_case(
-        Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] },
+        Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None },
         "0";
         "zero"
     )]
     #[test_case(
-        Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![] },
+        Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![], precision: None },
         "1";
         "plus integer"
     )]
     #[test_case(
-        Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![] },
+        Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![], precision: None },
         "-1";
         "minus integer"
     )]
     #[test_case(
-        Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![200000000000000000] },
+        Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![200000000000000000], precision: None },
         "1.2";
         "plus decimal"
     )]
     #[test_case(
-        Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![200000000000000000] },
+        Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![200000000000000000], precision: None },
         "-1.2";
         "minus decimal"
     )]
     #[test_case(
-        Decimal { sign: Sign::Plus, integer: vec![], decimal: vec![100000000000000000] },
+        Decimal { sign: Sign::Plus, integer: vec![], decimal: vec![100000000000000000], precision: None },
         "0.1";
         "without integer"
     )]
     #[test_case(
-        Decimal { sign: Sign::Plus, integer: vec![345678901234567890, 12], decimal: vec![123456789012345678, 900000000000000000] },
+        Decimal { sign: Sign::Plus, integer: vec![345678901234567890, 12], decimal: vec![123456789012345678, 900000000000000000], precision: None },
         "12345678901234567890.1234567890123456789";
         "long"
     )]
     #[test_case(
-        Decimal { sign: Sign::Plus, integer: vec![0], decimal: vec![1] },
+        Decimal { sign: Sign::Plus, integer: vec![0], decimal: vec![1], precision: None },
         "0.000000000000000001";
         "small decimal"
     )]
@@ -319,37 +321,37 @@ mod tests {
         assert_eq!(expected, decimal.to_string());
     }
 
-    #[test_case(u8::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "u8 zero")]
-    #[test_case(u8::MAX, Decimal { sign: Sign::Plus, integer: vec![255], decimal: vec![] }; "u8 max")]
-    #[test_case(u16::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "u16 zero")]
-    #[test_case(u16::MAX, Decimal { sign: Sign::Plus, integer: vec![65535], decimal: vec![] }; "u16 max")]
-    #[test_case(u32::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "u32 zero")]
-    #[test_case(u32::MAX, Decimal { sign: Sign::Plus, integer: vec![4294967295], decimal: vec![] }; "u32 max")]
-    #[test_case(u64::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "u64 zero")]
-    #[test_case(u64::MAX, Decimal { sign: Sign::Plus, integer: vec![446744073709551615, 18], decimal: vec![] }; "u64 max")]
-    #[test_case(u128::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "u128 zero")]
-    #[test_case(u128::MAX, Decimal { sign: Sign::Plus, integer: vec![374607431768211455, 282366920938463463, 340], decimal: vec![] }; "u128 max")]
-    #[test_case(i8::MIN, Decimal { sign: Sign::Minus, integer: vec![128], decimal: vec![] }; "i8 min")]
-    #[test_case(0i8, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "i8 zero")]
-    #[test_case(i8::MAX, Decimal { sign: Sign::Plus, integer: vec![127], decimal: vec![] }; "i8 max")]
-    #[test_case(i16::MIN, Decimal { sign: Sign::Minus, integer: vec![32768], decimal: vec![] }; "i16 min")]
-    #[test_case(0i16, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "i16 zero")]
-    #[test_case(i16::MAX, Decimal { sign: Sign::Plus, integer: vec![32767], decimal: vec![] }; "i16 max")]
-    #[test_case(i32::MIN, Decimal { sign: Sign::Minus, integer: vec![2147483648], decimal: vec![] }; "i32 min")]
-    #[test_case(0i32, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "i32 zero")]
-    #[test_case(i32::MAX, Decimal { sign: Sign::Plus, integer: vec![2147483647], decimal: vec![] }; "i32 max")]
-    #[test_case(i64::MIN, Decimal { sign: Sign::Minus, integer: vec![223372036854775808, 9], decimal: vec![] }; "i64 min")]
-    #[test_case(0i64, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "i64 zero")]
-    #[test_case(i64::MAX, Decimal { sign: Sign::Plus, integer: vec![223372036854775807, 9], decimal: vec![] }; "i64 max")]
-    #[test_case(i128::MIN, Decimal { sign: Sign::Minus, integer: vec![687303715884105728, 141183460469231731, 170], decimal: vec![] }; "i128 min")]
-    #[test_case(0i128, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "i128 zero")]
-    #[test_case(i128::MAX, Decimal { sign: Sign::Plus, integer: vec![687303715884105727, 141183460469231731, 170], decimal: vec![] }; "i128 max")]
-    #[test_case(0f32, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "f32 zero")]
-    #[test_case(1.1f32, Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![100000000000000000] }; "f32 plus")]
-    #[test_case(-1.1f32, Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![100000000000000000] }; "f32 minus")]
-    #[test_case(0f64, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![] }; "f64 zero")]
-    #[test_case(1.1f64, Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![100000000000000000] }; "f64 plus")]
-    #[test_case(-1.1f64, Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![100000000000000000] }; "f64 minus")]
+    #[test_case(u8::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "u8 zero")]
+    #[test_case(u8::MAX, Decimal { sign: Sign::Plus, integer: vec![255], decimal: vec![], precision: None }; "u8 max")]
+    #[test_case(u16::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "u16 zero")]
+    #[test_case(u16::MAX, Decimal { sign: Sign::Plus, integer: vec![65535], decimal: vec![], precision: None }; "u16 max")]
+    #[test_case(u32::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "u32 zero")]
+    #[test_case(u32::MAX, Decimal { sign: Sign::Plus, integer: vec![4294967295], decimal: vec![], precision: None }; "u32 max")]
+    #[test_case(u64::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "u64 zero")]
+    #[test_case(u64::MAX, Decimal { sign: Sign::Plus, integer: vec![446744073709551615, 18], decimal: vec![], precision: None }; "u64 max")]
+    #[test_case(u128::MIN, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "u128 zero")]
+    #[test_case(u128::MAX, Decimal { sign: Sign::Plus, integer: vec![374607431768211455, 282366920938463463, 340], decimal: vec![], precision: None }; "u128 max")]
+    #[test_case(i8::MIN, Decimal { sign: Sign::Minus, integer: vec![128], decimal: vec![], precision: None }; "i8 min")]
+    #[test_case(0i8, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "i8 zero")]
+    #[test_case(i8::MAX, Decimal { sign: Sign::Plus, integer: vec![127], decimal: vec![], precision: None }; "i8 max")]
+    #[test_case(i16::MIN, Decimal { sign: Sign::Minus, integer: vec![32768], decimal: vec![], precision: None }; "i16 min")]
+    #[test_case(0i16, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "i16 zero")]
+    #[test_case(i16::MAX, Decimal { sign: Sign::Plus, integer: vec![32767], decimal: vec![], precision: None }; "i16 max")]
+    #[test_case(i32::MIN, Decimal { sign: Sign::Minus, integer: vec![2147483648], decimal: vec![], precision: None }; "i32 min")]
+    #[test_case(0i32, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "i32 zero")]
+    #[test_case(i32::MAX, Decimal { sign: Sign::Plus, integer: vec![2147483647], decimal: vec![], precision: None }; "i32 max")]
+    #[test_case(i64::MIN, Decimal { sign: Sign::Minus, integer: vec![223372036854775808, 9], decimal: vec![], precision: None }; "i64 min")]
+    #[test_case(0i64, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "i64 zero")]
+    #[test_case(i64::MAX, Decimal { sign: Sign::Plus, integer: vec![223372036854775807, 9], decimal: vec![], precision: None }; "i64 max")]
+    #[test_case(i128::MIN, Decimal { sign: Sign::Minus, integer: vec![687303715884105728, 141183460469231731, 170], decimal: vec![], precision: None }; "i128 min")]
+    #[test_case(0i128, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "i128 zero")]
+    #[test_case(i128::MAX, Decimal { sign: Sign::Plus, integer: vec![687303715884105727, 141183460469231731, 170], decimal: vec![], precision: None }; "i128 max")]
+    #[test_case(0f32, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "f32 zero")]
+    #[test_case(1.1f32, Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![100000000000000000], precision: None }; "f32 plus")]
+    #[test_case(-1.1f32, Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![100000000000000000], precision: None }; "f32 minus")]
+    #[test_case(0f64, Decimal { sign: Sign::Zero, integer: vec![], decimal: vec![], precision: None }; "f64 zero")]
+    #[test_case(1.1f64, Decimal { sign: Sign::Plus, integer: vec![1], decimal: vec![100000000000000000], precision: None }; "f64 plus")]
+    #[test_case(-1.1f64, Decimal { sign: Sign::Minus, integer: vec![1], decimal: vec![100000000000000000], precision: None }; "f64 minus")]
     fn test_from(val: impl Into<Decimal>, expected: Decimal) {
         assert_eq!(expected, val.into());
     }
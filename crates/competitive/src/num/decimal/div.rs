@@ -0,0 +1,405 @@
+use super::{
+    mul::mul_limbs,
+    round::{RoundingMode, round_scaled},
+    *,
+};
+use std::ops::{Div, DivAssign, Rem, RemAssign};
+
+fn trim(limbs: &mut Vec<u64>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+/// Compare trimmed little-endian limbs.
+fn cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut c = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let mut t = x + b.get(i).copied().unwrap_or(0) + carry;
+        carry = (t >= RADIX) as u64;
+        if carry == 1 {
+            t -= RADIX;
+        }
+        c.push(t);
+    }
+    if carry > 0 {
+        c.push(carry);
+    }
+    c
+}
+
+/// Multiply little-endian limbs by `m < RADIX`.
+pub(super) fn mul_small(limbs: &mut Vec<u64>, m: u64) {
+    let mut carry = 0u128;
+    for x in limbs.iter_mut() {
+        let t = *x as u128 * m as u128 + carry;
+        *x = (t % RADIX as u128) as u64;
+        carry = t / RADIX as u128;
+    }
+    if carry > 0 {
+        limbs.push(carry as u64);
+    }
+}
+
+/// Divide little-endian limbs by `d < RADIX` and return the remainder.
+pub(super) fn div_small(limbs: &mut [u64], d: u64) -> u64 {
+    let mut rem = 0u128;
+    for x in limbs.iter_mut().rev() {
+        let t = rem * RADIX as u128 + *x as u128;
+        *x = (t / d as u128) as u64;
+        rem = t % d as u128;
+    }
+    rem as u64
+}
+
+/// Quotient and remainder of trimmed little-endian limbs, Knuth's algorithm D.
+fn divmod_limbs(u: &[u64], v: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!v.is_empty(), "division by zero");
+    if cmp_limbs(u, v) == Ordering::Less {
+        return (vec![], u.to_vec());
+    }
+    let n = v.len();
+    if n == 1 {
+        let mut q = u.to_vec();
+        let r = div_small(&mut q, v[0]);
+        trim(&mut q);
+        return (q, if r == 0 { vec![] } else { vec![r] });
+    }
+    let m = u.len() - n;
+    let d = RADIX / (v[n - 1] + 1);
+    let mut un = u.to_vec();
+    mul_small(&mut un, d);
+    un.resize(u.len() + 1, 0);
+    let mut vn = v.to_vec();
+    mul_small(&mut vn, d);
+    debug_assert_eq!(vn.len(), n);
+    let (r, vt, vs) = (RADIX as u128, vn[n - 1] as u128, vn[n - 2] as u128);
+    let mut q = vec![0u64; m + 1];
+    for j in (0..=m).rev() {
+        let num = un[j + n] as u128 * r + un[j + n - 1] as u128;
+        let mut qhat = num / vt;
+        let mut rhat = num % vt;
+        while qhat >= r || qhat * vs > rhat * r + un[j + n - 2] as u128 {
+            qhat -= 1;
+            rhat += vt;
+            if rhat >= r {
+                break;
+            }
+        }
+        let mut carry = 0u128;
+        let mut borrow = 0i128;
+        for i in 0..n {
+            let p = qhat * vn[i] as u128 + carry;
+            carry = p / r;
+            let t = un[i + j] as i128 - (p % r) as i128 + borrow;
+            (un[i + j], borrow) = if t < 0 {
+                ((t + r as i128) as u64, -1)
+            } else {
+                (t as u64, 0)
+            };
+        }
+        let t = un[j + n] as i128 - carry as i128 + borrow;
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0;
+            for i in 0..n {
+                let s = un[i + j] + vn[i] + carry;
+                carry = (s >= RADIX) as u64;
+                un[i + j] = if carry == 1 { s - RADIX } else { s };
+            }
+            un[j + n] = (t + r as i128 + carry as i128) as u64 % RADIX;
+        } else {
+            un[j + n] = t as u64;
+        }
+        q[j] = qhat as u64;
+    }
+    un.truncate(n);
+    div_small(&mut un, d);
+    trim(&mut q);
+    trim(&mut un);
+    (q, un)
+}
+
+/// Floor of the square root of trimmed little-endian limbs.
+fn sqrt_limbs(a: &[u64]) -> Vec<u64> {
+    if a.is_empty() {
+        return vec![];
+    }
+    let h = a.len().div_ceil(2);
+    let top = a[2 * (h - 1)..]
+        .iter()
+        .rev()
+        .fold(0u128, |acc, &x| acc * RADIX as u128 + x as u128);
+    let est = ((top + 1) as f64).sqrt() * (1. + 1e-12) + 1.;
+    let est = est as u128;
+    let mut x = vec![0u64; h - 1];
+    x.push((est % RADIX as u128) as u64);
+    x.push((est / RADIX as u128) as u64);
+    trim(&mut x);
+    loop {
+        let (q, _) = divmod_limbs(a, &x);
+        let mut y = add_limbs(&x, &q);
+        div_small(&mut y, 2);
+        trim(&mut y);
+        if cmp_limbs(&y, &x) != Ordering::Less {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl Decimal {
+    /// Return `self / rhs` rounded to `precision` fractional digits.
+    pub fn div_with_precision(&self, rhs: &Self, precision: usize, mode: RoundingMode) -> Self {
+        assert!(!rhs.is_zero(), "division by zero");
+        let carried = self.precision.max(rhs.precision);
+        if self.is_zero() {
+            return ZERO.inherit_precision(carried);
+        }
+        let sign = if self.sign == rhs.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        let (a, ka) = self.to_scaled();
+        let (mut b, kb) = rhs.to_scaled();
+        let t = precision.div_ceil(RADIX_LEN) + 1;
+        let mut a = [vec![0; kb + t], a].concat();
+        trim(&mut a);
+        trim(&mut b);
+        let (q, r) = divmod_limbs(&a, &b);
+        round_scaled(sign, q, ka + t, precision, mode, !r.is_empty()).inherit_precision(carried)
+    }
+
+    /// Return `(q, r)` such that `q` is the truncated integer quotient and `self = rhs * q + r`.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let q = self.div_with_precision(rhs, 0, RoundingMode::Down);
+        let r = self - rhs * &q;
+        (q, r)
+    }
+
+    /// Return the square root rounded to `precision` fractional digits.
+    pub fn sqrt(&self, precision: usize, mode: RoundingMode) -> Self {
+        assert!(self.sign != Sign::Minus, "square root of negative number");
+        if self.is_zero() {
+            return ZERO.inherit_precision(self.precision);
+        }
+        let (a, k) = self.to_scaled();
+        let t = (precision.div_ceil(RADIX_LEN) + 1).max(k.div_ceil(2));
+        let mut a = [vec![0; 2 * t - k], a].concat();
+        trim(&mut a);
+        let s = sqrt_limbs(&a);
+        let mut ss = mul_limbs(&s, &s);
+        trim(&mut ss);
+        let sticky = ss != a;
+        round_scaled(Sign::Plus, s, t, precision, mode, sticky).inherit_precision(self.precision)
+    }
+}
+
+macro_rules! div {
+    ($lhs:expr, $lhs_owned:expr, $rhs:expr, $rhs_owned:expr) => {
+        $lhs.div_with_precision(
+            &$rhs,
+            $lhs.precision.max($rhs.precision).expect(
+                "precision of `/` must be set by `Decimal::with_precision`, or use `div_with_precision`",
+            ),
+            RoundingMode::Down,
+        )
+    };
+}
+macro_rules! rem {
+    ($lhs:expr, $lhs_owned:expr, $rhs:expr, $rhs_owned:expr) => {
+        $lhs.div_rem(&$rhs).1
+    };
+}
+
+impl_binop!(impl Div for Decimal, div, div);
+impl_binop!(impl Rem for Decimal, rem, rem);
+impl_binop_assign!(impl DivAssign for Decimal, div_assign, /);
+impl_binop_assign!(impl RemAssign for Decimal, rem_assign, %);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+    use test_case::test_case;
+
+    #[test]
+    fn test_divmod_limbs() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let n = rng.random(0usize..=8);
+            let m = rng.random(1usize..=8);
+            let gen_limb = |rng: &mut Xorshift| match rng.random(0..4) {
+                0 => 0,
+                1 => RADIX - 1,
+                _ => rng.random(0..RADIX),
+            };
+            let mut u: Vec<u64> = (0..n).map(|_| gen_limb(&mut rng)).collect();
+            let mut v: Vec<u64> = (0..m).map(|_| gen_limb(&mut rng)).collect();
+            trim(&mut u);
+            trim(&mut v);
+            if v.is_empty() {
+                continue;
+            }
+            let (q, r) = divmod_limbs(&u, &v);
+            assert_eq!(cmp_limbs(&r, &v), Ordering::Less);
+            let mut qv = mul_limbs(&q, &v);
+            trim(&mut qv);
+            let mut e = add_limbs(&qv, &r);
+            trim(&mut e);
+            assert_eq!(e, u);
+        }
+    }
+
+    #[test_case("1", "3", 5, RoundingMode::Down, "0.33333"; "one third")]
+    #[test_case("2", "3", 5, RoundingMode::HalfUp, "0.66667"; "two thirds")]
+    #[test_case("-2", "3", 5, RoundingMode::Floor, "-0.66667"; "floor minus")]
+    #[test_case("0", "-7", 3, RoundingMode::Up, "0"; "zero")]
+    #[test_case("1.5", "0.25", 0, RoundingMode::Down, "6"; "exact")]
+    #[test_case("-10", "-4", 0, RoundingMode::HalfEven, "2"; "half even")]
+    #[test_case(
+        "1",
+        "7",
+        40,
+        RoundingMode::Down,
+        "0.1428571428571428571428571428571428571428";
+        "long"
+    )]
+    #[test_case(
+        "123456789012345678901234567890",
+        "0.000000000000000000003",
+        0,
+        RoundingMode::Down,
+        "41152263004115226300411522630000000000000000000000";
+        "large"
+    )]
+    fn test_div_with_precision(
+        a: &str,
+        b: &str,
+        precision: usize,
+        mode: RoundingMode,
+        expected: &str,
+    ) {
+        let a = a.parse::<Decimal>().unwrap();
+        let b = b.parse::<Decimal>().unwrap();
+        let expected = expected.parse::<Decimal>().unwrap();
+        assert_eq!(a.div_with_precision(&b, precision, mode), expected);
+    }
+
+    #[test_case("7", "2", "3", "1"; "plus")]
+    #[test_case("-7", "2", "-3", "-1"; "minus")]
+    #[test_case("7", "-2", "-3", "1"; "minus rhs")]
+    #[test_case("5.5", "1.5", "3", "1"; "decimal")]
+    #[test_case("1", "3", "0", "1"; "small")]
+    fn test_div_rem(a: &str, b: &str, q: &str, r: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        let b = b.parse::<Decimal>().unwrap();
+        assert_eq!(a.div_rem(&b), (q.parse().unwrap(), r.parse().unwrap()));
+        assert_eq!(&a % &b, r.parse().unwrap());
+    }
+
+    #[test]
+    fn test_div_random() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(-1_000_000_000_000i64..=1_000_000_000_000);
+            let b = rng.random(-1_000_000i64..=1_000_000);
+            if b == 0 {
+                continue;
+            }
+            let precision = rng.random(0usize..=100);
+            let (da, db) = (Decimal::from(a).with_precision(precision), Decimal::from(b));
+            assert_eq!(da.div_rem(&db), ((a / b).into(), (a % b).into()));
+            let q = &da / &db;
+            let r = &da - &db * &q;
+            assert!(r.is_zero() || r.sign == da.sign);
+            let ulp = Decimal::one().div_with_precision(
+                &Decimal::from(10u64).pow(precision),
+                precision,
+                RoundingMode::Down,
+            );
+            let abs = |x: Decimal| if x.sign == Sign::Minus { -x } else { x };
+            assert!(abs(r) < &ulp * abs(db));
+        }
+    }
+
+    #[test]
+    fn test_division_precision() {
+        let two = Decimal::from(2u64).with_precision(3);
+        let three = Decimal::from(3u64);
+        assert_eq!(&two / &three, "0.666".parse().unwrap());
+        assert_eq!((&two * &three).precision(), Some(3));
+        assert_eq!(
+            &two / three.clone().with_precision(5),
+            "0.66666".parse().unwrap()
+        );
+        assert_eq!((-three / &two).precision(), Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "precision of `/` must be set")]
+    fn test_division_without_precision() {
+        let _ = Decimal::from(2u64) / Decimal::from(3u64);
+    }
+
+    #[test_case("0", 5, RoundingMode::Down, "0"; "zero")]
+    #[test_case("4", 5, RoundingMode::Down, "2"; "exact")]
+    #[test_case("0.0001", 5, RoundingMode::Down, "0.01"; "small")]
+    #[test_case(
+        "2",
+        30,
+        RoundingMode::HalfUp,
+        "1.414213562373095048801688724210";
+        "two"
+    )]
+    #[test_case(
+        "2",
+        30,
+        RoundingMode::Down,
+        "1.414213562373095048801688724209";
+        "two down"
+    )]
+    #[test_case(
+        "1000000000000000000000000000000000000000000",
+        3,
+        RoundingMode::Down,
+        "1000000000000000000000";
+        "large"
+    )]
+    #[test_case(
+        "0.00000000000000000000000000000000000003",
+        25,
+        RoundingMode::Down,
+        "0.0000000000000000001732050";
+        "tiny"
+    )]
+    fn test_sqrt(a: &str, precision: usize, mode: RoundingMode, expected: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        let expected = expected.parse::<Decimal>().unwrap();
+        assert_eq!(a.sqrt(precision, mode), expected);
+    }
+
+    #[test]
+    fn test_sqrt_random() {
+        let mut rng = Xorshift::default();
+        for _ in 0..300 {
+            let a = rng.random(0u64..=1_000_000_000_000_000_000);
+            let s = Decimal::from(a).sqrt(0, RoundingMode::Down);
+            let e = (a as f64).sqrt() as u64;
+            let e = (e.saturating_sub(2)..e + 3)
+                .filter(|&x| x as u128 * x as u128 <= a as u128)
+                .max()
+                .unwrap();
+            assert_eq!(s, Decimal::from(e));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use super::*;
+use std::ops::{Mul, MulAssign};
+
+const SPLIT: u64 = 1_000_000;
+const NAIVE_THRESHOLD: usize = 64;
+
+fn mul_naive(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut c = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let t = c[i + j] as u128 + x as u128 * y as u128 + carry;
+            c[i + j] = (t % RADIX as u128) as u64;
+            carry = t / RADIX as u128;
+        }
+        c[i + b.len()] = carry as u64;
+    }
+    c
+}
+
+// exact while min(|a|, |b|) < 6 * 10^6
+fn mul_ntt(a: &[u64], b: &[u64]) -> Vec<u64> {
+    fn split(x: &[u64]) -> Vec<u64> {
+        x.iter()
+            .flat_map(|&x| [x % SPLIT, x / SPLIT % SPLIT, x / (SPLIT * SPLIT)])
+            .collect()
+    }
+    let c = U64Convolve::convolve(split(a), split(b));
+    let mut digits = Vec::with_capacity(c.len() + 8);
+    let mut carry = 0u128;
+    for c in c {
+        let t = c as u128 + carry;
+        digits.push((t % SPLIT as u128) as u64);
+        carry = t / SPLIT as u128;
+    }
+    while carry > 0 {
+        digits.push((carry % SPLIT as u128) as u64);
+        carry /= SPLIT as u128;
+    }
+    digits
+        .chunks(3)
+        .map(|d| d.iter().rev().fold(0, |acc, &d| acc * SPLIT + d))
+        .collect()
+}
+
+/// Product of little-endian limbs.
+pub(super) fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        vec![]
+    } else if a.len().min(b.len()) <= NAIVE_THRESHOLD {
+        mul_naive(a, b)
+    } else {
+        mul_ntt(a, b)
+    }
+}
+
+fn mul_ref(lhs: &Decimal, rhs: &Decimal) -> Decimal {
+    if lhs.is_zero() || rhs.is_zero() {
+        return ZERO;
+    }
+    let sign = if lhs.sign == rhs.sign {
+        Sign::Plus
+    } else {
+        Sign::Minus
+    };
+    let (a, ka) = lhs.to_scaled();
+    let (b, kb) = rhs.to_scaled();
+    Decimal::from_scaled(sign, mul_limbs(&a, &b), ka + kb)
+}
+
+macro_rules! mul {
+    ($lhs:expr, $lhs_owned:expr, $rhs:expr, $rhs_owned:expr) => {
+        mul_ref(&$lhs, &$rhs)
+    };
+}
+
+impl_binop!(impl Mul for Decimal, mul, mul);
+impl_binop_assign!(impl MulAssign for Decimal, mul_assign, *);
+
+impl Decimal {
+    /// Return `self^exp` exactly.
+    pub fn pow(&self, mut exp: usize) -> Self {
+        let mut res = Decimal::one();
+        let mut x = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= &x;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                x = &x * &x;
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+    use test_case::test_case;
+
+    #[test_case("0", "0", "0"; "zero")]
+    #[test_case("0", "-1.5", "0"; "zero vs minus")]
+    #[test_case("2", "3", "6"; "plus vs plus")]
+    #[test_case("-2", "3", "-6"; "minus vs plus")]
+    #[test_case("-2", "-3", "6"; "minus vs minus")]
+    #[test_case("1.5", "0.2", "0.3"; "decimal")]
+    #[test_case(
+        "999999999999999999",
+        "999999999999999999",
+        "999999999999999998000000000000000001";
+        "carry"
+    )]
+    #[test_case(
+        "0.000000000000000001",
+        "0.000000000000000001",
+        "0.000000000000000000000000000000000001";
+        "long decimal"
+    )]
+    #[test_case(
+        "12345678901234567890.0987654321",
+        "-98765432109876543210.123456789",
+        "-1219326311370217952248742569565310166255.3040695141112635269";
+        "long"
+    )]
+    fn test_mul(a: &str, b: &str, expected: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        let b = b.parse::<Decimal>().unwrap();
+        let expected = expected.parse::<Decimal>().unwrap();
+        assert_eq!(&a * &b, expected);
+        assert_eq!(&b * &a, expected);
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_mul_small() {
+        let mut rng = Xorshift::default();
+        for _ in 0..1000 {
+            let a = rng.random(-1_000_000_000_000i64..=1_000_000_000_000);
+            let b = rng.random(-1_000_000_000_000i64..=1_000_000_000_000);
+            assert_eq!(
+                Decimal::from(a) * Decimal::from(b),
+                Decimal::from(a as i128 * b as i128)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mul_ntt() {
+        let mut rng = Xorshift::default();
+        for _ in 0..20 {
+            let n = rng.random(1usize..=300);
+            let m = rng.random(1usize..=300);
+            let a: Vec<u64> = rng.random_iter(0..RADIX).take(n).collect();
+            let b: Vec<u64> = rng.random_iter(0..RADIX).take(m).collect();
+            let mut c = mul_ntt(&a, &b);
+            c.resize(n + m, 0);
+            assert_eq!(c, mul_naive(&a, &b));
+        }
+    }
+
+    #[test_case("0", 0, "1"; "zero to zero")]
+    #[test_case("0", 3, "0"; "zero")]
+    #[test_case("-1.5", 3, "-3.375"; "minus")]
+    #[test_case("10", 40, "10000000000000000000000000000000000000000"; "large")]
+    #[test_case("0.1", 20, "0.00000000000000000001"; "small")]
+    fn test_pow(a: &str, exp: usize, expected: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        let expected = expected.parse::<Decimal>().unwrap();
+        assert_eq!(a.pow(exp), expected);
+    }
+}
@@ -0,0 +1,188 @@
+use super::{
+    div::{div_small, mul_small},
+    *,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// toward zero
+    Down,
+    /// away from zero
+    Up,
+    /// toward negative infinity
+    Floor,
+    /// toward positive infinity
+    Ceiling,
+    /// to nearest, ties away from zero
+    HalfUp,
+    /// to nearest, ties toward zero
+    HalfDown,
+    /// to nearest, ties to even
+    HalfEven,
+}
+
+fn add_one(limbs: &mut Vec<u64>) {
+    for x in limbs.iter_mut() {
+        *x += 1;
+        if *x < RADIX {
+            return;
+        }
+        *x = 0;
+    }
+    limbs.push(1);
+}
+
+/// Round `limbs * RADIX^-scale` to `precision` fractional digits.
+///
+/// `sticky` means that the true value is slightly larger than `limbs * RADIX^-scale` in absolute value.
+pub(super) fn round_scaled(
+    sign: Sign,
+    mut limbs: Vec<u64>,
+    scale: usize,
+    precision: usize,
+    mode: RoundingMode,
+    sticky: bool,
+) -> Decimal {
+    let digits = scale * RADIX_LEN;
+    debug_assert!(precision < digits || !sticky);
+    if digits <= precision {
+        return Decimal::from_scaled(sign, limbs, scale);
+    }
+    let drop = digits - precision;
+    let (w, s) = (drop / RADIX_LEN, drop % RADIX_LEN);
+    let mut q = if limbs.len() > w {
+        limbs.split_off(w)
+    } else {
+        vec![]
+    };
+    let low = limbs;
+    let low_nonzero = sticky || low.iter().any(|&x| x != 0);
+    let (half, nonzero) = if s > 0 {
+        let r = div_small(&mut q, POW10[s]);
+        let half = r.cmp(&(POW10[s - 1] * 5)).then(if low_nonzero {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        });
+        (half, r != 0 || low_nonzero)
+    } else {
+        let top = low.get(w - 1).copied().unwrap_or(0);
+        let rest_nonzero = sticky || low.iter().take(w - 1).any(|&x| x != 0);
+        let half = top.cmp(&(RADIX / 2)).then(if rest_nonzero {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        });
+        (half, low_nonzero)
+    };
+    let round_up = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => nonzero,
+        RoundingMode::Floor => nonzero && sign == Sign::Minus,
+        RoundingMode::Ceiling => nonzero && sign == Sign::Plus,
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfDown => half == Ordering::Greater,
+        RoundingMode::HalfEven => {
+            half == Ordering::Greater
+                || half == Ordering::Equal && q.first().is_some_and(|&x| x & 1 == 1)
+        }
+    };
+    if round_up {
+        add_one(&mut q);
+    }
+    let scale = precision.div_ceil(RADIX_LEN);
+    let pad = scale * RADIX_LEN - precision;
+    if pad > 0 {
+        mul_small(&mut q, POW10[pad]);
+    }
+    Decimal::from_scaled(sign, q, scale)
+}
+
+impl Decimal {
+    /// Round to `precision` fractional digits.
+    pub fn round(&self, precision: usize, mode: RoundingMode) -> Self {
+        let (limbs, scale) = self.to_scaled();
+        round_scaled(self.sign, limbs, scale, precision, mode, false)
+            .inherit_precision(self.precision)
+    }
+
+    pub fn trunc(&self) -> Self {
+        self.round(0, RoundingMode::Down)
+    }
+
+    pub fn floor(&self) -> Self {
+        self.round(0, RoundingMode::Floor)
+    }
+
+    pub fn ceil(&self) -> Self {
+        self.round(0, RoundingMode::Ceiling)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.decimal.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("0", 0, RoundingMode::Up, "0"; "zero")]
+    #[test_case("1.21", 1, RoundingMode::Down, "1.2"; "down")]
+    #[test_case("-1.29", 1, RoundingMode::Down, "-1.2"; "down minus")]
+    #[test_case("1.21", 1, RoundingMode::Up, "1.3"; "up")]
+    #[test_case("-1.21", 1, RoundingMode::Up, "-1.3"; "up minus")]
+    #[test_case("1.2", 1, RoundingMode::Up, "1.2"; "up exact")]
+    #[test_case("1.29", 1, RoundingMode::Floor, "1.2"; "floor")]
+    #[test_case("-1.21", 1, RoundingMode::Floor, "-1.3"; "floor minus")]
+    #[test_case("1.21", 1, RoundingMode::Ceiling, "1.3"; "ceiling")]
+    #[test_case("-1.29", 1, RoundingMode::Ceiling, "-1.2"; "ceiling minus")]
+    #[test_case("1.25", 1, RoundingMode::HalfUp, "1.3"; "half up")]
+    #[test_case("-1.25", 1, RoundingMode::HalfUp, "-1.3"; "half up minus")]
+    #[test_case("1.249", 1, RoundingMode::HalfUp, "1.2"; "half up less")]
+    #[test_case("1.25", 1, RoundingMode::HalfDown, "1.2"; "half down")]
+    #[test_case("1.251", 1, RoundingMode::HalfDown, "1.3"; "half down greater")]
+    #[test_case("1.25", 1, RoundingMode::HalfEven, "1.2"; "half even")]
+    #[test_case("1.35", 1, RoundingMode::HalfEven, "1.4"; "half even odd")]
+    #[test_case("0.5", 0, RoundingMode::HalfEven, "0"; "half even zero")]
+    #[test_case("1.5", 0, RoundingMode::HalfEven, "2"; "half even integer")]
+    #[test_case("999999999999999999.9", 0, RoundingMode::HalfUp, "1000000000000000000"; "carry")]
+    #[test_case(
+        "0.1234567890123456785",
+        18,
+        RoundingMode::HalfUp,
+        "0.123456789012345679";
+        "limb boundary"
+    )]
+    #[test_case(
+        "0.1234567890123456785",
+        18,
+        RoundingMode::HalfEven,
+        "0.123456789012345678";
+        "limb boundary half even"
+    )]
+    #[test_case(
+        "0.1234567890123456785000000000000000001",
+        18,
+        RoundingMode::HalfEven,
+        "0.123456789012345679";
+        "limb boundary greater"
+    )]
+    #[test_case("0.1234", 10, RoundingMode::Up, "0.1234"; "exact")]
+    fn test_round(a: &str, precision: usize, mode: RoundingMode, expected: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        let expected = expected.parse::<Decimal>().unwrap();
+        assert_eq!(a.round(precision, mode), expected);
+    }
+
+    #[test_case("1.5", "1", "1", "2"; "plus")]
+    #[test_case("-1.5", "-1", "-2", "-1"; "minus")]
+    #[test_case("3", "3", "3", "3"; "integer")]
+    fn test_trunc_floor_ceil(a: &str, trunc: &str, floor: &str, ceil: &str) {
+        let a = a.parse::<Decimal>().unwrap();
+        assert_eq!(a.trunc(), trunc.parse().unwrap());
+        assert_eq!(a.floor(), floor.parse().unwrap());
+        assert_eq!(a.ceil(), ceil.parse().unwrap());
+    }
+}
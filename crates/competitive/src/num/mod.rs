@@ -2,6 +2,11 @@ use crate::tools::IterScan;
 
 #[codesnip::entry("BarrettReduction")]
 pub use self::barrett_reduction::{BarrettReduction, Barrettable};
+#[codesnip::entry("BigInt")]
+pub use self::bigint::{
+    BigInt, BigUint, ParseBigIntError, UnboundedBinaryRepr, UnboundedIntBase, UnboundedSigned,
+    UnboundedUnsigned,
+};
 #[codesnip::entry("bounded")]
 pub use self::bounded::Bounded;
 #[codesnip::entry("Complex")]
@@ -33,6 +38,14 @@ pub use self::zero_one::{One, Zero};
 
 #[cfg_attr(nightly, codesnip::entry("BarrettReduction", include("zero_one")))]
 mod barrett_reduction;
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "BigInt",
+        include("zero_one", "scanner", "ring", "NumberTheoreticTransform", "fastio")
+    )
+)]
+mod bigint;
 #[cfg_attr(nightly, codesnip::entry)]
 mod bounded;
 #[cfg_attr(
@@ -40,7 +53,13 @@ mod bounded;
     codesnip::entry("Complex", include("zero_one", "scanner", "float"))
 )]
 mod complex;
-#[cfg_attr(nightly, codesnip::entry("Decimal", include("zero_one", "scanner")))]
+#[cfg_attr(
+    nightly,
+    codesnip::entry(
+        "Decimal",
+        include("zero_one", "scanner", "ring", "NumberTheoreticTransform")
+    )
+)]
 pub mod decimal;
 #[cfg_attr(nightly, codesnip::entry(include("bounded")))]
 mod discrete_steps;
@@ -0,0 +1,414 @@
+use super::{Monoid, Transducer, UndirectedSparseGraph};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    char_size: usize,
+    /// goto-completed transitions
+    child: Vec<usize>,
+    failure: Vec<usize>,
+    /// nearest proper suffix node (via failure links) where some pattern ends
+    dictionary_suffix: Vec<usize>,
+    depth: Vec<usize>,
+    /// bfs order of nodes
+    order: Vec<usize>,
+    /// first pattern id which ends at the node
+    head: Vec<usize>,
+    /// next pattern id which ends at the same node
+    next_pattern: Vec<usize>,
+    pattern_node: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Build the automaton from non-empty patterns over the alphabet `0..char_size`.
+    pub fn new<I, P>(char_size: usize, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: IntoIterator<Item = usize>,
+    {
+        let mut child = vec![!0; char_size];
+        let mut depth = vec![0usize];
+        let mut head = vec![!0usize];
+        let mut next_pattern = vec![];
+        let mut pattern_node = vec![];
+        for pattern in patterns {
+            let mut node = 0usize;
+            for ch in pattern {
+                assert!(ch < char_size, "character out of range");
+                let index = node * char_size + ch;
+                if child[index] == !0 {
+                    child[index] = depth.len();
+                    child.resize(child.len() + char_size, !0);
+                    depth.push(depth[node] + 1);
+                    head.push(!0);
+                }
+                node = child[index];
+            }
+            assert_ne!(node, 0, "empty pattern");
+            next_pattern.push(head[node]);
+            head[node] = pattern_node.len();
+            pattern_node.push(node);
+        }
+
+        let node_size = depth.len();
+        let mut failure = vec![0usize; node_size];
+        let mut dictionary_suffix = vec![!0usize; node_size];
+        let mut order = Vec::with_capacity(node_size);
+        let mut deq = VecDeque::new();
+        deq.push_back(0usize);
+        while let Some(node) = deq.pop_front() {
+            order.push(node);
+            let f = failure[node];
+            for ch in 0..char_size {
+                let index = node * char_size + ch;
+                let nnode = child[index];
+                let fnext = if node == 0 {
+                    0
+                } else {
+                    child[f * char_size + ch]
+                };
+                if nnode != !0 {
+                    failure[nnode] = fnext;
+                    dictionary_suffix[nnode] = if head[fnext] != !0 {
+                        fnext
+                    } else {
+                        dictionary_suffix[fnext]
+                    };
+                    deq.push_back(nnode);
+                } else {
+                    child[index] = fnext;
+                }
+            }
+        }
+        Self {
+            char_size,
+            child,
+            failure,
+            dictionary_suffix,
+            depth,
+            order,
+            head,
+            next_pattern,
+            pattern_node,
+        }
+    }
+
+    pub fn char_size(&self) -> usize {
+        self.char_size
+    }
+
+    pub fn node_size(&self) -> usize {
+        self.depth.len()
+    }
+
+    pub fn pattern_size(&self) -> usize {
+        self.pattern_node.len()
+    }
+
+    /// Transition of the goto-completed automaton.
+    pub fn next_node(&self, node: usize, ch: usize) -> usize {
+        self.child[node * self.char_size + ch]
+    }
+
+    /// Longest proper suffix of `node` which is a node.
+    pub fn failure(&self, node: usize) -> usize {
+        self.failure[node]
+    }
+
+    /// Longest proper suffix of `node` where some pattern ends, or `None`.
+    pub fn dictionary_suffix(&self, node: usize) -> Option<usize> {
+        let d = self.dictionary_suffix[node];
+        if d == !0 { None } else { Some(d) }
+    }
+
+    /// Length of the string represented by `node`.
+    pub fn depth(&self, node: usize) -> usize {
+        self.depth[node]
+    }
+
+    /// Node where the pattern ends.
+    pub fn pattern_node(&self, id: usize) -> usize {
+        self.pattern_node[id]
+    }
+
+    /// Nodes in BFS order, parents (and failure links) come first.
+    pub fn bfs_order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Whether some pattern is a suffix of `node`.
+    pub fn is_matched(&self, node: usize) -> bool {
+        self.head[node] != !0 || self.dictionary_suffix[node] != !0
+    }
+
+    /// Iterate over pattern ids which end exactly at `node`.
+    pub fn patterns_at(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut id = self.head[node];
+        std::iter::from_fn(move || {
+            if id == !0 {
+                None
+            } else {
+                let res = id;
+                id = self.next_pattern[id];
+                Some(res)
+            }
+        })
+    }
+
+    /// Iterate over pattern ids which are suffixes of `node`, longer first.
+    pub fn matches_at(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut node = if self.head[node] != !0 {
+            node
+        } else {
+            self.dictionary_suffix[node]
+        };
+        let mut id = if node == !0 { !0 } else { self.head[node] };
+        std::iter::from_fn(move || {
+            if id == !0 {
+                return None;
+            }
+            let res = id;
+            id = self.next_pattern[id];
+            if id == !0 {
+                node = self.dictionary_suffix[node];
+                if node != !0 {
+                    id = self.head[node];
+                }
+            }
+            Some(res)
+        })
+    }
+
+    /// Return the node after reading `text` from the root for each position.
+    pub fn trace<I>(&self, text: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut node = 0usize;
+        text.into_iter()
+            .map(|ch| {
+                node = self.next_node(node, ch);
+                node
+            })
+            .collect()
+    }
+
+    /// Return all (pattern id, start position) of occurrences in O(n + matches).
+    pub fn find_all<I>(&self, text: I) -> Vec<(usize, usize)>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut res = vec![];
+        let mut node = 0usize;
+        for (i, ch) in text.into_iter().enumerate() {
+            node = self.next_node(node, ch);
+            for id in self.matches_at(node) {
+                res.push((id, i + 1 - self.depth[self.pattern_node[id]]));
+            }
+        }
+        res
+    }
+
+    /// Return the number of occurrences for each pattern in O(n + nodes + patterns).
+    pub fn count_matches<I>(&self, text: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut count = vec![0usize; self.node_size()];
+        let mut node = 0usize;
+        for ch in text {
+            node = self.next_node(node, ch);
+            count[node] += 1;
+        }
+        for &node in self.order.iter().skip(1).rev() {
+            count[self.failure[node]] += count[node];
+        }
+        self.pattern_node.iter().map(|&node| count[node]).collect()
+    }
+
+    /// Aggregate weights of patterns which are suffixes of each node.
+    pub fn suffix_fold<M>(&self, weights: &[M::T]) -> Vec<M::T>
+    where
+        M: Monoid,
+    {
+        assert_eq!(weights.len(), self.pattern_size());
+        let mut dp = vec![M::unit(); self.node_size()];
+        for (id, &node) in self.pattern_node.iter().enumerate() {
+            M::operate_assign(&mut dp[node], &weights[id]);
+        }
+        for &node in self.order.iter().skip(1) {
+            dp[node] = M::operate(&dp[node], &dp[self.failure[node]]);
+        }
+        dp
+    }
+
+    /// Tree whose edges are `(failure(v), v)` for each non-root node `v`.
+    pub fn failure_tree(&self) -> UndirectedSparseGraph {
+        let edges = (1..self.node_size())
+            .map(|node| (self.failure[node], node))
+            .collect();
+        UndirectedSparseGraph::from_edges(self.node_size(), edges)
+    }
+}
+
+impl Transducer for AhoCorasick {
+    type Input = usize;
+    type Output = usize;
+    type State = usize;
+    fn start(&self) -> Self::State {
+        0
+    }
+    fn relation(
+        &self,
+        state: &Self::State,
+        input: &Self::Input,
+    ) -> Option<(Self::State, Self::Output)> {
+        let nstate = self.next_node(*state, *input);
+        Some((nstate, nstate))
+    }
+    fn accept(&self, _state: &Self::State) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::AdditiveOperation, tools::Xorshift};
+
+    fn random_instance(rng: &mut Xorshift) -> (usize, Vec<Vec<usize>>, Vec<usize>) {
+        let csize = rng.random(1usize..=4);
+        let k = rng.random(1usize..=10);
+        let patterns: Vec<Vec<usize>> = (0..k)
+            .map(|_| {
+                let m = rng.random(1usize..=5);
+                rng.random_iter(0..csize).take(m).collect()
+            })
+            .collect();
+        let n = rng.random(0usize..=100);
+        let text: Vec<usize> = rng.random_iter(0..csize).take(n).collect();
+        (csize, patterns, text)
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut rng = Xorshift::default();
+        for _ in 0..300 {
+            let (csize, patterns, text) = random_instance(&mut rng);
+            let ac = AhoCorasick::new(csize, patterns.iter().cloned());
+            let mut result = ac.find_all(text.iter().cloned());
+            let mut expected = vec![];
+            for (id, pattern) in patterns.iter().enumerate() {
+                for i in 0..text.len() {
+                    if text[i..].starts_with(pattern) {
+                        expected.push((id, i));
+                    }
+                }
+            }
+            result.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(result, expected);
+
+            let count = ac.count_matches(text.iter().cloned());
+            for (id, &count) in count.iter().enumerate() {
+                let expected = expected.iter().filter(|&&(i, _)| i == id).count();
+                assert_eq!(count, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_links() {
+        let mut rng = Xorshift::default();
+        for _ in 0..300 {
+            let (csize, patterns, _) = random_instance(&mut rng);
+            let ac = AhoCorasick::new(csize, patterns.iter().cloned());
+            let mut strings = vec![vec![]; ac.node_size()];
+            for &node in ac.bfs_order() {
+                for ch in 0..csize {
+                    let nnode = ac.next_node(node, ch);
+                    if ac.depth(nnode) == ac.depth(node) + 1 {
+                        let mut s = strings[node].clone();
+                        s.push(ch);
+                        strings[nnode] = s;
+                    }
+                }
+            }
+            for node in 0..ac.node_size() {
+                let s = &strings[node];
+                assert_eq!(s.len(), ac.depth(node));
+                let f = ac.failure(node);
+                let expected = (0..ac.node_size())
+                    .filter(|&v| strings[v].len() < s.len().max(1) && s.ends_with(&strings[v]))
+                    .max_by_key(|&v| strings[v].len())
+                    .unwrap();
+                assert_eq!(f, expected);
+                let weights: Vec<usize> = (0..ac.pattern_size()).map(|id| id + 1).collect();
+                let fold = ac.suffix_fold::<AdditiveOperation<usize>>(&weights);
+                let expected: usize = patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| s.ends_with(p))
+                    .map(|(id, _)| id + 1)
+                    .sum();
+                assert_eq!(fold[node], expected);
+                let mut matches: Vec<_> = ac.matches_at(node).collect();
+                let mut expected: Vec<_> = (0..patterns.len())
+                    .filter(|&id| s.ends_with(&patterns[id]))
+                    .collect();
+                matches.sort_unstable();
+                expected.sort_unstable();
+                assert_eq!(matches, expected);
+                assert_eq!(ac.is_matched(node), !expected.is_empty());
+            }
+            let tree = ac.failure_tree();
+            assert_eq!(tree.edges_size() + 1, ac.node_size());
+        }
+    }
+
+    #[test]
+    fn test_transducer() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let csize = rng.random(1usize..=3);
+            let k = rng.random(1usize..=3);
+            let patterns: Vec<Vec<usize>> = (0..k)
+                .map(|_| {
+                    let m = rng.random(1usize..=3);
+                    rng.random_iter(0..csize).take(m).collect()
+                })
+                .collect();
+            let n = rng.random(0usize..=6);
+            let ac = AhoCorasick::new(csize, patterns.iter().cloned());
+            let matched: Vec<bool> = (0..ac.node_size()).map(|v| ac.is_matched(v)).collect();
+            let avoiding = ac
+                .clone()
+                .retain(|&node| !matched[node])
+                .dp::<AdditiveOperation<usize>>(1)
+                .with_hashmap()
+                .run(|| 0..csize, n);
+            let containing = ac
+                .with_fold(false, |&acc, &node| acc || matched[node])
+                .accepting(|&(_, acc)| acc)
+                .dp::<AdditiveOperation<usize>>(1)
+                .with_hashmap()
+                .run(|| 0..csize, n);
+            let mut expected = 0usize;
+            for mut x in 0..csize.pow(n as u32) {
+                let mut s = vec![];
+                for _ in 0..n {
+                    s.push(x % csize);
+                    x /= csize;
+                }
+                if patterns
+                    .iter()
+                    .all(|p| !s.windows(p.len()).any(|w| w == &p[..]))
+                {
+                    expected += 1;
+                }
+            }
+            assert_eq!(avoiding, expected);
+            assert_eq!(containing, csize.pow(n as u32) - expected);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+/// Lyndon factorization by Duval's algorithm.
+///
+/// Return boundaries `0 = a_0 < a_1 < ... < a_k = n` such that `s[a_i..a_{i+1}]` are Lyndon words in non-increasing order.
+pub fn lyndon_factorization<T>(s: &[T]) -> Vec<usize>
+where
+    T: Ord,
+{
+    let n = s.len();
+    let mut res = vec![0];
+    let mut i = 0;
+    while i < n {
+        let (mut j, mut k) = (i + 1, i);
+        while j < n && s[k] <= s[j] {
+            if s[k] < s[j] {
+                k = i;
+            } else {
+                k += 1;
+            }
+            j += 1;
+        }
+        while i <= k {
+            i += j - k;
+            res.push(i);
+        }
+    }
+    res
+}
+
+/// Return the smallest `i` such that `s[i..] + s[..i]` is the lexicographically minimum rotation.
+pub fn minimum_rotation<T>(s: &[T]) -> usize
+where
+    T: Ord,
+{
+    let n = s.len();
+    let (mut i, mut res) = (0, 0);
+    while i < n {
+        res = i;
+        let (mut j, mut k) = (i + 1, i);
+        while j < n * 2 && s[k % n] <= s[j % n] {
+            if s[k % n] < s[j % n] {
+                k = i;
+            } else {
+                k += 1;
+            }
+            j += 1;
+        }
+        while i <= k {
+            i += j - k;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    fn is_lyndon(s: &[usize]) -> bool {
+        !s.is_empty() && (1..s.len()).all(|i| s < &s[i..])
+    }
+
+    #[test]
+    fn test_lyndon_factorization() {
+        let mut rng = Xorshift::default();
+        for _ in 0..500 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=30);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let f = lyndon_factorization(&s);
+            assert_eq!(f[0], 0);
+            assert_eq!(*f.last().unwrap(), n);
+            for w in f.windows(2) {
+                assert!(is_lyndon(&s[w[0]..w[1]]));
+            }
+            for w in f.windows(3) {
+                assert!(s[w[0]..w[1]] >= s[w[1]..w[2]]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimum_rotation() {
+        let mut rng = Xorshift::default();
+        for _ in 0..500 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(1usize..=30);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let rotate = |i: usize| [&s[i..], &s[..i]].concat();
+            let expected = (0..n).min_by_key(|&i| rotate(i)).unwrap();
+            assert_eq!(minimum_rotation(&s), expected);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/// Longest palindromes for all centers.
+///
+/// Centers are indexed by `0..=2n`: even `2i` is the gap before `s[i]`, odd `2i+1` is `s[i]`.
+#[derive(Clone, Debug)]
+pub struct Manacher {
+    /// length of the longest palindrome for each center
+    len: Vec<usize>,
+}
+impl Manacher {
+    pub fn new<T: Eq>(s: &[T]) -> Self {
+        let m = s.len() * 2 + 1;
+        let eq = |a: usize, b: usize| a & 1 == b & 1 && (a & 1 == 0 || s[a / 2] == s[b / 2]);
+        let mut rad = vec![0usize; m];
+        let (mut i, mut j) = (0, 0);
+        while i < m {
+            while i >= j && i + j < m && eq(i - j, i + j) {
+                j += 1;
+            }
+            rad[i] = j;
+            let mut k = 1;
+            while i >= k && k + rad[i - k] < j {
+                rad[i + k] = rad[i - k];
+                k += 1;
+            }
+            i += k;
+            j -= k;
+        }
+        for r in rad.iter_mut() {
+            *r -= 1;
+        }
+        Self { len: rad }
+    }
+    /// Length of the longest odd palindrome centered at `s[i]`.
+    pub fn odd(&self, i: usize) -> usize {
+        self.len[i * 2 + 1]
+    }
+    /// Length of the longest even palindrome centered between `s[i-1]` and `s[i]`.
+    pub fn even(&self, i: usize) -> usize {
+        self.len[i * 2]
+    }
+    /// Return whether `s[l..r]` is a palindrome.
+    pub fn is_palindrome(&self, l: usize, r: usize) -> bool {
+        assert!(l <= r);
+        r - l <= self.len[l + r]
+    }
+    /// Lengths of the longest palindromes for centers `1..2n`.
+    pub fn lengths(&self) -> &[usize] {
+        let m = self.len.len();
+        if m == 1 { &[] } else { &self.len[1..m - 1] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_manacher() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=60);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let manacher = Manacher::new(&s);
+            let is_palindrome =
+                |l: usize, r: usize| (0..(r - l) / 2).all(|k| s[l + k] == s[r - 1 - k]);
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(manacher.is_palindrome(l, r), is_palindrome(l, r));
+                }
+            }
+            for i in 0..n {
+                let expected = (0..=i.min(n - 1 - i))
+                    .filter(|&k| is_palindrome(i - k, i + k + 1))
+                    .max()
+                    .unwrap();
+                assert_eq!(manacher.odd(i), expected * 2 + 1);
+            }
+            for i in 0..=n {
+                let expected = (0..=i.min(n - i))
+                    .filter(|&k| is_palindrome(i - k, i + k))
+                    .max()
+                    .unwrap();
+                assert_eq!(manacher.even(i), expected * 2);
+            }
+            assert_eq!(manacher.lengths().len(), (n * 2).saturating_sub(1));
+        }
+    }
+}
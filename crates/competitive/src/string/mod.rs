@@ -1,17 +1,28 @@
 //! string algorithms
 
 use crate::algebra::{Gf2_63, Invertible, Mersenne61, Monoid, Ring, SemiRing};
-use crate::data_structure::RangeMinimumQuery;
+use crate::data_structure::{RangeMinimumQuery, Transducer};
+use crate::graph::UndirectedSparseGraph;
 use crate::math::ConvolveRealFft;
 use crate::tools::Xorshift;
 
+#[codesnip::entry("AhoCorasick")]
+pub use self::aho_corasick::AhoCorasick;
 #[codesnip::entry("KnuthMorrisPratt")]
 pub use self::knuth_morris_pratt::KnuthMorrisPratt;
+#[codesnip::entry("lyndon_factorization")]
+pub use self::lyndon_factorization::{lyndon_factorization, minimum_rotation};
+#[codesnip::entry("Manacher")]
+pub use self::manacher::Manacher;
+#[codesnip::entry("PalindromicTree")]
+pub use self::palindromic_tree::PalindromicTree;
 #[codesnip::entry("RollingHash")]
 pub use self::rolling_hash::{
     Gf2_63x1, Gf2_63x2, Gf2_63x3, HashedRangeChained, Mersenne61x1, Mersenne61x2, Mersenne61x3,
     RollingHasher,
 };
+#[codesnip::entry("runs")]
+pub use self::runs::{lyndon_array, runs};
 #[codesnip::entry("StringSearch")]
 pub use self::string_search::{MultipleStringSearch, StringSearch};
 #[codesnip::entry("SuffixArray")]
@@ -25,8 +36,19 @@ pub use self::wildcard_pattern_matching::wildcard_pattern_matching;
 #[codesnip::entry("ZAlgorithm")]
 pub use self::z_algorithm::Zarray;
 
+#[cfg_attr(
+    nightly,
+    codesnip::entry("AhoCorasick", include("algebra", "SparseGraph", "transducer"))
+)]
+mod aho_corasick;
 #[cfg_attr(nightly, codesnip::entry("KnuthMorrisPratt"))]
 mod knuth_morris_pratt;
+#[cfg_attr(nightly, codesnip::entry("lyndon_factorization"))]
+mod lyndon_factorization;
+#[cfg_attr(nightly, codesnip::entry("Manacher"))]
+mod manacher;
+#[cfg_attr(nightly, codesnip::entry("PalindromicTree", include("algebra")))]
+mod palindromic_tree;
 #[cfg_attr(
     nightly,
     codesnip::entry(
@@ -35,6 +57,8 @@ mod knuth_morris_pratt;
     )
 )]
 pub mod rolling_hash;
+#[cfg_attr(nightly, codesnip::entry("runs", include("StringSearch")))]
+mod runs;
 #[cfg_attr(
     nightly,
     codesnip::entry("StringSearch", include("SuffixArray", "RangeMinimumQuery"))
@@ -0,0 +1,397 @@
+use super::Monoid;
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+struct Node {
+    /// `!0` for the imaginary root
+    len: usize,
+    link: usize,
+    /// longest suffix palindrome of `link` preceded by a different character than `link`
+    quick_link: usize,
+    diff: usize,
+    series_link: usize,
+    parent: usize,
+    first_child: usize,
+    sibling: usize,
+    /// end position of the first occurrence
+    end: usize,
+    count: usize,
+}
+
+impl Node {
+    fn new(len: usize, link: usize, parent: usize, end: usize) -> Self {
+        Self {
+            len,
+            link,
+            quick_link: 0,
+            diff: 0,
+            series_link: link,
+            parent,
+            first_child: !0,
+            sibling: !0,
+            end,
+            count: 0,
+        }
+    }
+}
+
+/// Palindromic tree (eertree).
+///
+/// Node `0` is the imaginary root of length `-1` and node `1` is the empty palindrome.
+#[derive(Clone, Debug)]
+pub struct PalindromicTree<T> {
+    text: Vec<T>,
+    nodes: Vec<Node>,
+    /// longest suffix palindrome for each prefix
+    suffix: Vec<usize>,
+}
+
+impl<T> Default for PalindromicTree<T> {
+    fn default() -> Self {
+        Self {
+            text: vec![],
+            nodes: vec![Node::new(!0, 0, !0, !0), Node::new(0, 0, !0, !0)],
+            suffix: vec![1],
+        }
+    }
+}
+
+impl<T> PalindromicTree<T>
+where
+    T: Eq,
+{
+    pub fn new(text: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let mut tree = Self::default();
+        tree.extend(text.iter().cloned());
+        tree
+    }
+
+    pub fn text(&self) -> &[T] {
+        &self.text
+    }
+
+    /// Return the number of nodes including two roots.
+    pub fn node_size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of distinct non-empty palindromes.
+    pub fn number_of_palindromes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Length of the palindrome, `!0` for the imaginary root.
+    pub fn length(&self, node: usize) -> usize {
+        self.nodes[node].len
+    }
+
+    /// Longest proper suffix palindrome.
+    pub fn link(&self, node: usize) -> usize {
+        self.nodes[node].link
+    }
+
+    /// `length(node) - length(link(node))`
+    pub fn diff(&self, node: usize) -> usize {
+        self.nodes[node].diff
+    }
+
+    /// Longest suffix palindrome whose `diff` differs from `diff(node)`.
+    pub fn series_link(&self, node: usize) -> usize {
+        self.nodes[node].series_link
+    }
+
+    /// Node `v` such that `node` is `c v c`.
+    pub fn parent(&self, node: usize) -> usize {
+        self.nodes[node].parent
+    }
+
+    /// Number of prefixes whose longest suffix palindrome is `node`.
+    pub fn count(&self, node: usize) -> usize {
+        self.nodes[node].count
+    }
+
+    /// Range of the first occurrence.
+    pub fn first_occurrence(&self, node: usize) -> Range<usize> {
+        let node = &self.nodes[node];
+        node.end + 1 - node.len..node.end + 1
+    }
+
+    /// Longest suffix palindrome of `text[..i]`.
+    pub fn suffix_palindrome(&self, i: usize) -> usize {
+        self.suffix[i]
+    }
+
+    /// Longest suffix palindrome of the whole text.
+    pub fn last(&self) -> usize {
+        *self.suffix.last().unwrap()
+    }
+
+    /// Node `c node c` if exists.
+    pub fn next(&self, node: usize, c: &T) -> Option<usize> {
+        let mut child = self.nodes[node].first_child;
+        while child != !0 {
+            if &self.text[self.nodes[child].end] == c {
+                return Some(child);
+            }
+            child = self.nodes[child].sibling;
+        }
+        None
+    }
+
+    /// Number of occurrences of each palindrome in the whole text.
+    pub fn occurrences(&self) -> Vec<usize> {
+        let mut occ: Vec<_> = self.nodes.iter().map(|node| node.count).collect();
+        for node in (2..self.nodes.len()).rev() {
+            occ[self.nodes[node].link] += occ[node];
+        }
+        occ
+    }
+
+    fn extendable(&self, node: usize, i: usize) -> bool {
+        let len = self.nodes[node].len.wrapping_add(1);
+        len <= i && self.text[i - len] == self.text[i]
+    }
+
+    /// Longest suffix palindrome `v` of `node` such that `text[i] v text[i]` is a palindrome.
+    ///
+    /// Quick links make this O(log n) regardless of the history of `push` and `pop`.
+    fn find_suffix(&self, mut node: usize, i: usize) -> usize {
+        loop {
+            if self.extendable(node, i) {
+                return node;
+            }
+            let link = self.nodes[node].link;
+            if self.extendable(link, i) {
+                return link;
+            }
+            node = self.nodes[node].quick_link;
+        }
+    }
+
+    pub fn push(&mut self, c: T) -> usize {
+        let i = self.text.len();
+        self.text.push(c);
+        let v = self.find_suffix(self.last(), i);
+        let w = match self.next(v, &self.text[i]) {
+            Some(w) => w,
+            None => {
+                let w = self.nodes.len();
+                let len = self.nodes[v].len.wrapping_add(2);
+                let link = if len == 1 {
+                    1
+                } else {
+                    let u = self.find_suffix(self.nodes[v].link, i);
+                    self.next(u, &self.text[i]).unwrap()
+                };
+                let mut node = Node::new(len, link, v, i);
+                let link_len = self.nodes[link].len;
+                if link_len != 0 {
+                    let next = self.nodes[link].link;
+                    node.quick_link =
+                        if self.text[i - self.nodes[next].len] == self.text[i - link_len] {
+                            self.nodes[link].quick_link
+                        } else {
+                            next
+                        };
+                }
+                node.diff = len - self.nodes[link].len;
+                if node.diff == self.nodes[link].diff {
+                    node.series_link = self.nodes[link].series_link;
+                }
+                node.sibling = self.nodes[v].first_child;
+                self.nodes[v].first_child = w;
+                self.nodes.push(node);
+                w
+            }
+        };
+        self.nodes[w].count += 1;
+        self.suffix.push(w);
+        w
+    }
+
+    /// Remove the last character, rollback of the last `push`.
+    pub fn pop(&mut self) -> Option<T> {
+        let i = self.text.len().checked_sub(1)?;
+        let w = self.suffix.pop().unwrap();
+        self.nodes[w].count -= 1;
+        if self.nodes[w].end == i {
+            debug_assert_eq!(w, self.nodes.len() - 1);
+            let node = self.nodes.pop().unwrap();
+            self.nodes[node.parent].first_child = node.sibling;
+        }
+        self.text.pop()
+    }
+
+    /// `dp[i] = f(fold_{text[j..i]: palindrome} dp[j])`, `dp[0] = init` in O(n log n).
+    ///
+    /// `M` should be commutative.
+    pub fn factorization_dp<M, F>(&self, init: M::T, mut f: F) -> Vec<M::T>
+    where
+        M: Monoid,
+        F: FnMut(M::T) -> M::T,
+    {
+        let n = self.text.len();
+        let mut dp = Vec::with_capacity(n + 1);
+        dp.push(init);
+        let mut series = vec![M::unit(); self.nodes.len()];
+        for i in 1..=n {
+            let mut acc = M::unit();
+            let mut v = self.suffix[i];
+            while v > 1 {
+                let node = &self.nodes[v];
+                let slen = self.nodes[node.series_link].len;
+                series[v] = dp[i - slen - node.diff].clone();
+                if node.diff == self.nodes[node.link].diff {
+                    series[v] = M::operate(&series[v], &series[node.link]);
+                }
+                M::operate_assign(&mut acc, &series[v]);
+                v = node.series_link;
+            }
+            dp.push(f(acc));
+        }
+        dp
+    }
+}
+
+impl<T> Extend<T> for PalindromicTree<T>
+where
+    T: Eq,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for c in iter {
+            self.push(c);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for PalindromicTree<T>
+where
+    T: Eq,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = Self::default();
+        tree.extend(iter);
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algebra::{AdditiveOperation, MinOperation},
+        tools::Xorshift,
+    };
+    use std::collections::HashMap;
+
+    fn is_palindrome(s: &[usize]) -> bool {
+        s.iter().eq(s.iter().rev())
+    }
+
+    fn check(tree: &PalindromicTree<usize>, s: &[usize]) {
+        let n = s.len();
+        let mut occ = HashMap::new();
+        for l in 0..n {
+            for r in l + 1..=n {
+                if is_palindrome(&s[l..r]) {
+                    *occ.entry(&s[l..r]).or_insert(0usize) += 1;
+                }
+            }
+        }
+        assert_eq!(tree.number_of_palindromes(), occ.len());
+        let occurrences = tree.occurrences();
+        for (node, &occurrence) in occurrences.iter().enumerate().skip(2) {
+            let range = tree.first_occurrence(node);
+            assert_eq!(range.len(), tree.length(node));
+            let p = &s[range.clone()];
+            assert!(is_palindrome(p));
+            assert!(!s[..range.end - 1].windows(p.len()).any(|w| w == p));
+            assert_eq!(occurrence, occ[p]);
+            let link = tree.link(node);
+            let expected = (1..p.len())
+                .find(|&k| is_palindrome(&p[k..]))
+                .unwrap_or(p.len());
+            assert_eq!(tree.length(link), p.len() - expected);
+            assert_eq!(tree.diff(node), expected);
+        }
+        for i in 1..=n {
+            let expected = (0..i).find(|&k| is_palindrome(&s[k..i])).unwrap();
+            assert_eq!(tree.length(tree.suffix_palindrome(i)), i - expected);
+        }
+    }
+
+    #[test]
+    fn test_palindromic_tree() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=40);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let tree = PalindromicTree::new(&s);
+            check(&tree, &s);
+        }
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut rng = Xorshift::default();
+        for _ in 0..100 {
+            let csize = rng.random(1usize..=3);
+            let mut tree = PalindromicTree::default();
+            let mut s = vec![];
+            for _ in 0..100 {
+                if s.is_empty() || rng.random(0..3) != 0 {
+                    let c = rng.random(0..csize);
+                    s.push(c);
+                    tree.push(c);
+                } else {
+                    assert_eq!(tree.pop(), s.pop());
+                }
+                check(&tree, &s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_pop_worst_case() {
+        const N: usize = 1_000;
+        let mut tree: PalindromicTree<u8> = std::iter::repeat_n(b'a', N).collect();
+        for _ in 0..N {
+            let w = tree.push(b'b');
+            assert_eq!(tree.length(w), 1);
+            assert_eq!(tree.number_of_palindromes(), N + 1);
+            assert_eq!(tree.pop(), Some(b'b'));
+        }
+        assert_eq!(tree.length(tree.last()), N);
+    }
+
+    #[test]
+    fn test_factorization_dp() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=40);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let tree = PalindromicTree::new(&s);
+            let count = tree.factorization_dp::<AdditiveOperation<u64>, _>(1, |x| x);
+            let min = tree.factorization_dp::<MinOperation<usize>, _>(0, |x| x + 1);
+            let mut ecount = vec![0u64; n + 1];
+            let mut emin = vec![usize::MAX; n + 1];
+            ecount[0] = 1;
+            emin[0] = 0;
+            for i in 1..=n {
+                for j in 0..i {
+                    if is_palindrome(&s[j..i]) {
+                        ecount[i] += ecount[j];
+                        emin[i] = emin[i].min(emin[j] + 1);
+                    }
+                }
+            }
+            assert_eq!(count, ecount);
+            assert_eq!(min, emin);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::StringSearch;
+
+/// Lyndon array: `s[i..res[i]]` is the longest Lyndon word starting at `i`.
+///
+/// If `inverse`, the order of characters is reversed.
+pub fn lyndon_array<T>(search: &StringSearch<T>, inverse: bool) -> Vec<usize>
+where
+    T: Ord,
+{
+    let n = search.text().len();
+    let rank = search.rank();
+    let mut res = vec![n; n];
+    for i in (0..n).rev() {
+        let mut j = i + 1;
+        while j < n {
+            let lcp = search.longest_common_prefix(j..n, i..n);
+            if lcp == n - j || (rank[j] < rank[i]) ^ inverse {
+                break;
+            }
+            j = res[j];
+        }
+        res[i] = j;
+    }
+    res
+}
+
+/// Enumerate all runs (maximal repetitions) in O(n log n).
+///
+/// Return `(p, l, r)` sorted, where `s[l..r]` is a run with the smallest period `p`.
+pub fn runs<T>(s: &[T]) -> Vec<(usize, usize, usize)>
+where
+    T: Ord + Clone,
+{
+    let n = s.len();
+    let search = StringSearch::new(s.to_vec());
+    let rsearch = StringSearch::new(s.iter().rev().cloned().collect());
+    let mut res = vec![];
+    for inverse in [false, true] {
+        let lyndon = lyndon_array(&search, inverse);
+        for (i, &j) in lyndon.iter().enumerate() {
+            let p = j - i;
+            let l = i - rsearch.longest_common_prefix(n - i..n, n - j..n);
+            let r = j + search.longest_common_prefix(i..n, j..n);
+            if r - l >= p * 2 {
+                res.push((p, l, r));
+            }
+        }
+    }
+    res.sort_unstable();
+    res.dedup();
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::Xorshift;
+
+    #[test]
+    fn test_lyndon_array() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=30);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let search = StringSearch::new(s.clone());
+            for inverse in [false, true] {
+                let t: Vec<_> = if inverse {
+                    s.iter().map(|&c| csize - c).collect()
+                } else {
+                    s.clone()
+                };
+                let is_lyndon = |s: &[usize]| (1..s.len()).all(|i| s < &s[i..]);
+                let lyndon = lyndon_array(&search, inverse);
+                for i in 0..n {
+                    let expected = (i + 1..=n).rev().find(|&j| is_lyndon(&t[i..j])).unwrap();
+                    assert_eq!(lyndon[i], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut rng = Xorshift::default();
+        for _ in 0..200 {
+            let csize = rng.random(1usize..=3);
+            let n = rng.random(0usize..=40);
+            let s: Vec<usize> = rng.random_iter(0usize..csize).take(n).collect();
+            let has_period = |l: usize, r: usize, p: usize| (l..r - p).all(|i| s[i] == s[i + p]);
+            let mut expected = vec![];
+            for l in 0..n {
+                for r in l + 1..=n {
+                    let Some(p) = (1..=(r - l) / 2).find(|&p| has_period(l, r, p)) else {
+                        continue;
+                    };
+                    let left = l == 0 || s[l - 1] != s[l - 1 + p];
+                    let right = r == n || s[r] != s[r - p];
+                    if left && right {
+                        expected.push((p, l, r));
+                    }
+                }
+            }
+            expected.sort_unstable();
+            assert_eq!(runs(&s), expected);
+        }
+    }
+}
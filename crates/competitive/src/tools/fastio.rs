@@ -553,6 +553,27 @@ where
         }
     }
 
+    #[inline]
+    unsafe fn write_u64_padded19_unchecked(&mut self, x: u64) {
+        debug_assert!(x < 10_000_000_000_000_000_000);
+        let d = x / 10000;
+        let c = d / 10000;
+        let b = c / 10000;
+        let a = b / 10000;
+        unsafe {
+            ptr::copy_nonoverlapping(
+                (DIGIT4.as_ptr() as *const u8).add(4 * a as usize + 1),
+                self.buf.as_mut_ptr().add(self.pos),
+                3,
+            );
+            self.pos += 3;
+            self.write_digit4_unchecked((b % 10000) as usize);
+            self.write_digit4_unchecked((c % 10000) as usize);
+            self.write_digit4_unchecked((d % 10000) as usize);
+            self.write_digit4_unchecked((x % 10000) as usize);
+        }
+    }
+
     pub fn u8(&mut self, x: u8) {
         self.ensure_capacity(4);
         unsafe { self.write_u8_unchecked(x) }
@@ -621,6 +642,16 @@ where
         }
     }
 
+    /// Write little-endian base $10^{19}$ digits without leading zeros.
+    pub(crate) fn decimal_chunks(&mut self, chunks: &[u64]) {
+        let mut iter = chunks.iter().rev();
+        self.u64(iter.next().copied().unwrap_or(0));
+        for &x in iter {
+            self.ensure_capacity(19);
+            unsafe { self.write_u64_padded19_unchecked(x) }
+        }
+    }
+
     pub fn byte(&mut self, b: u8) {
         self.ensure_capacity(1);
         unsafe { self.write_byte_unchecked(b) }
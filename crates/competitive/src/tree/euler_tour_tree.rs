@@ -0,0 +1,436 @@
+use super::{
+    AbelianMonoid, Allocator, MemoryPool,
+    binary_search_tree::{
+        BstDataAccess, BstDataMutRef, BstImmutRef, BstNode, BstRoot, BstSeeker, BstSpec, EqualSide,
+        data, node::WithParent, seeker::SeekBySize,
+    },
+    splay_operations,
+};
+use std::{cmp::Ordering, collections::HashMap, marker::PhantomData, ptr::NonNull};
+
+struct EulerTourData<M>
+where
+    M: AbelianMonoid,
+{
+    /// `from == to` for vertices and `from -> to` for arcs
+    from: usize,
+    to: usize,
+    value: M::T,
+    agg: M::T,
+    size: usize,
+    vertices: usize,
+    /// [edge mark, vertex mark]
+    mark: [bool; 2],
+    sub_mark: [bool; 2],
+}
+
+impl<M> BstDataAccess<data::marker::Size> for EulerTourData<M>
+where
+    M: AbelianMonoid,
+{
+    type Value = usize;
+
+    fn bst_data(&self) -> &Self::Value {
+        &self.size
+    }
+
+    fn bst_data_mut(&mut self) -> &mut Self::Value {
+        &mut self.size
+    }
+}
+
+struct EulerTourBstSpec<M>(PhantomData<fn() -> M>);
+
+type EulerTourNode<M> = BstNode<EulerTourData<M>, WithParent<EulerTourData<M>>>;
+type EulerTourPtr<M> = NonNull<EulerTourNode<M>>;
+type EulerTourRoot<M> = BstRoot<EulerTourBstSpec<M>>;
+
+impl<M> BstSpec for EulerTourBstSpec<M>
+where
+    M: AbelianMonoid,
+{
+    type Parent = WithParent<Self::Data>;
+    type Data = EulerTourData<M>;
+
+    fn bottom_up(mut node: BstDataMutRef<'_, Self>) {
+        let data = node.reborrow().into_data();
+        let mut agg = data.value.clone();
+        let mut size = 1;
+        let mut vertices = (data.from == data.to) as usize;
+        let mut sub_mark = data.mark;
+        for child in [
+            node.reborrow().left().descend().ok(),
+            node.reborrow().right().descend().ok(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let child = child.into_data();
+            agg = M::operate(&agg, &child.agg);
+            size += child.size;
+            vertices += child.vertices;
+            sub_mark[0] |= child.sub_mark[0];
+            sub_mark[1] |= child.sub_mark[1];
+        }
+        let data = node.data_mut();
+        data.agg = agg;
+        data.size = size;
+        data.vertices = vertices;
+        data.sub_mark = sub_mark;
+    }
+
+    fn merge(left: Option<BstRoot<Self>>, right: Option<BstRoot<Self>>) -> Option<BstRoot<Self>> {
+        let (left, right) = match (left, right) {
+            (Some(left), Some(right)) => (left, right),
+            (left, right) => return left.or(right),
+        };
+        let mut node = left.node;
+        while let Some(child) = unsafe { node.as_ref().child[1] } {
+            node = child;
+        }
+        unsafe { splay_operations::with_parent::splay::<Self, Self::Data>(node) };
+        let mut root = BstRoot::new(node);
+        unsafe { root.borrow_mut().right_mut().set(right) };
+        Self::bottom_up(root.borrow_datamut());
+        Some(root)
+    }
+
+    fn split<Seeker>(
+        node: Option<BstRoot<Self>>,
+        mut seeker: Seeker,
+        equal_side: EqualSide,
+    ) -> (Option<BstRoot<Self>>, Option<BstRoot<Self>>)
+    where
+        Seeker: BstSeeker<Spec = Self>,
+    {
+        let Some(root) = node else {
+            return (None, None);
+        };
+        let mut node = root.node;
+        let ordering = loop {
+            let ordering = seeker.bst_seek(unsafe { BstImmutRef::new_unchecked(node) });
+            let next = match ordering {
+                Ordering::Less => unsafe { node.as_ref().child[1] },
+                Ordering::Equal => None,
+                Ordering::Greater => unsafe { node.as_ref().child[0] },
+            };
+            match next {
+                Some(next) => node = next,
+                None => break ordering,
+            }
+        };
+        unsafe { splay_operations::with_parent::splay::<Self, Self::Data>(node) };
+        let mut root = BstRoot::new(node);
+        if equal_side.goes_left(ordering) {
+            let right = unsafe { root.borrow_mut().right_mut().take() };
+            Self::bottom_up(root.borrow_datamut());
+            (Some(root), right)
+        } else {
+            let left = unsafe { root.borrow_mut().left_mut().take() };
+            Self::bottom_up(root.borrow_datamut());
+            (left, Some(root))
+        }
+    }
+}
+
+/// Dynamic forest on Euler tours in splay trees with component aggregates
+///
+/// Each vertex and edge can hold a mark to find marked ones in a component.
+pub struct EulerTourTree<M>
+where
+    M: AbelianMonoid,
+{
+    vertices: Vec<EulerTourPtr<M>>,
+    arcs: HashMap<(usize, usize), EulerTourPtr<M>>,
+    allocator: MemoryPool<EulerTourNode<M>>,
+}
+
+impl<M> EulerTourTree<M>
+where
+    M: AbelianMonoid,
+{
+    pub fn new(n: usize) -> Self {
+        (0..n).map(|_| M::unit()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    fn allocate(&mut self, from: usize, to: usize, value: M::T) -> EulerTourPtr<M> {
+        self.allocator.allocate(BstNode::new(EulerTourData {
+            from,
+            to,
+            agg: value.clone(),
+            value,
+            size: 1,
+            vertices: (from == to) as usize,
+            mark: [false; 2],
+            sub_mark: [false; 2],
+        }))
+    }
+
+    fn data<'a>(node: EulerTourPtr<M>) -> &'a EulerTourData<M> {
+        unsafe { &(*node.as_ptr()).data }
+    }
+
+    fn data_mut<'a>(node: EulerTourPtr<M>) -> &'a mut EulerTourData<M> {
+        unsafe { &mut (*node.as_ptr()).data }
+    }
+
+    fn pull(node: EulerTourPtr<M>) {
+        unsafe { EulerTourBstSpec::<M>::bottom_up(BstDataMutRef::new_unchecked(node)) };
+    }
+
+    fn splay(node: EulerTourPtr<M>) {
+        unsafe {
+            splay_operations::with_parent::splay::<EulerTourBstSpec<M>, EulerTourData<M>>(node)
+        };
+    }
+
+    fn child(node: EulerTourPtr<M>, dir: usize) -> Option<EulerTourPtr<M>> {
+        unsafe { node.as_ref().child[dir] }
+    }
+
+    /// The root of the tour containing `node`
+    fn root(node: EulerTourPtr<M>) -> EulerTourRoot<M> {
+        Self::splay(node);
+        BstRoot::new(node)
+    }
+
+    /// Split the tour containing `node` into the first `at` arcs and vertices and the rest.
+    fn split_at(
+        node: EulerTourPtr<M>,
+        at: usize,
+    ) -> (Option<EulerTourRoot<M>>, Option<EulerTourRoot<M>>) {
+        EulerTourBstSpec::split(
+            Some(Self::root(node)),
+            SeekBySize::new(at),
+            EqualSide::Right,
+        )
+    }
+
+    /// Rotate the tour to start from `node` and return the root.
+    fn reroot(node: EulerTourPtr<M>) -> EulerTourRoot<M> {
+        let (left, right) = Self::split_at(node, Self::index(node));
+        EulerTourBstSpec::merge(right, left).unwrap()
+    }
+
+    fn index(node: EulerTourPtr<M>) -> usize {
+        Self::splay(node);
+        Self::child(node, 0).map_or(0, |left| Self::data(left).size)
+    }
+
+    pub fn is_connected(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return true;
+        }
+        let (u, v) = (self.vertices[u], self.vertices[v]);
+        Self::splay(u);
+        Self::splay(v);
+        unsafe { u.as_ref().parent.parent.is_some() }
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.arcs.contains_key(&(u, v))
+    }
+
+    /// `u` and `v` must belong to different trees.
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.is_connected(u, v), "already connected");
+        let tu = Self::reroot(self.vertices[u]);
+        let tv = Self::reroot(self.vertices[v]);
+        let uv = self.allocate(u, v, M::unit());
+        let vu = self.allocate(v, u, M::unit());
+        self.arcs.insert((u, v), uv);
+        self.arcs.insert((v, u), vu);
+        let root = EulerTourBstSpec::merge(Some(tu), Some(BstRoot::new(uv)));
+        let root = EulerTourBstSpec::merge(root, Some(tv));
+        EulerTourBstSpec::merge(root, Some(BstRoot::new(vu)));
+    }
+
+    /// `(u, v)` must be an edge.
+    pub fn cut(&mut self, u: usize, v: usize) {
+        let a = self.arcs.remove(&(u, v)).expect("edge not found");
+        let b = self.arcs.remove(&(v, u)).expect("edge not found");
+        let (ia, ib) = (Self::index(a), Self::index(b));
+        let (i, j) = (ia.min(ib), ia.max(ib));
+        // left a middle b right -> middle, left right
+        let (left, rest) = Self::split_at(a, i);
+        let (_, rest) = EulerTourBstSpec::split(rest, SeekBySize::new(1), EqualSide::Right);
+        let (_, rest) = EulerTourBstSpec::split(rest, SeekBySize::new(j - i - 1), EqualSide::Right);
+        let (_, right) = EulerTourBstSpec::split(rest, SeekBySize::new(1), EqualSide::Right);
+        EulerTourBstSpec::merge(left, right);
+        self.allocator.deallocate(a);
+        self.allocator.deallocate(b);
+    }
+
+    /// The number of vertices in the tree containing `v`
+    pub fn component_size(&mut self, v: usize) -> usize {
+        let v = self.vertices[v];
+        Self::splay(v);
+        Self::data(v).vertices
+    }
+
+    /// Fold values in the tree containing `v`
+    pub fn fold(&mut self, v: usize) -> M::T {
+        let v = self.vertices[v];
+        Self::splay(v);
+        Self::data(v).agg.clone()
+    }
+
+    pub fn get(&self, v: usize) -> &M::T {
+        &Self::data(self.vertices[v]).value
+    }
+
+    pub fn set(&mut self, v: usize, x: M::T) {
+        let v = self.vertices[v];
+        Self::splay(v);
+        Self::data_mut(v).value = x;
+        Self::pull(v);
+    }
+
+    fn set_mark(node: EulerTourPtr<M>, kind: usize, mark: bool) {
+        Self::splay(node);
+        Self::data_mut(node).mark[kind] = mark;
+        Self::pull(node);
+    }
+
+    fn find_mark(mut node: EulerTourPtr<M>, kind: usize) -> Option<EulerTourPtr<M>> {
+        Self::splay(node);
+        if !Self::data(node).sub_mark[kind] {
+            return None;
+        }
+        while !Self::data(node).mark[kind] {
+            node = match Self::child(node, 0) {
+                Some(left) if Self::data(left).sub_mark[kind] => left,
+                _ => Self::child(node, 1).unwrap(),
+            };
+        }
+        Self::splay(node);
+        Some(node)
+    }
+
+    /// `(u, v)` must be an edge.
+    pub fn set_edge_mark(&mut self, u: usize, v: usize, mark: bool) {
+        let node = *self.arcs.get(&(u, v)).expect("edge not found");
+        Self::set_mark(node, 0, mark);
+    }
+
+    pub fn set_vertex_mark(&mut self, v: usize, mark: bool) {
+        Self::set_mark(self.vertices[v], 1, mark);
+    }
+
+    /// Find a marked edge in the tree containing `v`.
+    pub fn find_marked_edge(&mut self, v: usize) -> Option<(usize, usize)> {
+        Self::find_mark(self.vertices[v], 0).map(|node| {
+            let data = Self::data(node);
+            (data.from, data.to)
+        })
+    }
+
+    /// Find a marked vertex in the tree containing `v`.
+    pub fn find_marked_vertex(&mut self, v: usize) -> Option<usize> {
+        Self::find_mark(self.vertices[v], 1).map(|node| Self::data(node).from)
+    }
+}
+
+impl<M> FromIterator<M::T> for EulerTourTree<M>
+where
+    M: AbelianMonoid,
+{
+    fn from_iter<T: IntoIterator<Item = M::T>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let mut tree = Self {
+            vertices: Vec::with_capacity(lower),
+            arcs: HashMap::new(),
+            allocator: MemoryPool::with_capacity(lower * 3),
+        };
+        for value in iter {
+            let v = tree.vertices.len();
+            let node = tree.allocate(v, v, value);
+            tree.vertices.push(node);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algebra::AdditiveOperation, data_structure::UnionFind, tools::Xorshift};
+
+    #[test]
+    fn test_euler_tour_tree() {
+        const N: usize = 30;
+        const Q: usize = 5_000;
+        let mut rng = Xorshift::default();
+        for _ in 0..5 {
+            let mut values: Vec<i64> = (0..N).map(|_| rng.random(-100..100)).collect();
+            let mut vmark = [false; N];
+            let mut ett: EulerTourTree<AdditiveOperation<i64>> = values.iter().cloned().collect();
+            let mut edges: Vec<(usize, usize, bool)> = vec![];
+            for _ in 0..Q {
+                let mut uf = UnionFind::new(N);
+                for &(u, v, _) in &edges {
+                    uf.unite(u, v);
+                }
+                let (u, v) = (rng.random(0..N), rng.random(0..N));
+                match rng.random(0..6) {
+                    0 => {
+                        if !uf.same(u, v) {
+                            ett.link(u, v);
+                            edges.push((u, v, false));
+                        }
+                    }
+                    1 if !edges.is_empty() => {
+                        let (u, v, _) = edges.swap_remove(rng.random(0..edges.len()));
+                        if rng.gen_bool(0.5) {
+                            ett.cut(u, v);
+                        } else {
+                            ett.cut(v, u);
+                        }
+                    }
+                    2 => {
+                        let x = rng.random(-100..100);
+                        values[u] = x;
+                        ett.set(u, x);
+                    }
+                    3 => {
+                        vmark[u] = rng.gen_bool(0.5);
+                        ett.set_vertex_mark(u, vmark[u]);
+                        match ett.find_marked_vertex(v) {
+                            Some(w) => assert!(vmark[w] && uf.same(v, w)),
+                            None => assert!((0..N).all(|w| !vmark[w] || !uf.same(v, w))),
+                        }
+                    }
+                    4 if !edges.is_empty() => {
+                        let i = rng.random(0..edges.len());
+                        edges[i].2 = rng.gen_bool(0.5);
+                        let (x, y, mark) = edges[i];
+                        ett.set_edge_mark(x, y, mark);
+                        match ett.find_marked_edge(v) {
+                            Some((x, y)) => {
+                                assert!(uf.same(v, x));
+                                assert!(edges.contains(&(x, y, true)));
+                            }
+                            None => assert!(edges.iter().all(|&(x, _, m)| !m || !uf.same(v, x))),
+                        }
+                    }
+                    _ => {
+                        assert_eq!(ett.is_connected(u, v), uf.same(u, v));
+                        assert_eq!(ett.component_size(u), uf.size(u));
+                        let sum: i64 = (0..N).filter(|&w| uf.same(u, w)).map(|w| values[w]).sum();
+                        assert_eq!(ett.fold(u), sum);
+                        assert_eq!(*ett.get(u), values[u]);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! tree algorithms
 
 use crate::{
-    algebra::{LazyMapMonoid, Magma, Monoid, Unital},
+    algebra::{AbelianMonoid, LazyMapMonoid, Magma, Monoid, Unital},
     data_structure::{
         Allocator, MemoryPool, RangeMinimumQuery, binary_search_tree, splay_operations,
     },
@@ -14,6 +14,8 @@ use crate::{
 pub use self::centroid_decomposition::ContourQueryRange;
 #[codesnip::entry("EulerTour")]
 pub use self::euler_tour::LowestCommonAncestor;
+#[codesnip::entry("EulerTourTree")]
+pub use self::euler_tour_tree::EulerTourTree;
 #[codesnip::entry("tree_generator")]
 pub use self::generator::*;
 #[codesnip::entry("HeavyLightDecomposition")]
@@ -49,6 +51,11 @@ mod depth;
     codesnip::entry("EulerTour", include("RangeMinimumQuery", "SparseGraph", "tree_order"))
 )]
 mod euler_tour;
+#[cfg_attr(
+    nightly,
+    codesnip::entry("EulerTourTree", include("_splay_operations", "Allocator", "algebra"))
+)]
+mod euler_tour_tree;
 #[cfg_attr(
     nightly,
     codesnip::entry("tree_generator", include("SparseGraph", "random_generator"))
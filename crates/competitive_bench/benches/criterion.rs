@@ -6,6 +6,7 @@ mod dynamic_sequence;
 mod fast_io;
 mod gcd;
 mod mint;
+mod ordered_multiset;
 mod special_ring;
 mod xor_linked_tree;
 
@@ -24,6 +25,8 @@ criterion_group!(
         gcd::bench_modinv,
         mint::bench_mod_mul,
         mint::bench_mod_pow,
+        ordered_multiset::bench_ordered_multiset,
+        ordered_multiset::bench_ordered_multiset_fold,
         special_ring::bench_special_ring,
         fast_io::bench_fast_input_u32,
         fast_io::bench_fast_input_u64,
@@ -0,0 +1,174 @@
+use competitive::{
+    algebra::{AdditiveOperation, EmptyAct, EmptyActLazy},
+    data_structure::{SortedBucketList, SplayTree, Treap},
+    tools::Xorshift,
+};
+use criterion::{BatchSize, Criterion};
+use std::hint::black_box;
+
+type CountSum = (AdditiveOperation<usize>, AdditiveOperation<u64>);
+
+#[derive(Clone, Copy)]
+enum Query {
+    Insert { key: u64, x: u64 },
+    Remove { key: u64 },
+    Select { k: usize },
+    Rank { key: u64 },
+    Fold { l: u64, r: u64 },
+}
+
+fn gen_case(n: usize, q: usize, with_fold: bool) -> (Vec<(u64, u64)>, Vec<Query>) {
+    let mut rng = Xorshift::default();
+    let a: Vec<_> = (0..n)
+        .map(|_| (rng.random(0..1 << 40), rng.random(0..1 << 20)))
+        .collect();
+    let mut keys: Vec<_> = a.iter().map(|&(key, _)| key).collect();
+    let mut queries = Vec::with_capacity(q);
+    for _ in 0..q {
+        match rng.random(0..if with_fold { 5 } else { 3 }) {
+            0 => {
+                let key = rng.random(0..1 << 40);
+                let x = rng.random(0..1 << 20);
+                queries.push(Query::Insert { key, x });
+                keys.push(key);
+            }
+            1 if !keys.is_empty() => {
+                let key = keys.swap_remove(rng.random(0..keys.len()));
+                queries.push(Query::Remove { key });
+            }
+            2 if !keys.is_empty() => {
+                let k = rng.random(0..keys.len());
+                queries.push(Query::Select { k });
+            }
+            3 => {
+                let key = rng.random(0..1 << 40);
+                queries.push(Query::Rank { key });
+            }
+            4 => {
+                let l = rng.random(0..1 << 40);
+                let r = rng.random(l..=1 << 40);
+                queries.push(Query::Fold { l, r });
+            }
+            _ => {}
+        }
+    }
+    (a, queries)
+}
+
+fn run_sorted_bucket_list(a: &[(u64, u64)], queries: &[Query]) -> u64 {
+    let mut set: SortedBucketList<u64, AdditiveOperation<u64>> = a.iter().copied().collect();
+    let mut acc = 0u64;
+    for &query in queries {
+        match query {
+            Query::Insert { key, x } => {
+                set.insert(key, x);
+            }
+            Query::Remove { key } => {
+                set.remove(&key);
+            }
+            Query::Select { k } => acc = acc.wrapping_add(*set.select(k).unwrap().1),
+            Query::Rank { key } => acc = acc.wrapping_add(set.rank(&key) as u64),
+            Query::Fold { l, r } => acc = acc.wrapping_add(set.fold(l..r)),
+        }
+    }
+    acc
+}
+
+fn run_treap(a: &[(u64, u64)], queries: &[Query]) -> u64 {
+    let mut set = Treap::<EmptyAct<u64>, EmptyActLazy<CountSum>>::new();
+    for &(key, x) in a {
+        set.insert(key, (1, x));
+    }
+    let mut acc = 0u64;
+    for &query in queries {
+        match query {
+            Query::Insert { key, x } => {
+                set.insert(key, (1, x));
+            }
+            Query::Remove { key } => {
+                if let Some(id) = set.find_by_key(&key) {
+                    set.remove(id);
+                }
+            }
+            Query::Select { k } => {
+                let id = set.find_by_acc_cond(|&(count, _)| count > k).unwrap();
+                acc = acc.wrapping_add(set.get(id).unwrap().1.1);
+            }
+            Query::Rank { key } => acc = acc.wrapping_add(set.range_by_key(..key).fold().0 as u64),
+            Query::Fold { l, r } => acc = acc.wrapping_add(set.range_by_key(l..r).fold().1),
+        }
+    }
+    acc
+}
+
+fn run_splay_tree(a: &[(u64, u64)], queries: &[Query]) -> u64 {
+    let mut set = SplayTree::<u64, u64>::with_capacity(a.len() + queries.len());
+    for &(key, x) in a {
+        set.insert(key, x);
+    }
+    let mut acc = 0u64;
+    for &query in queries {
+        match query {
+            Query::Insert { key, x } => {
+                set.insert(key, x);
+            }
+            Query::Remove { key } => {
+                set.remove(&key);
+            }
+            Query::Select { k } => {
+                if let Some((_, &x)) = set.get_key_value_at(k) {
+                    acc = acc.wrapping_add(x);
+                }
+            }
+            Query::Rank { .. } | Query::Fold { .. } => unreachable!(),
+        }
+    }
+    acc
+}
+
+pub fn bench_ordered_multiset(c: &mut Criterion) {
+    let (a, queries) = gen_case(100_000, 100_000, false);
+    let mut group = c.benchmark_group("ordered_multiset");
+    group.bench_function("sorted_bucket_list", |b| {
+        b.iter_batched(
+            || (a.clone(), queries.clone()),
+            |(a, queries)| black_box(run_sorted_bucket_list(&a, &queries)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("treap", |b| {
+        b.iter_batched(
+            || (a.clone(), queries.clone()),
+            |(a, queries)| black_box(run_treap(&a, &queries)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("splay_tree", |b| {
+        b.iter_batched(
+            || (a.clone(), queries.clone()),
+            |(a, queries)| black_box(run_splay_tree(&a, &queries)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+pub fn bench_ordered_multiset_fold(c: &mut Criterion) {
+    let (a, queries) = gen_case(100_000, 100_000, true);
+    let mut group = c.benchmark_group("ordered_multiset_fold");
+    group.bench_function("sorted_bucket_list", |b| {
+        b.iter_batched(
+            || (a.clone(), queries.clone()),
+            |(a, queries)| black_box(run_sorted_bucket_list(&a, &queries)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("treap", |b| {
+        b.iter_batched(
+            || (a.clone(), queries.clone()),
+            |(a, queries)| black_box(run_treap(&a, &queries)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
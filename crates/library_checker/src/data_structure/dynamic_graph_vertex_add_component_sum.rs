@@ -0,0 +1,81 @@
+use competitive::prelude::*;
+use competitive::{
+    algebra::AdditiveOperation,
+    data_structure::{
+        OfflineDynamicConnectivity, UnionFindBase,
+        union_find::{Undoable, UnionBySize},
+    },
+    graph::DynamicConnectivity,
+};
+
+#[verify::library_checker("dynamic_graph_vertex_add_component_sum")]
+pub fn dynamic_graph_vertex_add_component_sum(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, q, a: [u64; n]);
+    // adding x to a_v is connecting a new vertex of weight x to v
+    let mut weight = a;
+    let mut dc = OfflineDynamicConnectivity::<()>::new();
+    let mut targets = vec![];
+    for _ in 0..q {
+        scan!(scanner, t);
+        match t {
+            0 => {
+                scan!(scanner, u, v);
+                dc.add_edge(u, v);
+            }
+            1 => {
+                scan!(scanner, u, v);
+                dc.remove_edge(u, v);
+            }
+            2 => {
+                scan!(scanner, v, x: u64);
+                dc.add_edge(v, weight.len());
+                weight.push(x);
+            }
+            _ => {
+                scan!(scanner, v);
+                dc.query();
+                targets.push(v);
+            }
+        }
+    }
+    let mut uf = UnionFindBase::<UnionBySize, (), _, (), Undoable>::new_with_merger(
+        weight.len(),
+        |i| weight[i],
+        |x, y| *x += *y,
+    );
+    let mut ans = vec![0u64; targets.len()];
+    dc.solve(&mut uf, |uf, id| ans[id] = *uf.merge_data(targets[id]));
+    iter_print!(writer, @lf @it ans);
+}
+
+#[verify::library_checker("dynamic_graph_vertex_add_component_sum")]
+pub fn dynamic_graph_vertex_add_component_sum_online(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, q, a: [u64; n]);
+    let mut dc: DynamicConnectivity<AdditiveOperation<u64>> = a.into_iter().collect();
+    for _ in 0..q {
+        scan!(scanner, t);
+        match t {
+            0 => {
+                scan!(scanner, u, v);
+                dc.link(u, v);
+            }
+            1 => {
+                scan!(scanner, u, v);
+                dc.cut(u, v);
+            }
+            2 => {
+                scan!(scanner, v, x: u64);
+                let y = *dc.get(v) + x;
+                dc.set(v, y);
+            }
+            _ => {
+                scan!(scanner, v);
+                writeln!(writer, "{}", dc.fold(v)).ok();
+            }
+        }
+    }
+}
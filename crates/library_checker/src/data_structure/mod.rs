@@ -1,11 +1,14 @@
 pub mod deque_operate_all_composite;
+pub mod dynamic_graph_vertex_add_component_sum;
 pub mod dynamic_sequence_range_affine_range_sum;
 pub mod line_add_get_min;
 pub mod majority_voting;
+pub mod persistent_unionfind;
 pub mod point_add_range_sum;
 pub mod point_add_rectangle_sum;
 pub mod point_set_range_composite;
 pub mod point_set_range_frequency;
+pub mod predecessor_problem;
 pub mod queue_operate_all_composite;
 pub mod range_add_range_min;
 pub mod range_affine_point_get;
@@ -0,0 +1,22 @@
+use competitive::data_structure::PersistentUnionFind;
+use competitive::prelude::*;
+
+#[verify::library_checker("persistent_unionfind")]
+pub fn persistent_unionfind(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, q);
+    let mut uf = PersistentUnionFind::new(n);
+    // versions[k + 1]: the graph after the k-th query
+    let mut versions = vec![uf.base()];
+    for _ in 0..q {
+        scan!(scanner, t, k: i64, u, v);
+        let version = versions[(k + 1) as usize];
+        if t == 0 {
+            versions.push(uf.unite(version, u, v));
+        } else {
+            writeln!(writer, "{}", uf.same(version, u, v) as usize).ok();
+            versions.push(version);
+        }
+    }
+}
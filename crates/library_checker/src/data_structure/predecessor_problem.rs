@@ -0,0 +1,31 @@
+use competitive::data_structure::FastSet;
+use competitive::prelude::*;
+
+#[verify::library_checker("predecessor_problem")]
+pub fn predecessor_problem(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, q, t: Bytes);
+    let mut set = FastSet::new(n);
+    set.extend((0..n).filter(|&i| t[i] == b'1'));
+    for _ in 0..q {
+        scan!(scanner, c, k: usize);
+        match c {
+            0 => {
+                set.insert(k);
+            }
+            1 => {
+                set.remove(k);
+            }
+            2 => {
+                writeln!(writer, "{}", set.contains(k) as u8).ok();
+            }
+            3 => {
+                writeln!(writer, "{}", set.next(k).map_or(-1, |i| i as i64)).ok();
+            }
+            _ => {
+                writeln!(writer, "{}", set.prev(k).map_or(-1, |i| i as i64)).ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use competitive::graph::{DirectedSparseGraph, DominatorTreeExt};
+use competitive::prelude::*;
+
+#[verify::library_checker("dominatortree")]
+pub fn dominatortree(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, m, s, edges: [(usize, usize); m]);
+    let graph = DirectedSparseGraph::from_edges(n, edges);
+    let idom = graph.dominator_tree(s).into_idom();
+    iter_print!(writer, @it idom.iter().map(|&p| p.map_or(-1, |p| p as i64)));
+}
@@ -0,0 +1,20 @@
+use competitive::graph::DirectedSparseGraph;
+use competitive::prelude::*;
+
+#[verify::library_checker("eulerian_trail_directed")]
+pub fn eulerian_trail_directed(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, t);
+    for _ in 0..t {
+        scan!(scanner, n, m, edges: [(usize, usize); m]);
+        let graph = DirectedSparseGraph::from_edges(n, edges);
+        if let Some((vs, es)) = graph.eulerian_trail() {
+            writeln!(writer, "Yes").ok();
+            iter_print!(writer, @it vs);
+            iter_print!(writer, @it es);
+        } else {
+            writeln!(writer, "No").ok();
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use competitive::prelude::*;
+use competitive::{
+    data_structure::PersistentLeftistHeap,
+    graph::{DirectedSparseGraph, ShortestPathExt},
+    tools::PartialIgnoredOrd,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    iter::repeat,
+};
+
+#[verify::library_checker("k_shortest_walk")]
+pub fn k_shortest_walk(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, m, s, t, k, edges: [(usize, usize, u64); m]);
+    let rg = DirectedSparseGraph::from_edges(n, edges.iter().map(|&(a, b, _)| (b, a)).collect());
+    let sp = rg
+        .option_sp_additive()
+        .with_parent()
+        .dijkstra([t], &|eid: usize| Some(edges[eid].2));
+    let dist = sp.dist;
+    let mut ans = vec![];
+    if let Some(ds) = dist[s] {
+        // the first tree edge from each vertex towards t
+        let mut tree_edge = vec![None; n];
+        for (eid, &(a, b, c)) in edges.iter().enumerate() {
+            if tree_edge[a].is_none()
+                && sp.parent[a] == Some(b)
+                && dist[b].is_some_and(|db| Some(db + c) == dist[a])
+            {
+                tree_edge[a] = Some(eid);
+            }
+        }
+        let mut children = vec![vec![]; n];
+        for v in 0..n {
+            if let Some(p) = sp.parent[v] {
+                children[p].push(v);
+            }
+        }
+        let mut sidetracks = vec![vec![]; n];
+        for (eid, &(a, b, c)) in edges.iter().enumerate() {
+            if tree_edge[a] == Some(eid) {
+                continue;
+            }
+            if let (Some(da), Some(db)) = (dist[a], dist[b]) {
+                sidetracks[a].push((db + c - da, b));
+            }
+        }
+        // heaps[v]: sidetracks on the shortest path tree from v to t
+        let mut heaps = vec![PersistentLeftistHeap::<(u64, usize)>::new(); n];
+        let mut deq: VecDeque<_> = [t].into_iter().collect();
+        while let Some(u) = deq.pop_front() {
+            let mut heap = match sp.parent[u] {
+                Some(p) => heaps[p].clone(),
+                None => PersistentLeftistHeap::new(),
+            };
+            for &x in &sidetracks[u] {
+                heap = heap.push(x);
+            }
+            heaps[u] = heap;
+            deq.extend(children[u].iter().copied());
+        }
+        ans.push(ds);
+        let mut pq = BinaryHeap::new();
+        if let Some(&(d, _)) = heaps[s].peek() {
+            pq.push(PartialIgnoredOrd(Reverse(ds + d), heaps[s].clone()));
+        }
+        while ans.len() < k {
+            let Some(PartialIgnoredOrd(Reverse(d), heap)) = pq.pop() else {
+                break;
+            };
+            ans.push(d);
+            let (top, rest) = heap.pop().unwrap();
+            if let Some(&(x, _)) = rest.peek() {
+                pq.push(PartialIgnoredOrd(Reverse(d - top.0 + x), rest));
+            }
+            let next = &heaps[top.1];
+            if let Some(&(x, _)) = next.peek() {
+                pq.push(PartialIgnoredOrd(Reverse(d + x), next.clone()));
+            }
+        }
+    }
+    let ans = ans.into_iter().map(|d| d as i64).chain(repeat(-1)).take(k);
+    iter_print!(writer, @lf @it ans);
+}
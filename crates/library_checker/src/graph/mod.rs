@@ -1,10 +1,16 @@
 pub mod assignment;
+pub mod biconnected_components;
 pub mod bipartitematching;
 pub mod chromatic_number;
 pub mod directedmst;
+pub mod dominatortree;
+pub mod eulerian_trail_directed;
+pub mod eulerian_trail_undirected;
 pub mod general_matching;
 pub mod general_weighted_matching;
+pub mod k_shortest_walk;
 pub mod min_cost_b_flow;
 pub mod minimum_spanning_tree;
 pub mod scc;
 pub mod shortest_path;
+pub mod two_edge_connected_components;
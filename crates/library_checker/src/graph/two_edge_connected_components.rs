@@ -0,0 +1,15 @@
+use competitive::graph::{LowLink, UndirectedSparseGraph};
+use competitive::prelude::*;
+
+#[verify::library_checker("two_edge_connected_components")]
+pub fn two_edge_connected_components(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, n, m, edges: [(usize, usize); m]);
+    let graph = UndirectedSparseGraph::from_edges(n, edges);
+    let comp = LowLink::new(&graph).two_edge_connected_components();
+    writeln!(writer, "{}", comp.len()).ok();
+    for vs in comp.into_iter() {
+        iter_print!(writer, vs.len(), @it vs);
+    }
+}
@@ -0,0 +1,11 @@
+use competitive::prelude::*;
+use competitive::string::Manacher;
+
+#[verify::library_checker("enumerate_palindromes")]
+pub fn enumerate_palindromes(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, s: Bytes);
+    let manacher = Manacher::new(&s);
+    iter_print!(writer, @it manacher.lengths());
+}
@@ -0,0 +1,11 @@
+use competitive::prelude::*;
+use competitive::string::lyndon_factorization as lyndon_factorization_library;
+
+#[verify::library_checker("lyndon_factorization")]
+pub fn lyndon_factorization(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, s: Bytes);
+    let f = lyndon_factorization_library(&s);
+    iter_print!(writer, @it f);
+}
@@ -1,4 +1,7 @@
+pub mod enumerate_palindromes;
+pub mod lyndon_factorization;
 pub mod number_of_substrings;
+pub mod runenumerate;
 pub mod suffixarray;
 pub mod wildcard_pattern_matching;
 pub mod zalgorithm;
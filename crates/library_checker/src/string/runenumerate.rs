@@ -0,0 +1,11 @@
+use competitive::prelude::*;
+use competitive::string::runs;
+
+#[verify::library_checker("runenumerate")]
+pub fn runenumerate(reader: impl Read, mut writer: impl Write) {
+    let s = read_all_unchecked(reader);
+    let mut scanner = Scanner::new(&s);
+    scan!(scanner, s: Bytes);
+    let runs = runs(&s);
+    iter_print!(writer, runs.len(); @it2d runs.iter().map(|&(p, l, r)| [p, l, r]));
+}